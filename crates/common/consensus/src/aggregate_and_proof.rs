@@ -0,0 +1,19 @@
+use ream_bls::BLSSignature;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+use crate::attestation::Attestation;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct AggregateAndProof {
+    pub aggregator_index: u64,
+    pub aggregate: Attestation,
+    pub selection_proof: BLSSignature,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SignedAggregateAndProof {
+    pub message: AggregateAndProof,
+    pub signature: BLSSignature,
+}
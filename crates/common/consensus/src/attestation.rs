@@ -1,4 +1,5 @@
-use ream_bls::BLSSignature;
+use anyhow::ensure;
+use ream_bls::{traits::Aggregatable, AggregateSignature, BLSSignature};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{typenum, BitList};
@@ -12,3 +13,190 @@ pub struct Attestation {
     pub data: AttestationData,
     pub signature: BLSSignature,
 }
+
+impl Attestation {
+    /// Check if the participants of ``self`` and ``other`` don't overlap, so their signatures can
+    /// be aggregated.
+    pub fn is_disjoint(&self, other: &Attestation) -> bool {
+        self.aggregation_bits
+            .intersection(&other.aggregation_bits)
+            .is_zero()
+    }
+
+    /// Merge ``other`` into ``self`` by OR-ing the aggregation bits and aggregating the
+    /// signatures.
+    pub fn aggregate(&mut self, other: &Attestation) -> anyhow::Result<()> {
+        ensure!(
+            self.data == other.data,
+            "Can't aggregate attestations with different data"
+        );
+        ensure!(
+            self.aggregation_bits.len() == other.aggregation_bits.len(),
+            "Can't aggregate attestations of different committees"
+        );
+        ensure!(
+            self.is_disjoint(other),
+            "Can't aggregate attestations with overlapping participants"
+        );
+
+        let signature = AggregateSignature::aggregate(&[&self.signature, &other.signature])
+            .map_err(|err| anyhow::anyhow!("Failed to aggregate signatures: {err:?}"))?
+            .to_signature();
+        self.aggregation_bits = self.aggregation_bits.union(&other.aggregation_bits);
+        self.signature = signature;
+        Ok(())
+    }
+}
+
+/// Return the BLS aggregate of the signatures of ``attestations``.
+pub fn get_aggregate_signature(attestations: &[Attestation]) -> anyhow::Result<BLSSignature> {
+    ensure!(!attestations.is_empty(), "No attestations to aggregate");
+    Ok(AggregateSignature::aggregate(
+        &attestations
+            .iter()
+            .map(|attestation| &attestation.signature)
+            .collect::<Vec<_>>(),
+    )
+    .map_err(|err| anyhow::anyhow!("Failed to aggregate signatures: {err:?}"))?
+    .to_signature())
+}
+
+/// Greedily merge ``attestations`` with the same ``AttestationData`` and disjoint participants,
+/// keeping the order in which the aggregates were first seen.
+pub fn aggregate_attestations(attestations: &[Attestation]) -> anyhow::Result<Vec<Attestation>> {
+    let mut aggregates: Vec<Attestation> = vec![];
+    for attestation in attestations {
+        match aggregates.iter_mut().find(|aggregate| {
+            aggregate.data == attestation.data
+                && aggregate.aggregation_bits.len() == attestation.aggregation_bits.len()
+                && aggregate.is_disjoint(attestation)
+        }) {
+            Some(aggregate) => aggregate.aggregate(attestation)?,
+            None => aggregates.push(attestation.clone()),
+        }
+    }
+    Ok(aggregates)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::{traits::Signable, SecretKey};
+    use tree_hash::TreeHash;
+
+    use super::*;
+    use crate::checkpoint::Checkpoint;
+
+    fn attestation_data(slot: u64) -> AttestationData {
+        let checkpoint = Checkpoint {
+            epoch: 0,
+            root: B256::ZERO,
+        };
+        AttestationData {
+            slot,
+            index: 0,
+            beacon_block_root: B256::ZERO,
+            source: checkpoint,
+            target: checkpoint,
+        }
+    }
+
+    /// Return an attestation to ``data`` of the committee members at ``participants``, each signing
+    /// with the secret key ``participant + 1``.
+    fn attestation(data: AttestationData, participants: &[usize]) -> Attestation {
+        let mut aggregation_bits = BitList::with_capacity(8).unwrap();
+        let mut signatures = vec![];
+        for &participant in participants {
+            aggregation_bits.set(participant, true).unwrap();
+            let mut secret_key = [0; 32];
+            secret_key[31] = participant as u8 + 1;
+            signatures.push(
+                SecretKey::from_bytes(&secret_key)
+                    .unwrap()
+                    .sign(data.tree_hash_root().as_ref())
+                    .unwrap(),
+            );
+        }
+        Attestation {
+            aggregation_bits,
+            data,
+            signature: AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>())
+                .unwrap()
+                .to_signature(),
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut aggregate = attestation(attestation_data(1), &[0, 2]);
+        let other = attestation(attestation_data(1), &[1, 5]);
+        assert!(aggregate.is_disjoint(&other));
+
+        aggregate.aggregate(&other).unwrap();
+        assert_eq!(aggregate, attestation(attestation_data(1), &[0, 1, 2, 5]));
+    }
+
+    #[test]
+    fn test_aggregate_rejections() {
+        let mut aggregate = attestation(attestation_data(1), &[0, 2]);
+
+        let overlapping = attestation(attestation_data(1), &[2, 3]);
+        assert!(!aggregate.is_disjoint(&overlapping));
+        assert_eq!(
+            aggregate.aggregate(&overlapping).unwrap_err().to_string(),
+            "Can't aggregate attestations with overlapping participants"
+        );
+        assert_eq!(
+            aggregate
+                .aggregate(&attestation(attestation_data(2), &[1]))
+                .unwrap_err()
+                .to_string(),
+            "Can't aggregate attestations with different data"
+        );
+
+        let mut other_committee = attestation(attestation_data(1), &[1]);
+        other_committee.aggregation_bits = BitList::with_capacity(16).unwrap();
+        assert_eq!(
+            aggregate
+                .aggregate(&other_committee)
+                .unwrap_err()
+                .to_string(),
+            "Can't aggregate attestations of different committees"
+        );
+
+        let mut undecodable = attestation(attestation_data(1), &[1]);
+        undecodable.signature = BLSSignature::default();
+        assert_eq!(
+            aggregate.aggregate(&undecodable).unwrap_err().to_string(),
+            "Failed to aggregate signatures: InvalidSignature"
+        );
+
+        // Rejected attestations leave the aggregate untouched
+        assert_eq!(aggregate, attestation(attestation_data(1), &[0, 2]));
+    }
+
+    #[test]
+    fn test_aggregate_attestations() {
+        let aggregates = aggregate_attestations(&[
+            attestation(attestation_data(2), &[0]),
+            attestation(attestation_data(1), &[0]),
+            attestation(attestation_data(2), &[1]),
+            attestation(attestation_data(2), &[0, 3]),
+            attestation(attestation_data(1), &[4]),
+            attestation(attestation_data(2), &[3]),
+        ])
+        .unwrap();
+
+        // Overlapping attestations start a new aggregate, in the order they were first seen
+        assert_eq!(
+            aggregates,
+            vec![
+                attestation(attestation_data(2), &[0, 1, 3]),
+                attestation(attestation_data(1), &[0, 4]),
+                attestation(attestation_data(2), &[0, 3]),
+            ]
+        );
+        assert!(aggregate_attestations(&[]).unwrap().is_empty());
+    }
+}
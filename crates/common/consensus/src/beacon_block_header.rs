@@ -10,7 +10,7 @@ pub struct SignedBeaconBlockHeader {
     pub signature: BLSSignature,
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct BeaconBlockHeader {
     pub slot: u64,
     pub proposer_index: u64,
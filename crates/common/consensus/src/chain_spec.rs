@@ -0,0 +1,212 @@
+use std::path::Path;
+
+use alloy_primitives::{address, aliases::B32, fixed_bytes, Address};
+use serde::{Deserialize, Serialize};
+
+use crate::fork_choice::helpers::constants::FAR_FUTURE_EPOCH;
+
+/// Runtime configuration values of the consensus specs.
+///
+/// Unlike the preset values in [`EthSpec`](crate::eth_spec::EthSpec), these can differ between
+/// networks that share a preset, so custom devnets can load them from a `config.yaml`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct ChainSpec {
+    // Extends the mainnet preset
+    pub preset_base: String,
+    pub config_name: String,
+
+    // Genesis
+    pub min_genesis_active_validator_count: u64,
+    pub min_genesis_time: u64,
+    pub genesis_fork_version: B32,
+    pub genesis_delay: u64,
+
+    // Forking
+    pub altair_fork_version: B32,
+    pub altair_fork_epoch: u64,
+    pub bellatrix_fork_version: B32,
+    pub bellatrix_fork_epoch: u64,
+    pub capella_fork_version: B32,
+    pub capella_fork_epoch: u64,
+    pub deneb_fork_version: B32,
+    pub deneb_fork_epoch: u64,
+
+    // Time parameters
+    pub seconds_per_slot: u64,
+    pub seconds_per_eth1_block: u64,
+    pub min_validator_withdrawability_delay: u64,
+    pub shard_committee_period: u64,
+    pub eth1_follow_distance: u64,
+
+    // Validator cycle
+    pub inactivity_score_bias: u64,
+    pub inactivity_score_recovery_rate: u64,
+    pub ejection_balance: u64,
+    pub min_per_epoch_churn_limit: u64,
+    pub churn_limit_quotient: u64,
+    pub max_per_epoch_activation_churn_limit: u64,
+
+    // Fork choice
+    pub proposer_score_boost: u64,
+    pub reorg_head_weight_threshold: u64,
+    pub reorg_parent_weight_threshold: u64,
+    pub reorg_max_epochs_since_finalization: u64,
+
+    // Blob
+    pub max_blobs_per_block_electra: u64,
+    #[serde(default)]
+    pub blob_schedule: Vec<BlobScheduleEntry>,
+
+    // Deposit contract
+    pub deposit_chain_id: u64,
+    pub deposit_network_id: u64,
+    pub deposit_contract_address: Address,
+}
+
+/// An entry of ``BLOB_SCHEDULE``, raising the blob limit from ``epoch`` onwards.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct BlobScheduleEntry {
+    pub epoch: u64,
+    pub max_blobs_per_block: u64,
+}
+
+impl ChainSpec {
+    /// Configuration of Ethereum mainnet.
+    pub fn mainnet() -> Self {
+        Self {
+            preset_base: "mainnet".to_string(),
+            config_name: "mainnet".to_string(),
+
+            min_genesis_active_validator_count: 16384,
+            min_genesis_time: 1606824000,
+            genesis_fork_version: fixed_bytes!("0x00000000"),
+            genesis_delay: 604800,
+
+            altair_fork_version: fixed_bytes!("0x01000000"),
+            altair_fork_epoch: 74240,
+            bellatrix_fork_version: fixed_bytes!("0x02000000"),
+            bellatrix_fork_epoch: 144896,
+            capella_fork_version: fixed_bytes!("0x03000000"),
+            capella_fork_epoch: 194048,
+            deneb_fork_version: fixed_bytes!("0x04000000"),
+            deneb_fork_epoch: 269568,
+
+            seconds_per_slot: 12,
+            seconds_per_eth1_block: 14,
+            min_validator_withdrawability_delay: 256,
+            shard_committee_period: 256,
+            eth1_follow_distance: 2048,
+
+            inactivity_score_bias: 4,
+            inactivity_score_recovery_rate: 16,
+            ejection_balance: 16_000_000_000,
+            min_per_epoch_churn_limit: 4,
+            churn_limit_quotient: 65536,
+            max_per_epoch_activation_churn_limit: 8,
+
+            proposer_score_boost: 40,
+            reorg_head_weight_threshold: 20,
+            reorg_parent_weight_threshold: 160,
+            reorg_max_epochs_since_finalization: 2,
+
+            max_blobs_per_block_electra: 9,
+            blob_schedule: vec![
+                BlobScheduleEntry {
+                    epoch: 412672,
+                    max_blobs_per_block: 15,
+                },
+                BlobScheduleEntry {
+                    epoch: 419072,
+                    max_blobs_per_block: 21,
+                },
+            ],
+
+            deposit_chain_id: 1,
+            deposit_network_id: 1,
+            deposit_contract_address: address!("00000000219ab540356cBB839Cbe05303d7705Fa"),
+        }
+    }
+
+    /// Configuration used by the `minimal` preset consensus spec tests.
+    pub fn minimal() -> Self {
+        Self {
+            preset_base: "minimal".to_string(),
+            config_name: "minimal".to_string(),
+
+            min_genesis_active_validator_count: 64,
+            min_genesis_time: 1578009600,
+            genesis_fork_version: fixed_bytes!("0x00000001"),
+            genesis_delay: 300,
+
+            altair_fork_version: fixed_bytes!("0x01000001"),
+            altair_fork_epoch: FAR_FUTURE_EPOCH,
+            bellatrix_fork_version: fixed_bytes!("0x02000001"),
+            bellatrix_fork_epoch: FAR_FUTURE_EPOCH,
+            capella_fork_version: fixed_bytes!("0x03000001"),
+            capella_fork_epoch: FAR_FUTURE_EPOCH,
+            deneb_fork_version: fixed_bytes!("0x04000001"),
+            deneb_fork_epoch: FAR_FUTURE_EPOCH,
+
+            seconds_per_slot: 6,
+            seconds_per_eth1_block: 14,
+            min_validator_withdrawability_delay: 256,
+            shard_committee_period: 64,
+            eth1_follow_distance: 16,
+
+            inactivity_score_bias: 4,
+            inactivity_score_recovery_rate: 16,
+            ejection_balance: 16_000_000_000,
+            min_per_epoch_churn_limit: 2,
+            churn_limit_quotient: 32,
+            max_per_epoch_activation_churn_limit: 4,
+
+            proposer_score_boost: 40,
+            reorg_head_weight_threshold: 20,
+            reorg_parent_weight_threshold: 160,
+            reorg_max_epochs_since_finalization: 2,
+
+            max_blobs_per_block_electra: 9,
+            blob_schedule: vec![],
+
+            deposit_chain_id: 5,
+            deposit_network_id: 5,
+            deposit_contract_address: address!("1234567890123456789012345678901234567890"),
+        }
+    }
+
+    /// Return the fork version at the given ``epoch``.
+    pub fn compute_fork_version(&self, epoch: u64) -> B32 {
+        if epoch >= self.deneb_fork_epoch {
+            self.deneb_fork_version
+        } else if epoch >= self.capella_fork_epoch {
+            self.capella_fork_version
+        } else if epoch >= self.bellatrix_fork_epoch {
+            self.bellatrix_fork_version
+        } else if epoch >= self.altair_fork_epoch {
+            self.altair_fork_version
+        } else {
+            self.genesis_fork_version
+        }
+    }
+
+    /// Return the ``max_blobs_per_block`` of the blob parameters at ``epoch``.
+    ///
+    /// Falls back to ``MAX_BLOBS_PER_BLOCK_ELECTRA`` before the first ``BLOB_SCHEDULE`` entry.
+    pub fn get_max_blobs_per_block(&self, epoch: u64) -> u64 {
+        self.blob_schedule
+            .iter()
+            .filter(|entry| epoch >= entry.epoch)
+            .max_by_key(|entry| entry.epoch)
+            .map_or(self.max_blobs_per_block_electra, |entry| {
+                entry.max_blobs_per_block
+            })
+    }
+
+    /// Load a configuration from a consensus spec `config.yaml` file.
+    pub fn from_yaml_file(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_yaml::from_reader(file)?)
+    }
+}
@@ -2,21 +2,49 @@ use alloy_primitives::B256;
 use ream_bls::BLSSignature;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
+use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
 use super::beacon_block_body::BeaconBlockBody;
+use crate::{
+    beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader},
+    eth_spec::EthSpec,
+};
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
-pub struct SignedBeaconBlock {
-    pub message: BeaconBlock,
+#[serde(bound = "E: EthSpec")]
+pub struct SignedBeaconBlock<E: EthSpec> {
+    pub message: BeaconBlock<E>,
     pub signature: BLSSignature,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
-pub struct BeaconBlock {
+#[serde(bound = "E: EthSpec")]
+pub struct BeaconBlock<E: EthSpec> {
     pub slot: u64,
     pub proposer_index: u64,
     pub parent_root: B256,
     pub state_root: B256,
-    pub body: BeaconBlockBody,
+    pub body: BeaconBlockBody<E>,
+}
+
+impl<E: EthSpec> SignedBeaconBlock<E> {
+    pub fn compute_signed_block_header(&self) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: self.message.block_header(),
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+impl<E: EthSpec> BeaconBlock<E> {
+    pub fn block_header(&self) -> BeaconBlockHeader {
+        BeaconBlockHeader {
+            slot: self.slot,
+            proposer_index: self.proposer_index,
+            parent_root: self.parent_root,
+            state_root: self.state_root,
+            body_root: self.body.tree_hash_root(),
+        }
+    }
 }
@@ -3,7 +3,7 @@ use ream_bls::BLSSignature;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
-    typenum::{U128, U16, U2, U4096},
+    typenum::{U128, U16, U2},
     VariableList,
 };
 use tree_hash_derive::TreeHash;
@@ -12,12 +12,13 @@ use super::execution_payload::ExecutionPayload;
 use crate::{
     attestation::Attestation, attester_slashing::AttesterSlashing,
     bls_to_execution_change::SignedBLSToExecutionChange, deposit::Deposit, eth_1_data::Eth1Data,
-    kzg_commitment::KZGCommitment, proposer_slashing::ProposerSlashing,
+    eth_spec::EthSpec, kzg_commitment::KZGCommitment, proposer_slashing::ProposerSlashing,
     sync_aggregate::SyncAggregate, voluntary_exit::SignedVoluntaryExit,
 };
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
-pub struct BeaconBlockBody {
+#[serde(bound = "E: EthSpec")]
+pub struct BeaconBlockBody<E: EthSpec> {
     pub randao_reveal: BLSSignature,
 
     /// Eth1 data vote
@@ -32,8 +33,8 @@ pub struct BeaconBlockBody {
     pub attestations: VariableList<Attestation, U128>,
     pub deposits: VariableList<Deposit, U16>,
     pub voluntary_exits: VariableList<SignedVoluntaryExit, U16>,
-    pub sync_aggregate: SyncAggregate,
-    pub execution_payload: ExecutionPayload,
+    pub sync_aggregate: SyncAggregate<E>,
+    pub execution_payload: ExecutionPayload<E>,
     pub bls_to_execution_changes: VariableList<SignedBLSToExecutionChange, U16>,
-    pub blob_kzg_commitments: VariableList<KZGCommitment, U4096>,
+    pub blob_kzg_commitments: VariableList<KZGCommitment, E::MaxBlobCommitmentsPerBlock>,
 }
@@ -1,5 +1,6 @@
 use std::{
-    cmp::{max, min},
+    borrow::Cow,
+    cmp::{max, min, Reverse},
     collections::HashSet,
     ops::Deref,
     sync::Arc,
@@ -10,15 +11,12 @@ use anyhow::{anyhow, bail, ensure};
 use ethereum_hashing::{hash, hash_fixed};
 use itertools::Itertools;
 use ream_bls::{
-    traits::{Aggregatable, Verifiable},
-    AggregatePubKey, BLSSignature, PubKey,
+    traits::{Aggregatable, Signable, Verifiable},
+    AggregatePubKey, BLSSignature, DecompressedPubKey, PubKey, SecretKey,
 };
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{
-    typenum::{U1099511627776, U16777216, U2048, U4, U65536, U8192},
-    BitVector, FixedVector, VariableList,
-};
+use ssz_types::{typenum::U4, BitVector, FixedVector, VariableList};
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
@@ -29,38 +27,31 @@ use super::{
     execution_payload_header::ExecutionPayloadHeader,
 };
 use crate::{
+    aggregate_and_proof::AggregateAndProof,
     attestation::Attestation,
     attestation_data::AttestationData,
     attester_slashing::AttesterSlashing,
     beacon_block_header::BeaconBlockHeader,
     bls_to_execution_change::SignedBLSToExecutionChange,
+    chain_spec::ChainSpec,
     checkpoint::Checkpoint,
     deposit::Deposit,
     deposit_message::DepositMessage,
+    eth_1_block::Eth1Block,
     eth_1_data::Eth1Data,
+    eth_spec::EthSpec,
     execution_engine::{engine_trait::ExecutionApi, new_payload_request::NewPayloadRequest},
     fork::Fork,
     fork_choice::helpers::constants::{
-        BASE_REWARD_FACTOR, BLS_WITHDRAWAL_PREFIX, CAPELLA_FORK_VERSION, CHURN_LIMIT_QUOTIENT,
-        DEPOSIT_CONTRACT_TREE_DEPTH, DOMAIN_BEACON_ATTESTER, DOMAIN_BEACON_PROPOSER,
-        DOMAIN_BLS_TO_EXECUTION_CHANGE, DOMAIN_DEPOSIT, DOMAIN_RANDAO, DOMAIN_SYNC_COMMITTEE,
-        DOMAIN_VOLUNTARY_EXIT, EFFECTIVE_BALANCE_INCREMENT, EJECTION_BALANCE,
-        EPOCHS_PER_ETH1_VOTING_PERIOD, EPOCHS_PER_HISTORICAL_VECTOR, EPOCHS_PER_SLASHINGS_VECTOR,
-        EPOCHS_PER_SYNC_COMMITTEE_PERIOD, ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH,
-        GENESIS_EPOCH, GENESIS_SLOT, HYSTERESIS_DOWNWARD_MULTIPLIER, HYSTERESIS_QUOTIENT,
-        HYSTERESIS_UPWARD_MULTIPLIER, INACTIVITY_PENALTY_QUOTIENT_ALTAIR, INACTIVITY_SCORE_BIAS,
-        INACTIVITY_SCORE_RECOVERY_RATE, JUSTIFICATION_BITS_LENGTH, MAX_BLOBS_PER_BLOCK,
-        MAX_COMMITTEES_PER_SLOT, MAX_DEPOSITS, MAX_EFFECTIVE_BALANCE,
-        MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT, MAX_RANDOM_BYTE,
-        MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP, MAX_WITHDRAWALS_PER_PAYLOAD,
-        MIN_ATTESTATION_INCLUSION_DELAY, MIN_EPOCHS_TO_INACTIVITY_PENALTY,
-        MIN_GENESIS_ACTIVE_VALIDATOR_COUNT, MIN_GENESIS_TIME, MIN_PER_EPOCH_CHURN_LIMIT,
-        MIN_SEED_LOOKAHEAD, MIN_SLASHING_PENALTY_QUOTIENT, MIN_VALIDATOR_WITHDRAWABILITY_DELAY,
-        PARTICIPATION_FLAG_WEIGHTS, PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX,
-        PROPOSER_REWARD_QUOTIENT, PROPOSER_WEIGHT, SECONDS_PER_SLOT, SHARD_COMMITTEE_PERIOD,
-        SLOTS_PER_EPOCH, SLOTS_PER_HISTORICAL_ROOT, SYNC_COMMITTEE_SIZE, SYNC_REWARD_WEIGHT,
-        TARGET_COMMITTEE_SIZE, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX,
-        TIMELY_TARGET_FLAG_INDEX, WEIGHT_DENOMINATOR, WHISTLEBLOWER_REWARD_QUOTIENT,
+        BLS_WITHDRAWAL_PREFIX, DEPOSIT_CONTRACT_TREE_DEPTH, DOMAIN_AGGREGATE_AND_PROOF,
+        DOMAIN_BEACON_ATTESTER, DOMAIN_BEACON_PROPOSER, DOMAIN_BLS_TO_EXECUTION_CHANGE,
+        DOMAIN_CONTRIBUTION_AND_PROOF, DOMAIN_DEPOSIT, DOMAIN_RANDAO, DOMAIN_SELECTION_PROOF,
+        DOMAIN_SYNC_COMMITTEE, DOMAIN_SYNC_COMMITTEE_SELECTION_PROOF, DOMAIN_VOLUNTARY_EXIT,
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH, GENESIS_EPOCH, GENESIS_SLOT,
+        JUSTIFICATION_BITS_LENGTH, MAX_RANDOM_BYTE, PARTICIPATION_FLAG_WEIGHTS, PROPOSER_WEIGHT,
+        SYNC_COMMITTEE_SUBNET_COUNT, SYNC_REWARD_WEIGHT, TARGET_AGGREGATORS_PER_COMMITTEE,
+        TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX,
+        WEIGHT_DENOMINATOR,
     },
     helpers::xor,
     historical_summary::HistoricalSummary,
@@ -69,19 +60,22 @@ use crate::{
     misc::{
         compute_activation_exit_epoch, compute_committee, compute_domain, compute_epoch_at_slot,
         compute_shuffled_index, compute_signing_root, compute_start_slot_at_epoch,
-        is_sorted_and_unique,
+        compute_sync_committee_period, is_sorted_and_unique,
     },
     predicates::is_slashable_attestation_data,
     proposer_slashing::ProposerSlashing,
+    pubkey_cache::PubkeyCache,
     sync_aggregate::SyncAggregate,
     sync_committee::SyncCommittee,
-    validator::Validator,
+    sync_committee_contribution::{ContributionAndProof, SyncAggregatorSelectionData},
+    validator::{Validator, ValidatorStatus},
     voluntary_exit::SignedVoluntaryExit,
     withdrawal::Withdrawal,
 };
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
-pub struct BeaconState {
+#[serde(bound = "E: EthSpec")]
+pub struct BeaconState<E: EthSpec> {
     // Versioning
     pub genesis_time: u64,
     pub genesis_validators_root: B256,
@@ -90,29 +84,29 @@ pub struct BeaconState {
 
     // History
     pub latest_block_header: BeaconBlockHeader,
-    pub block_roots: FixedVector<B256, U8192>,
-    pub state_roots: FixedVector<B256, U8192>,
+    pub block_roots: FixedVector<B256, E::SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, E::SlotsPerHistoricalRoot>,
     /// Frozen in Capella, replaced by historical_summaries
-    pub historical_roots: VariableList<B256, U16777216>,
+    pub historical_roots: VariableList<B256, E::HistoricalRootsLimit>,
 
     // Eth1
     pub eth1_data: Eth1Data,
-    pub eth1_data_votes: VariableList<Eth1Data, U2048>,
+    pub eth1_data_votes: VariableList<Eth1Data, E::SlotsPerEth1VotingPeriod>,
     pub eth1_deposit_index: u64,
 
     // Registry
-    pub validators: VariableList<Validator, U1099511627776>,
-    pub balances: VariableList<u64, U1099511627776>,
+    pub validators: VariableList<Validator, E::ValidatorRegistryLimit>,
+    pub balances: VariableList<u64, E::ValidatorRegistryLimit>,
 
     // Randomness
-    pub randao_mixes: FixedVector<B256, U65536>,
+    pub randao_mixes: FixedVector<B256, E::EpochsPerHistoricalVector>,
 
     // Slashings
-    pub slashings: FixedVector<u64, U8192>,
+    pub slashings: FixedVector<u64, E::EpochsPerSlashingsVector>,
 
     // Participation
-    pub previous_epoch_participation: VariableList<u8, U1099511627776>,
-    pub current_epoch_participation: VariableList<u8, U1099511627776>,
+    pub previous_epoch_participation: VariableList<u8, E::ValidatorRegistryLimit>,
+    pub current_epoch_participation: VariableList<u8, E::ValidatorRegistryLimit>,
 
     // Finality
     pub justification_bits: BitVector<U4>,
@@ -121,11 +115,11 @@ pub struct BeaconState {
     pub finalized_checkpoint: Checkpoint,
 
     // Inactivity
-    pub inactivity_scores: VariableList<u64, U1099511627776>,
+    pub inactivity_scores: VariableList<u64, E::ValidatorRegistryLimit>,
 
     // Sync
-    pub current_sync_committee: Arc<SyncCommittee>,
-    pub next_sync_committee: Arc<SyncCommittee>,
+    pub current_sync_committee: Arc<SyncCommittee<E>>,
+    pub next_sync_committee: Arc<SyncCommittee<E>>,
 
     // Execution
     pub latest_execution_payload_header: ExecutionPayloadHeader,
@@ -135,13 +129,19 @@ pub struct BeaconState {
     pub next_withdrawal_validator_index: u64,
 
     // Deep history valid from Capella onwards.
-    pub historical_summaries: VariableList<HistoricalSummary, U16777216>,
+    pub historical_summaries: VariableList<HistoricalSummary, E::HistoricalRootsLimit>,
+
+    // Caches
+    #[serde(skip)]
+    #[ssz(skip_serializing, skip_deserializing)]
+    #[tree_hash(skip_hashing)]
+    pub pubkey_cache: Arc<PubkeyCache>,
 }
 
-impl BeaconState {
+impl<E: EthSpec> BeaconState<E> {
     /// Return the current epoch.
     pub fn get_current_epoch(&self) -> u64 {
-        compute_epoch_at_slot(self.slot)
+        compute_epoch_at_slot::<E>(self.slot)
     }
 
     /// Return the previous epoch (unless the current epoch is ``GENESIS_EPOCH``).
@@ -156,21 +156,21 @@ impl BeaconState {
 
     /// Return the block root at the start of a recent ``epoch``.
     pub fn get_block_root(&self, epoch: u64) -> anyhow::Result<B256> {
-        self.get_block_root_at_slot(compute_start_slot_at_epoch(epoch))
+        self.get_block_root_at_slot(compute_start_slot_at_epoch::<E>(epoch))
     }
 
     /// Return the block root at a recent ``slot``.
     pub fn get_block_root_at_slot(&self, slot: u64) -> anyhow::Result<B256> {
         ensure!(
-            slot < self.slot && self.slot <= slot + SLOTS_PER_HISTORICAL_ROOT,
+            slot < self.slot && self.slot <= slot + E::SLOTS_PER_HISTORICAL_ROOT,
             "slot given was outside of block_roots range"
         );
-        Ok(self.block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT) as usize])
+        Ok(self.block_roots[(slot % E::SLOTS_PER_HISTORICAL_ROOT) as usize])
     }
 
     /// Return the randao mix at a recent ``epoch``.
     pub fn get_randao_mix(&self, epoch: u64) -> B256 {
-        self.randao_mixes[(epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize]
+        self.randao_mixes[(epoch % E::EPOCHS_PER_HISTORICAL_VECTOR) as usize]
     }
 
     /// Return the sequence of active validator indices at ``epoch``.
@@ -189,18 +189,18 @@ impl BeaconState {
     }
 
     /// Return the validator churn limit for the current epoch.
-    pub fn get_validator_churn_limit(&self) -> u64 {
+    pub fn get_validator_churn_limit(&self, spec: &ChainSpec) -> u64 {
         let active_validator_indices = self.get_active_validator_indices(self.get_current_epoch());
         max(
-            MIN_PER_EPOCH_CHURN_LIMIT,
-            active_validator_indices.len() as u64 / CHURN_LIMIT_QUOTIENT,
+            spec.min_per_epoch_churn_limit,
+            active_validator_indices.len() as u64 / spec.churn_limit_quotient,
         )
     }
 
     /// Return the seed at ``epoch``.
     pub fn get_seed(&self, epoch: u64, domain_type: B32) -> B256 {
-        let mix =
-            self.get_randao_mix(epoch + EPOCHS_PER_HISTORICAL_VECTOR - MIN_SEED_LOOKAHEAD - 1);
+        let mix = self
+            .get_randao_mix(epoch + E::EPOCHS_PER_HISTORICAL_VECTOR - E::MIN_SEED_LOOKAHEAD - 1);
         let epoch_with_index =
             [domain_type.as_slice(), &epoch.to_le_bytes(), mix.as_slice()].concat();
         B256::from(hash_fixed(&epoch_with_index))
@@ -209,9 +209,9 @@ impl BeaconState {
     /// Return the number of committees in each slot for the given ``epoch``.
     pub fn get_committee_count_per_slot(&self, epoch: u64) -> u64 {
         (self.get_active_validator_indices(epoch).len() as u64
-            / SLOTS_PER_EPOCH
-            / TARGET_COMMITTEE_SIZE)
-            .clamp(1, MAX_COMMITTEES_PER_SLOT)
+            / E::SLOTS_PER_EPOCH
+            / E::TARGET_COMMITTEE_SIZE)
+            .clamp(1, E::MAX_COMMITTEES_PER_SLOT)
     }
 
     /// Return from ``indices`` a random index sampled by effective balance
@@ -222,7 +222,7 @@ impl BeaconState {
         let total = indices.len();
 
         loop {
-            let candidate_index = indices[compute_shuffled_index(i % total, total, seed)?];
+            let candidate_index = indices[compute_shuffled_index::<E>(i % total, total, seed)?];
 
             let seed_with_index = [seed.as_slice(), &(i / 32).to_le_bytes()].concat();
             let hash = hash(&seed_with_index);
@@ -230,7 +230,8 @@ impl BeaconState {
 
             let effective_balance = self.validators[candidate_index as usize].effective_balance;
 
-            if (effective_balance * MAX_RANDOM_BYTE) >= (MAX_EFFECTIVE_BALANCE * random_byte as u64)
+            if (effective_balance * MAX_RANDOM_BYTE)
+                >= (E::MAX_EFFECTIVE_BALANCE * random_byte as u64)
             {
                 return Ok(candidate_index);
             }
@@ -258,7 +259,7 @@ impl BeaconState {
     /// Math safe up to ~10B ETH, after which this overflows uint64.
     pub fn get_total_balance(&self, indices: HashSet<u64>) -> u64 {
         max(
-            EFFECTIVE_BALANCE_INCREMENT,
+            E::EFFECTIVE_BALANCE_INCREMENT,
             indices
                 .iter()
                 .map(|index| self.validators[*index as usize].effective_balance)
@@ -290,23 +291,186 @@ impl BeaconState {
         };
         compute_domain(
             domain_type,
-            Some(fork_version),
+            fork_version,
             Some(self.genesis_validators_root),
         )
     }
 
     /// Return the beacon committee at ``slot`` for ``index``.
     pub fn get_beacon_committee(&self, slot: u64, index: u64) -> anyhow::Result<Vec<u64>> {
-        let epoch = compute_epoch_at_slot(slot);
+        let epoch = compute_epoch_at_slot::<E>(slot);
         let committees_per_slot = self.get_committee_count_per_slot(epoch);
-        compute_committee(
+        compute_committee::<E>(
             &self.get_active_validator_indices(epoch),
             self.get_seed(epoch, DOMAIN_BEACON_ATTESTER),
-            (slot % SLOTS_PER_EPOCH) * committees_per_slot + index,
-            committees_per_slot * SLOTS_PER_EPOCH,
+            (slot % E::SLOTS_PER_EPOCH) * committees_per_slot + index,
+            committees_per_slot * E::SLOTS_PER_EPOCH,
         )
     }
 
+    /// Return the signing root of the selection proof of a validator at ``slot``.
+    pub fn get_slot_signing_root(&self, slot: u64) -> B256 {
+        let domain = self.get_domain(
+            DOMAIN_SELECTION_PROOF,
+            Some(compute_epoch_at_slot::<E>(slot)),
+        );
+        compute_signing_root(slot, domain)
+    }
+
+    /// Return the selection proof of the validator with ``privkey`` at ``slot``.
+    pub fn get_slot_signature(
+        &self,
+        slot: u64,
+        privkey: &SecretKey,
+    ) -> anyhow::Result<BLSSignature> {
+        privkey
+            .sign(self.get_slot_signing_root(slot).as_ref())
+            .map_err(|err| anyhow!("Failed to sign slot {slot}: {err:?}"))
+    }
+
+    /// Check if the validator with ``slot_signature`` is an aggregator of the committee at
+    /// ``slot`` for ``index``.
+    pub fn is_aggregator(
+        &self,
+        slot: u64,
+        index: u64,
+        slot_signature: &BLSSignature,
+    ) -> anyhow::Result<bool> {
+        let committee = self.get_beacon_committee(slot, index)?;
+        let modulo = max(1, committee.len() as u64 / TARGET_AGGREGATORS_PER_COMMITTEE);
+        let signature_hash = hash(slot_signature.to_bytes());
+        Ok(u64::from_le_bytes(signature_hash[0..8].try_into()?) % modulo == 0)
+    }
+
+    /// Return the signing root of ``aggregate_and_proof``.
+    pub fn get_aggregate_and_proof_signing_root(
+        &self,
+        aggregate_and_proof: &AggregateAndProof,
+    ) -> B256 {
+        let domain = self.get_domain(
+            DOMAIN_AGGREGATE_AND_PROOF,
+            Some(compute_epoch_at_slot::<E>(
+                aggregate_and_proof.aggregate.data.slot,
+            )),
+        );
+        compute_signing_root(aggregate_and_proof, domain)
+    }
+
+    /// Return the sync committee that signs the block at ``state.slot + 1``.
+    fn get_sync_committee_for_next_slot(&self) -> &Arc<SyncCommittee<E>> {
+        let next_slot_epoch = compute_epoch_at_slot::<E>(self.slot + 1);
+        if compute_sync_committee_period::<E>(self.get_current_epoch())
+            == compute_sync_committee_period::<E>(next_slot_epoch)
+        {
+            &self.current_sync_committee
+        } else {
+            &self.next_sync_committee
+        }
+    }
+
+    /// Return the pubkeys of the sync subcommittee ``subcommittee_index``.
+    pub fn get_sync_subcommittee_pubkeys(
+        &self,
+        subcommittee_index: u64,
+    ) -> anyhow::Result<Vec<PubKey>> {
+        ensure!(
+            subcommittee_index < SYNC_COMMITTEE_SUBNET_COUNT,
+            "Invalid sync subcommittee index {subcommittee_index}"
+        );
+        let sync_committee = self.get_sync_committee_for_next_slot();
+        let start = (subcommittee_index * E::SYNC_SUBCOMMITTEE_SIZE) as usize;
+        Ok(sync_committee.pubkeys[start..start + E::SYNC_SUBCOMMITTEE_SIZE as usize].to_vec())
+    }
+
+    /// Return the sync committee subnets of the validator at ``validator_index``.
+    pub fn compute_subnets_for_sync_committee(
+        &self,
+        validator_index: u64,
+    ) -> anyhow::Result<HashSet<u64>> {
+        let target_pubkey = &self
+            .validators
+            .get(validator_index as usize)
+            .ok_or_else(|| anyhow!("Validator index {validator_index} is out of range"))?
+            .pubkey;
+        Ok(self
+            .get_sync_committee_for_next_slot()
+            .pubkeys
+            .iter()
+            .positions(|pubkey| pubkey == target_pubkey)
+            .map(|index| index as u64 / E::SYNC_SUBCOMMITTEE_SIZE)
+            .collect())
+    }
+
+    /// Return the signing root of the sync committee message for ``block_root`` at ``slot``.
+    pub fn get_sync_committee_message_signing_root(&self, slot: u64, block_root: B256) -> B256 {
+        let domain = self.get_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            Some(compute_epoch_at_slot::<E>(slot)),
+        );
+        compute_signing_root(block_root, domain)
+    }
+
+    /// Return the signing root of the sync committee selection proof for ``subcommittee_index``
+    /// at ``slot``.
+    pub fn get_sync_committee_selection_proof_signing_root(
+        &self,
+        slot: u64,
+        subcommittee_index: u64,
+    ) -> B256 {
+        let domain = self.get_domain(
+            DOMAIN_SYNC_COMMITTEE_SELECTION_PROOF,
+            Some(compute_epoch_at_slot::<E>(slot)),
+        );
+        compute_signing_root(
+            SyncAggregatorSelectionData {
+                slot,
+                subcommittee_index,
+            },
+            domain,
+        )
+    }
+
+    /// Return the signing root of ``contribution_and_proof``.
+    pub fn get_contribution_and_proof_signing_root(
+        &self,
+        contribution_and_proof: &ContributionAndProof<E>,
+    ) -> B256 {
+        let domain = self.get_domain(
+            DOMAIN_CONTRIBUTION_AND_PROOF,
+            Some(compute_epoch_at_slot::<E>(
+                contribution_and_proof.contribution.slot,
+            )),
+        );
+        compute_signing_root(contribution_and_proof, domain)
+    }
+
+    /// Return the decompressed public key of the validator at ``index``, borrowed from the pubkey
+    /// cache when the cache holds it.
+    pub fn get_validator_pubkey(&self, index: u64) -> anyhow::Result<Cow<'_, DecompressedPubKey>> {
+        let validator = self
+            .validators
+            .get(index as usize)
+            .ok_or(anyhow!("Invalid validator index"))?;
+
+        match self
+            .pubkey_cache
+            .get_matching(index as usize, &validator.pubkey)
+        {
+            Some(pubkey) => Ok(Cow::Borrowed(pubkey)),
+            None => Ok(Cow::Owned(
+                DecompressedPubKey::try_from(&validator.pubkey)
+                    .map_err(|err| anyhow!("Invalid validator public key: {err:?}"))?,
+            )),
+        }
+    }
+
+    /// Add the validators that joined since the last update to the pubkey cache.
+    pub fn update_pubkey_cache(&mut self) {
+        if self.pubkey_cache.len() < self.validators.len() {
+            Arc::make_mut(&mut self.pubkey_cache).update(&self.validators);
+        }
+    }
+
     /// Check if ``indexed_attestation`` is not empty, has sorted and unique indices and has a valid
     /// aggregate signature.
     pub fn is_valid_indexed_attestation(
@@ -329,18 +493,15 @@ impl BeaconState {
         );
         let signing_root = compute_signing_root(&indexed_attestation.data, domain);
 
+        let pubkeys = indices
+            .iter()
+            .map(|&index| self.get_validator_pubkey(index as u64))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
         indexed_attestation
             .signature
-            .fast_aggregate_verify(
-                indices
-                    .iter()
-                    .map(|&index| {
-                        self.validators
-                            .get(index)
-                            .map(|validator| &validator.pubkey)
-                            .ok_or(anyhow!("Invalid index"))
-                    })
-                    .collect::<anyhow::Result<Vec<_>>>()?,
+            .fast_aggregate_verify_decompressed(
+                pubkeys.iter().map(Deref::deref).collect::<Vec<_>>(),
                 signing_root.as_ref(),
             )
             .map_err(|e| anyhow!("Invalid indexed attestation: {:?}", e))
@@ -394,7 +555,7 @@ impl BeaconState {
     }
 
     /// Initiate if validator already initiated exit.
-    pub fn initiate_validator_exit(&mut self, index: u64) {
+    pub fn initiate_validator_exit(&mut self, index: u64, spec: &ChainSpec) {
         if index as usize >= self.validators.len() {
             return;
         }
@@ -420,7 +581,7 @@ impl BeaconState {
             })
             .collect();
 
-        exit_epochs.push(compute_activation_exit_epoch(self.get_current_epoch()));
+        exit_epochs.push(compute_activation_exit_epoch::<E>(self.get_current_epoch()));
         let mut exit_queue_epoch = *exit_epochs.iter().max().unwrap_or(&0);
 
         let exit_queue_churn = self
@@ -429,7 +590,7 @@ impl BeaconState {
             .filter(|v| v.exit_epoch == exit_queue_epoch)
             .count();
 
-        if exit_queue_churn >= self.get_validator_churn_limit() as usize {
+        if exit_queue_churn >= self.get_validator_churn_limit(spec) as usize {
             exit_queue_epoch += 1;
         }
 
@@ -437,7 +598,7 @@ impl BeaconState {
         if let Some(validator) = self.validators.get_mut(index as usize) {
             validator.exit_epoch = exit_queue_epoch;
             validator.withdrawable_epoch =
-                validator.exit_epoch + MIN_VALIDATOR_WITHDRAWABILITY_DELAY;
+                validator.exit_epoch + spec.min_validator_withdrawability_delay;
         }
     }
 
@@ -446,49 +607,50 @@ impl BeaconState {
         &mut self,
         slashed_index: u64,
         whistleblower_index: Option<u64>,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
         let epoch = self.get_current_epoch();
 
         // Initiate validator exit
-        self.initiate_validator_exit(slashed_index);
+        self.initiate_validator_exit(slashed_index, spec);
 
         let validator_effective_balance =
             if let Some(validator) = self.validators.get_mut(slashed_index as usize) {
                 validator.slashed = true;
                 validator.withdrawable_epoch = std::cmp::max(
                     validator.withdrawable_epoch,
-                    epoch + EPOCHS_PER_SLASHINGS_VECTOR,
+                    epoch + E::EPOCHS_PER_SLASHINGS_VECTOR,
                 );
                 validator.effective_balance
             } else {
                 bail!("Validator at index {slashed_index} not found")
             };
         // Add slashed effective balance to the slashings vector
-        self.slashings[(epoch % EPOCHS_PER_SLASHINGS_VECTOR) as usize] +=
+        self.slashings[(epoch % E::EPOCHS_PER_SLASHINGS_VECTOR) as usize] +=
             validator_effective_balance;
         // Decrease validator balance
         self.decrease_balance(
             slashed_index,
-            validator_effective_balance / MIN_SLASHING_PENALTY_QUOTIENT,
+            validator_effective_balance / E::MIN_SLASHING_PENALTY_QUOTIENT,
         );
 
         // Apply proposer and whistleblower rewards
         let proposer_index = self.get_beacon_proposer_index()?;
         let whistleblower_index = whistleblower_index.unwrap_or(proposer_index);
 
-        let whistleblower_reward = validator_effective_balance / WHISTLEBLOWER_REWARD_QUOTIENT;
+        let whistleblower_reward = validator_effective_balance / E::WHISTLEBLOWER_REWARD_QUOTIENT;
         let proposer_reward = whistleblower_reward * PROPOSER_WEIGHT / WEIGHT_DENOMINATOR;
         self.increase_balance(proposer_index, proposer_reward);
         self.increase_balance(whistleblower_index, whistleblower_reward - proposer_reward);
 
         Ok(())
     }
-    pub fn is_valid_genesis_state(&self) -> bool {
-        if self.genesis_time < MIN_GENESIS_TIME {
+    pub fn is_valid_genesis_state(&self, spec: &ChainSpec) -> bool {
+        if self.genesis_time < spec.min_genesis_time {
             return false;
         }
         if self.get_active_validator_indices(GENESIS_EPOCH).len()
-            < MIN_GENESIS_ACTIVE_VALIDATOR_COUNT as usize
+            < spec.min_genesis_active_validator_count as usize
         {
             return false;
         }
@@ -533,7 +695,7 @@ impl BeaconState {
         Ok(filtered_indices)
     }
 
-    pub fn process_inactivity_updates(&mut self) -> anyhow::Result<()> {
+    pub fn process_inactivity_updates(&mut self, spec: &ChainSpec) -> anyhow::Result<()> {
         // Skip the genesis epoch as score updates are based on the previous epoch participation
         if self.get_current_epoch() == GENESIS_EPOCH {
             return Ok(());
@@ -550,13 +712,13 @@ impl BeaconState {
                 self.inactivity_scores[index as usize] -=
                     min(1, self.inactivity_scores[index as usize])
             } else {
-                self.inactivity_scores[index as usize] += INACTIVITY_SCORE_BIAS
+                self.inactivity_scores[index as usize] += spec.inactivity_score_bias
             }
 
             // Decrease the inactivity score of all eligible validators during a leak-free epoch
             if !self.is_in_inactivity_leak() {
                 self.inactivity_scores[index as usize] -= min(
-                    INACTIVITY_SCORE_RECOVERY_RATE,
+                    spec.inactivity_score_recovery_rate,
                     self.inactivity_scores[index as usize],
                 )
             }
@@ -565,7 +727,7 @@ impl BeaconState {
     }
 
     pub fn get_base_reward_per_increment(&self) -> u64 {
-        EFFECTIVE_BALANCE_INCREMENT * BASE_REWARD_FACTOR
+        E::EFFECTIVE_BALANCE_INCREMENT * E::BASE_REWARD_FACTOR
             / (self.get_total_active_balance() as f64).sqrt() as u64
     }
 
@@ -573,12 +735,12 @@ impl BeaconState {
     /// ``state``.
     pub fn get_base_reward(&self, index: u64) -> u64 {
         let increments =
-            self.validators[index as usize].effective_balance / EFFECTIVE_BALANCE_INCREMENT;
+            self.validators[index as usize].effective_balance / E::EFFECTIVE_BALANCE_INCREMENT;
         increments * self.get_base_reward_per_increment()
     }
 
     pub fn get_proposer_reward(&self, attesting_index: u64) -> u64 {
-        self.get_base_reward(attesting_index) / PROPOSER_REWARD_QUOTIENT
+        self.get_base_reward(attesting_index) / E::PROPOSER_REWARD_QUOTIENT
     }
 
     pub fn get_finality_delay(&self) -> u64 {
@@ -586,7 +748,7 @@ impl BeaconState {
     }
 
     pub fn is_in_inactivity_leak(&self) -> bool {
-        self.get_finality_delay() > MIN_EPOCHS_TO_INACTIVITY_PENALTY
+        self.get_finality_delay() > E::MIN_EPOCHS_TO_INACTIVITY_PENALTY
     }
 
     pub fn get_eligible_validator_indices(&self) -> anyhow::Result<Vec<u64>> {
@@ -626,20 +788,23 @@ impl BeaconState {
 
         let mut participation_flag_indices = vec![];
 
-        if is_matching_source && inclusion_delay <= (SLOTS_PER_EPOCH as f64).sqrt() as u64 {
+        if is_matching_source && inclusion_delay <= (E::SLOTS_PER_EPOCH as f64).sqrt() as u64 {
             participation_flag_indices.push(TIMELY_SOURCE_FLAG_INDEX);
         }
         if is_matching_target {
             participation_flag_indices.push(TIMELY_TARGET_FLAG_INDEX);
         }
-        if is_matching_head && inclusion_delay == MIN_ATTESTATION_INCLUSION_DELAY {
+        if is_matching_head && inclusion_delay == E::MIN_ATTESTATION_INCLUSION_DELAY {
             participation_flag_indices.push(TIMELY_HEAD_FLAG_INDEX);
         }
 
         Ok(participation_flag_indices)
     }
 
-    pub fn get_inactivity_penalty_deltas(&self) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
+    pub fn get_inactivity_penalty_deltas(
+        &self,
+        spec: &ChainSpec,
+    ) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
         let rewards = vec![0; self.validators.len()];
         let mut penalties = vec![0; self.validators.len()];
         let previous_epoch = self.get_previous_epoch();
@@ -650,14 +815,14 @@ impl BeaconState {
                 let penalty_numerator = self.validators[index as usize].effective_balance
                     * self.inactivity_scores[index as usize];
                 let penalty_denominator =
-                    INACTIVITY_SCORE_BIAS * INACTIVITY_PENALTY_QUOTIENT_ALTAIR;
+                    spec.inactivity_score_bias * E::INACTIVITY_PENALTY_QUOTIENT_ALTAIR;
                 penalties[index as usize] += penalty_numerator / penalty_denominator
             }
         }
         Ok((rewards, penalties))
     }
 
-    pub fn process_block_header(&mut self, block: &BeaconBlock) -> anyhow::Result<()> {
+    pub fn process_block_header(&mut self, block: &BeaconBlock<E>) -> anyhow::Result<()> {
         // Verify that the slots match
         ensure!(
             self.slot == block.slot,
@@ -700,7 +865,10 @@ impl BeaconState {
         let mut withdrawal_index = self.next_withdrawal_index;
         let mut validator_index = self.next_withdrawal_validator_index;
         let mut withdrawals: Vec<Withdrawal> = vec![];
-        let bound = min(self.validators.len(), MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP);
+        let bound = min(
+            self.validators.len(),
+            E::MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP as usize,
+        );
         for _ in 0..bound {
             let validator = &self.validators[validator_index as usize];
             let balance = self.balances[validator_index as usize];
@@ -712,16 +880,16 @@ impl BeaconState {
                     amount: balance,
                 });
                 withdrawal_index += 1
-            } else if validator.is_partially_withdrawable_validator(balance) {
+            } else if validator.is_partially_withdrawable_validator::<E>(balance) {
                 withdrawals.push(Withdrawal {
                     index: withdrawal_index,
                     validator_index,
                     address: Address::from_slice(&validator.withdrawal_credentials[12..]),
-                    amount: balance - MAX_EFFECTIVE_BALANCE,
+                    amount: balance - E::MAX_EFFECTIVE_BALANCE,
                 });
                 withdrawal_index += 1
             }
-            if withdrawals.len() == MAX_WITHDRAWALS_PER_PAYLOAD as usize {
+            if withdrawals.len() == E::MAX_WITHDRAWALS_PER_PAYLOAD as usize {
                 break;
             }
             validator_index = (validator_index + 1) % self.validators.len() as u64
@@ -729,7 +897,7 @@ impl BeaconState {
         withdrawals
     }
 
-    pub fn process_withdrawals(&mut self, payload: &ExecutionPayload) -> anyhow::Result<()> {
+    pub fn process_withdrawals(&mut self, payload: &ExecutionPayload<E>) -> anyhow::Result<()> {
         let expected_withdrawals = self.get_expected_withdrawals();
         ensure!(
             payload.withdrawals.deref() == expected_withdrawals,
@@ -747,7 +915,7 @@ impl BeaconState {
         }
 
         // Update the next validator index to start the next withdrawal sweep
-        if expected_withdrawals.len() == MAX_WITHDRAWALS_PER_PAYLOAD as usize {
+        if expected_withdrawals.len() == E::MAX_WITHDRAWALS_PER_PAYLOAD as usize {
             // Next sweep starts after the latest withdrawal's validator index
             let next_validator_index = expected_withdrawals[expected_withdrawals.len() - 1]
                 .validator_index
@@ -757,7 +925,7 @@ impl BeaconState {
             // Advance sweep by the max length of the sweep if there was not a full set of
             // withdrawals
             let next_index =
-                self.next_withdrawal_validator_index + MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP as u64;
+                self.next_withdrawal_validator_index + E::MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP;
             let next_validator_index = next_index % self.validators.len() as u64;
             self.next_withdrawal_validator_index = next_validator_index
         }
@@ -772,7 +940,7 @@ impl BeaconState {
         amount: u64,
     ) -> anyhow::Result<()> {
         self.validators
-            .push(get_validator_from_deposit(
+            .push(get_validator_from_deposit::<E>(
                 pubkey,
                 withdrawal_credentials,
                 amount,
@@ -799,6 +967,7 @@ impl BeaconState {
         withdrawal_credentials: B256,
         amount: u64,
         signature: BLSSignature,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
         let mut validator_pubkeys = vec![];
         for validator in &self.validators {
@@ -812,7 +981,7 @@ impl BeaconState {
                 withdrawal_credentials,
                 amount,
             };
-            let domain = compute_domain(DOMAIN_DEPOSIT, None, None); // # Fork-agnostic domain since deposits are valid across forks
+            let domain = compute_domain(DOMAIN_DEPOSIT, spec.genesis_fork_version, None); // # Fork-agnostic domain since deposits are valid across forks
             let signing_root = compute_signing_root(deposit_message, domain);
 
             match signature.verify(&pubkey, signing_root.as_ref()) {
@@ -833,7 +1002,7 @@ impl BeaconState {
         Ok(())
     }
 
-    pub fn process_deposit(&mut self, deposit: &Deposit) -> anyhow::Result<()> {
+    pub fn process_deposit(&mut self, deposit: &Deposit, spec: &ChainSpec) -> anyhow::Result<()> {
         // Verify the Merkle branch
         ensure!(is_valid_merkle_branch(
             deposit.data.tree_hash_root(),
@@ -851,12 +1020,14 @@ impl BeaconState {
             deposit.data.withdrawal_credentials,
             deposit.data.amount,
             deposit.data.signature.clone(),
+            spec,
         )
     }
 
     pub fn process_bls_to_execution_change(
         &mut self,
         signed_address_change: &SignedBLSToExecutionChange,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
         let address_change = &signed_address_change.message;
 
@@ -873,7 +1044,7 @@ impl BeaconState {
         // Fork-agnostic domain since address changes are valid across forks
         let domain = compute_domain(
             DOMAIN_BLS_TO_EXECUTION_CHANGE,
-            None,
+            spec.genesis_fork_version,
             Some(self.genesis_validators_root),
         );
 
@@ -897,14 +1068,60 @@ impl BeaconState {
         Ok(())
     }
 
-    pub fn compute_timestamp_at_slot(&self, slot: u64) -> u64 {
+    pub fn compute_timestamp_at_slot(&self, slot: u64, spec: &ChainSpec) -> u64 {
         let slots_since_genesis = slot - GENESIS_SLOT;
-        self.genesis_time + slots_since_genesis * SECONDS_PER_SLOT
+        self.genesis_time + slots_since_genesis * spec.seconds_per_slot
+    }
+
+    pub fn voting_period_start_time(&self, spec: &ChainSpec) -> u64 {
+        let eth1_voting_period_start_slot =
+            self.slot - self.slot % (E::EPOCHS_PER_ETH1_VOTING_PERIOD * E::SLOTS_PER_EPOCH);
+        self.compute_timestamp_at_slot(eth1_voting_period_start_slot, spec)
+    }
+
+    /// ``eth1_chain`` represents all blocks in the eth1 chain sorted by ascending block height
+    pub fn get_eth1_vote(&self, eth1_chain: &[Eth1Block], spec: &ChainSpec) -> Eth1Data {
+        let period_start = self.voting_period_start_time(spec);
+        let votes_to_consider = eth1_chain
+            .iter()
+            .filter(|block| {
+                block.is_candidate_block(period_start, spec)
+                    // Ensure cannot move back to earlier deposit contract states
+                    && block.deposit_count >= self.eth1_data.deposit_count
+            })
+            .map(|block| block.get_eth1_data())
+            .collect::<Vec<_>>();
+
+        // Valid votes already cast during this period
+        let valid_votes = self
+            .eth1_data_votes
+            .iter()
+            .filter(|vote| votes_to_consider.contains(vote))
+            .collect::<Vec<_>>();
+
+        // Default vote on latest eth1 block data in the period range unless eth1 chain is not live
+        let default_vote = votes_to_consider
+            .last()
+            .cloned()
+            .unwrap_or_else(|| self.eth1_data.clone());
+
+        // Tiebreak by smallest distance
+        valid_votes
+            .iter()
+            .max_by_key(|vote| {
+                (
+                    valid_votes.iter().filter(|other| other == vote).count(),
+                    Reverse(valid_votes.iter().position(|other| other == *vote)),
+                )
+            })
+            .map(|vote| (*vote).clone())
+            .unwrap_or(default_vote)
     }
 
     pub fn process_voluntary_exit(
         &mut self,
         signed_voluntary_exit: &SignedVoluntaryExit,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
         let voluntary_exit = &signed_voluntary_exit.message;
         let validator_index = voluntary_exit.validator_index as usize;
@@ -935,7 +1152,7 @@ impl BeaconState {
         // Verify the validator has been active long enough
         let earlist_exit_epoch = validator
             .activation_epoch
-            .checked_add(SHARD_COMMITTEE_PERIOD)
+            .checked_add(spec.shard_committee_period)
             .ok_or(anyhow!("Failed to calculate earlist exit epoch"))?;
         ensure!(
             self.get_current_epoch() >= earlist_exit_epoch,
@@ -945,20 +1162,21 @@ impl BeaconState {
         // Compute signature domain
         let domain = compute_domain(
             DOMAIN_VOLUNTARY_EXIT,
-            Some(CAPELLA_FORK_VERSION),
+            spec.capella_fork_version,
             Some(self.genesis_validators_root),
         );
         let signing_root = compute_signing_root(voluntary_exit, domain);
 
         ensure!(
-            signed_voluntary_exit
-                .signature
-                .verify(&validator.pubkey, signing_root.as_ref())?,
+            signed_voluntary_exit.signature.verify_decompressed(
+                &self.get_validator_pubkey(validator_index as u64)?,
+                signing_root.as_ref()
+            )?,
             "BLS Signature verification failed!"
         );
 
         // Initiate exit
-        self.initiate_validator_exit(validator_index as u64);
+        self.initiate_validator_exit(validator_index as u64, spec);
 
         Ok(())
     }
@@ -971,15 +1189,19 @@ impl BeaconState {
         let seed = self.get_seed(epoch, DOMAIN_SYNC_COMMITTEE);
         let mut i = 0;
         let mut sync_committee_indices: Vec<u64> = vec![];
-        while sync_committee_indices.len() < SYNC_COMMITTEE_SIZE as usize {
-            let shuffled_index =
-                compute_shuffled_index(i % active_validator_count, active_validator_count, seed)?;
+        while sync_committee_indices.len() < E::SYNC_COMMITTEE_SIZE as usize {
+            let shuffled_index = compute_shuffled_index::<E>(
+                i % active_validator_count,
+                active_validator_count,
+                seed,
+            )?;
             let candidate_index = active_validator_indices[shuffled_index];
             let seed_with_index = [seed.as_slice(), &(i / 32).to_le_bytes()].concat();
             let hash = hash(&seed_with_index);
             let random_byte = hash[i % 32];
             let effective_balance = self.validators[candidate_index as usize].effective_balance;
-            if effective_balance * MAX_RANDOM_BYTE >= MAX_EFFECTIVE_BALANCE * random_byte as u64 {
+            if effective_balance * MAX_RANDOM_BYTE >= E::MAX_EFFECTIVE_BALANCE * random_byte as u64
+            {
                 sync_committee_indices.push(candidate_index)
             }
             i += 1
@@ -991,6 +1213,7 @@ impl BeaconState {
     pub fn process_proposer_slashing(
         &mut self,
         proposer_slashing: &ProposerSlashing,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
         let header_1 = &proposer_slashing.signed_header_1.message;
         let header_2 = &proposer_slashing.signed_header_2.message;
@@ -1026,27 +1249,28 @@ impl BeaconState {
         ] {
             let domain = self.get_domain(
                 DOMAIN_BEACON_PROPOSER,
-                Some(compute_epoch_at_slot(signed_header.message.slot)),
+                Some(compute_epoch_at_slot::<E>(signed_header.message.slot)),
             );
 
             let signing_root = compute_signing_root(&signed_header.message, domain);
 
             ensure!(
-                signed_header
-                    .signature
-                    .verify(&proposer.pubkey, signing_root.as_ref())?,
+                signed_header.signature.verify_decompressed(
+                    &self.get_validator_pubkey(proposer_index)?,
+                    signing_root.as_ref()
+                )?,
                 "BLS Signature verification failed!"
             );
         }
 
         // Slash the validator
-        self.slash_validator(proposer_index, None)
+        self.slash_validator(proposer_index, None, spec)
     }
 
     pub fn process_historical_summaries_update(&mut self) -> anyhow::Result<()> {
         // Set historical block root accumulator.
         let next_epoch = self.get_current_epoch() + 1;
-        if next_epoch % SLOTS_PER_HISTORICAL_ROOT / SLOTS_PER_EPOCH == 0 {
+        if next_epoch % (E::SLOTS_PER_HISTORICAL_ROOT / E::SLOTS_PER_EPOCH) == 0 {
             let historical_summary = HistoricalSummary {
                 block_summary_root: self.block_roots.tree_hash_root(),
                 state_summary_root: self.state_roots.tree_hash_root(),
@@ -1061,6 +1285,7 @@ impl BeaconState {
     pub fn process_attester_slashing(
         &mut self,
         attester_slashing: &AttesterSlashing,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
         let attestation_1 = &attester_slashing.attestation_1;
         let attestation_2 = &attester_slashing.attestation_2;
@@ -1090,7 +1315,7 @@ impl BeaconState {
         // Find common attesting indices and process slashing
         for &index in indices_1.intersection(&indices_2).sorted() {
             if self.validators[index as usize].is_slashable_validator(current_epoch) {
-                self.slash_validator(index, None)?;
+                self.slash_validator(index, None, spec)?;
                 slashed_any = true;
             }
         }
@@ -1099,7 +1324,10 @@ impl BeaconState {
         Ok(())
     }
 
-    pub fn process_sync_aggregate(&mut self, sync_aggregate: &SyncAggregate) -> anyhow::Result<()> {
+    pub fn process_sync_aggregate(
+        &mut self,
+        sync_aggregate: &SyncAggregate<E>,
+    ) -> anyhow::Result<()> {
         // Verify sync committee aggregate signature signing over the previous slot block root
         let committee_pubkeys = &self.current_sync_committee.pubkeys;
         let mut participant_pubkeys = vec![];
@@ -1116,7 +1344,7 @@ impl BeaconState {
         let previous_slot = max(self.slot, 1) - 1;
         let domain = self.get_domain(
             DOMAIN_SYNC_COMMITTEE,
-            Some(compute_epoch_at_slot(previous_slot)),
+            Some(compute_epoch_at_slot::<E>(previous_slot)),
         );
         let signing_root =
             compute_signing_root(self.get_block_root_at_slot(previous_slot)?, domain);
@@ -1131,11 +1359,12 @@ impl BeaconState {
         );
 
         // Compute participant and proposer rewards
-        let total_active_increments = self.get_total_active_balance() / EFFECTIVE_BALANCE_INCREMENT;
+        let total_active_increments =
+            self.get_total_active_balance() / E::EFFECTIVE_BALANCE_INCREMENT;
         let total_base_rewards = self.get_base_reward_per_increment() * total_active_increments;
         let max_participant_rewards =
-            total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / SLOTS_PER_EPOCH;
-        let participant_reward = max_participant_rewards / SYNC_COMMITTEE_SIZE;
+            total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / E::SLOTS_PER_EPOCH;
+        let participant_reward = max_participant_rewards / E::SYNC_COMMITTEE_SIZE;
         let proposer_reward =
             participant_reward * PROPOSER_WEIGHT / (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT);
 
@@ -1273,7 +1502,7 @@ impl BeaconState {
         let next_epoch = self.get_current_epoch() + 1;
 
         // Reset eth1 data votes
-        if next_epoch % EPOCHS_PER_ETH1_VOTING_PERIOD == 0 {
+        if next_epoch % E::EPOCHS_PER_ETH1_VOTING_PERIOD == 0 {
             self.eth1_data_votes = VariableList::default();
         }
 
@@ -1284,33 +1513,33 @@ impl BeaconState {
         // Update effective balances with hysteresis
         for (index, validator) in self.validators.iter_mut().enumerate() {
             let balance = self.balances[index];
-            let hysteresis_increment = EFFECTIVE_BALANCE_INCREMENT / HYSTERESIS_QUOTIENT;
-            let downward_threshold = hysteresis_increment * HYSTERESIS_DOWNWARD_MULTIPLIER;
-            let upward_threshold = hysteresis_increment * HYSTERESIS_UPWARD_MULTIPLIER;
+            let hysteresis_increment = E::EFFECTIVE_BALANCE_INCREMENT / E::HYSTERESIS_QUOTIENT;
+            let downward_threshold = hysteresis_increment * E::HYSTERESIS_DOWNWARD_MULTIPLIER;
+            let upward_threshold = hysteresis_increment * E::HYSTERESIS_UPWARD_MULTIPLIER;
 
             if balance + downward_threshold < validator.effective_balance
                 || validator.effective_balance + upward_threshold < balance
             {
-                validator.effective_balance =
-                    (balance - balance % EFFECTIVE_BALANCE_INCREMENT).min(MAX_EFFECTIVE_BALANCE);
+                validator.effective_balance = (balance - balance % E::EFFECTIVE_BALANCE_INCREMENT)
+                    .min(E::MAX_EFFECTIVE_BALANCE);
             }
         }
         Ok(())
     }
 
-    pub fn process_randao(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+    pub fn process_randao(&mut self, body: &BeaconBlockBody<E>) -> anyhow::Result<()> {
         let epoch = self.get_current_epoch();
 
         // Verify RANDAO reveal
-        if let Some(proposer) = self
-            .validators
-            .get(self.get_beacon_proposer_index()? as usize)
-        {
+        let proposer_index = self.get_beacon_proposer_index()?;
+        if self.validators.get(proposer_index as usize).is_some() {
             let signing_root =
                 compute_signing_root(epoch, self.get_domain(DOMAIN_RANDAO, Some(epoch)));
             ensure!(
-                body.randao_reveal
-                    .verify(&proposer.pubkey, signing_root.as_ref())?,
+                body.randao_reveal.verify_decompressed(
+                    &self.get_validator_pubkey(proposer_index)?,
+                    signing_root.as_ref()
+                )?,
                 "BLS Signature verification failed!"
             );
 
@@ -1319,13 +1548,13 @@ impl BeaconState {
                 self.get_randao_mix(epoch).as_slice(),
                 hash(body.randao_reveal.to_bytes()).as_slice(),
             );
-            self.randao_mixes[(epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize] = mix;
+            self.randao_mixes[(epoch % E::EPOCHS_PER_HISTORICAL_VECTOR) as usize] = mix;
         }
 
         Ok(())
     }
 
-    pub fn process_eth1_data(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+    pub fn process_eth1_data(&mut self, body: &BeaconBlockBody<E>) -> anyhow::Result<()> {
         self.eth1_data_votes
             .push(body.eth1_data.clone())
             .map_err(|err| anyhow!("Can't push eth1_data {err:?}"))?;
@@ -1336,7 +1565,7 @@ impl BeaconState {
             .filter(|data| **data == body.eth1_data)
             .count() as u64;
 
-        if count * 2 > (EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH) {
+        if count * 2 > (E::EPOCHS_PER_ETH1_VOTING_PERIOD * E::SLOTS_PER_EPOCH) {
             self.eth1_data = body.eth1_data.clone();
         }
 
@@ -1351,12 +1580,12 @@ impl BeaconState {
         );
 
         ensure!(
-            attestation.data.target.epoch == compute_epoch_at_slot(attestation.data.slot),
+            attestation.data.target.epoch == compute_epoch_at_slot::<E>(attestation.data.slot),
             "Target epoch must match the computed epoch at slot"
         );
 
         ensure!(
-            attestation.data.slot + MIN_ATTESTATION_INCLUSION_DELAY <= self.slot,
+            attestation.data.slot + E::MIN_ATTESTATION_INCLUSION_DELAY <= self.slot,
             "Attestation must be included after the minimum delay"
         );
 
@@ -1426,7 +1655,7 @@ impl BeaconState {
         let current_epoch = self.get_current_epoch();
         let next_epoch = current_epoch + 1;
         // Set randao mix
-        self.randao_mixes[(next_epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize] =
+        self.randao_mixes[(next_epoch % E::EPOCHS_PER_HISTORICAL_VECTOR) as usize] =
             self.get_randao_mix(current_epoch);
         Ok(())
     }
@@ -1434,7 +1663,7 @@ impl BeaconState {
     pub fn process_slashings_reset(&mut self) -> anyhow::Result<()> {
         let next_epoch = self.get_current_epoch() + 1;
         // Reset slashings
-        self.slashings[(next_epoch % EPOCHS_PER_SLASHINGS_VECTOR) as usize] = 0;
+        self.slashings[(next_epoch % E::EPOCHS_PER_SLASHINGS_VECTOR) as usize] = 0;
         Ok(())
     }
 
@@ -1442,15 +1671,15 @@ impl BeaconState {
         let epoch = self.get_current_epoch();
         let total_balance = self.get_total_active_balance();
         let adjusted_total_slashing_balance = (self.slashings.iter().sum::<u64>()
-            * PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX)
+            * E::PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX)
             .min(total_balance);
 
         for index in 0..self.validators.len() {
             let validator = &self.validators[index];
             if validator.slashed
-                && epoch + EPOCHS_PER_SLASHINGS_VECTOR / 2 == validator.withdrawable_epoch
+                && epoch + E::EPOCHS_PER_SLASHINGS_VECTOR / 2 == validator.withdrawable_epoch
             {
-                let increment = EFFECTIVE_BALANCE_INCREMENT; // Factored out from penalty numerator to avoid uint64 overflow
+                let increment = E::EFFECTIVE_BALANCE_INCREMENT; // Factored out from penalty numerator to avoid uint64 overflow
                 let penalty_numerator =
                     validator.effective_balance / increment * adjusted_total_slashing_balance;
                 let penalty = penalty_numerator / total_balance * increment;
@@ -1465,51 +1694,58 @@ impl BeaconState {
     pub fn process_slot(&mut self) -> anyhow::Result<()> {
         // Cache state root
         let previous_state_root = self.tree_hash_root();
-        self.state_roots[(self.slot % SLOTS_PER_HISTORICAL_ROOT) as usize] = previous_state_root;
+        self.state_roots[(self.slot % E::SLOTS_PER_HISTORICAL_ROOT) as usize] = previous_state_root;
         // Cache latest block header state root
         if self.latest_block_header.state_root == B256::default() {
             self.latest_block_header.state_root = previous_state_root;
         }
         // Cache block root
         let previous_block_root = self.latest_block_header.tree_hash_root();
-        self.block_roots[(self.slot % SLOTS_PER_HISTORICAL_ROOT) as usize] = previous_block_root;
+        self.block_roots[(self.slot % E::SLOTS_PER_HISTORICAL_ROOT) as usize] = previous_block_root;
         Ok(())
     }
 
-    pub fn process_operations(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+    pub fn process_operations(
+        &mut self,
+        body: &BeaconBlockBody<E>,
+        spec: &ChainSpec,
+    ) -> anyhow::Result<()> {
         // Verify that outstanding deposits are processed up to the maximum number of deposits
         ensure!(
             body.deposits.len()
                 == min(
-                    MAX_DEPOSITS as usize,
+                    E::MAX_DEPOSITS as usize,
                     (self.eth1_data.deposit_count - self.eth1_deposit_index) as usize
                 )
         );
 
         for proposer_slashing in body.proposer_slashings.iter() {
-            self.process_proposer_slashing(proposer_slashing)?;
+            self.process_proposer_slashing(proposer_slashing, spec)?;
         }
         for attester_slashing in body.attester_slashings.iter() {
-            self.process_attester_slashing(attester_slashing)?;
+            self.process_attester_slashing(attester_slashing, spec)?;
         }
         for attestation in body.attestations.iter() {
             self.process_attestation(attestation)?;
         }
         for deposit in body.deposits.iter() {
-            self.process_deposit(deposit)?;
+            self.process_deposit(deposit, spec)?;
         }
         for voluntary_exit in body.voluntary_exits.iter() {
-            self.process_voluntary_exit(voluntary_exit)?;
+            self.process_voluntary_exit(voluntary_exit, spec)?;
         }
         for bls_to_execution_change in body.bls_to_execution_changes.iter() {
-            self.process_bls_to_execution_change(bls_to_execution_change)?;
+            self.process_bls_to_execution_change(bls_to_execution_change, spec)?;
         }
 
         Ok(())
     }
 
-    pub fn verify_block_signature(&self, signed_block: &SignedBeaconBlock) -> anyhow::Result<bool> {
-        let proposer = &self.validators[signed_block.message.proposer_index as usize];
+    pub fn verify_block_signature(
+        &self,
+        signed_block: &SignedBeaconBlock<E>,
+    ) -> anyhow::Result<bool> {
+        let proposer_pubkey = self.get_validator_pubkey(signed_block.message.proposer_index)?;
         let signing_root = compute_signing_root(
             signed_block.message.clone(),
             self.get_domain(DOMAIN_BEACON_PROPOSER, None),
@@ -1517,7 +1753,7 @@ impl BeaconState {
 
         signed_block
             .signature
-            .verify(&proposer.pubkey, signing_root.as_ref())
+            .verify_decompressed(&proposer_pubkey, signing_root.as_ref())
             .map_err(|e| anyhow!("Invalid block signature: {:?}", e))
     }
 
@@ -1529,32 +1765,101 @@ impl BeaconState {
     }
 
     /// Return the validator activation churn limit for the current epoch.
-    pub fn get_validator_activation_churn_limit(&self) -> u64 {
+    pub fn get_validator_activation_churn_limit(&self, spec: &ChainSpec) -> u64 {
         min(
-            MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT,
-            self.get_validator_churn_limit(),
+            spec.max_per_epoch_activation_churn_limit,
+            self.get_validator_churn_limit(spec),
         )
     }
 
-    pub fn process_registry_updates(&mut self) -> anyhow::Result<()> {
+    /// Return the indices of validators waiting for activation, ordered by the sequence of
+    /// ``activation_eligibility_epoch`` setting and then index.
+    pub fn get_activation_queue(&self) -> Vec<u64> {
+        let mut activation_queue = (0..self.validators.len())
+            .filter(|&index| {
+                let validator = &self.validators[index];
+                validator.activation_eligibility_epoch != FAR_FUTURE_EPOCH
+                    && validator.activation_epoch == FAR_FUTURE_EPOCH
+            })
+            .collect::<Vec<_>>();
+        activation_queue
+            .sort_by_key(|&index| (self.validators[index].activation_eligibility_epoch, index));
+        activation_queue
+            .into_iter()
+            .map(|index| index as u64)
+            .collect()
+    }
+
+    /// Return the position of ``validator_index`` in the activation queue.
+    pub fn get_activation_queue_position(&self, validator_index: u64) -> Option<u64> {
+        self.get_activation_queue()
+            .iter()
+            .position(|&index| index == validator_index)
+            .map(|position| position as u64)
+    }
+
+    /// Return the estimated activation epoch of ``validator_index``.
+    ///
+    /// Assumes the activation churn limit stays the same and the chain finalizes every epoch, so
+    /// a validator can be dequeued two epochs after its ``activation_eligibility_epoch``.
+    pub fn get_estimated_activation_epoch(
+        &self,
+        validator_index: u64,
+        spec: &ChainSpec,
+    ) -> Option<u64> {
+        let validator = self.validators.get(validator_index as usize)?;
+        if validator.activation_epoch != FAR_FUTURE_EPOCH {
+            return Some(validator.activation_epoch);
+        }
+        let position = self.get_activation_queue_position(validator_index)?;
+        let dequeue_epoch = max(
+            self.get_current_epoch() + position / self.get_validator_activation_churn_limit(spec),
+            validator.activation_eligibility_epoch + 2,
+        );
+        Some(compute_activation_exit_epoch::<E>(dequeue_epoch))
+    }
+
+    /// Return the estimated activation time of ``validator_index`` in seconds since the Unix epoch.
+    pub fn get_estimated_activation_time(
+        &self,
+        validator_index: u64,
+        spec: &ChainSpec,
+    ) -> Option<u64> {
+        let activation_epoch = self.get_estimated_activation_epoch(validator_index, spec)?;
+        Some(
+            self.compute_timestamp_at_slot(
+                compute_start_slot_at_epoch::<E>(activation_epoch),
+                spec,
+            ),
+        )
+    }
+
+    /// Return the Beacon API status of ``validator_index`` at the current epoch.
+    pub fn get_validator_status(&self, validator_index: u64) -> Option<ValidatorStatus> {
+        let validator = self.validators.get(validator_index as usize)?;
+        let balance = *self.balances.get(validator_index as usize)?;
+        Some(validator.status(balance, self.get_current_epoch()))
+    }
+
+    pub fn process_registry_updates(&mut self, spec: &ChainSpec) -> anyhow::Result<()> {
         let current_epoch = self.get_current_epoch();
         let mut initiate_validator = vec![];
 
         // Process activation eligibility and ejections
         for (index, validator) in self.validators.iter_mut().enumerate() {
-            if validator.is_eligible_for_activation_queue() {
+            if validator.is_eligible_for_activation_queue::<E>() {
                 validator.activation_eligibility_epoch = current_epoch + 1;
             }
 
             if validator.is_active_validator(current_epoch)
-                && validator.effective_balance <= EJECTION_BALANCE
+                && validator.effective_balance <= spec.ejection_balance
             {
                 initiate_validator.push(index as u64);
             }
         }
 
         for index in initiate_validator {
-            self.initiate_validator_exit(index);
+            self.initiate_validator_exit(index, spec);
         }
 
         // Queue validators eligible for activation and not yet dequeued for activation
@@ -1568,9 +1873,11 @@ impl BeaconState {
 
         // Dequeued validators for activation up to activation churn limit
         // [Modified in Deneb:EIP7514]
-        for index in activation_queue[..self.get_validator_activation_churn_limit() as usize].iter()
+        for index in
+            activation_queue[..self.get_validator_activation_churn_limit(spec) as usize].iter()
         {
-            self.validators[*index].activation_epoch = compute_activation_exit_epoch(current_epoch);
+            self.validators[*index].activation_epoch =
+                compute_activation_exit_epoch::<E>(current_epoch);
         }
 
         Ok(())
@@ -1588,8 +1895,8 @@ impl BeaconState {
         let unslashed_participating_balance =
             self.get_total_balance(unslashed_participating_indices.clone());
         let unslashed_participating_increments =
-            unslashed_participating_balance / EFFECTIVE_BALANCE_INCREMENT;
-        let active_increments = self.get_total_active_balance() / EFFECTIVE_BALANCE_INCREMENT;
+            unslashed_participating_balance / E::EFFECTIVE_BALANCE_INCREMENT;
+        let active_increments = self.get_total_active_balance() / E::EFFECTIVE_BALANCE_INCREMENT;
 
         for index in self.get_eligible_validator_indices()? {
             let base_reward = self.get_base_reward(index);
@@ -1609,7 +1916,7 @@ impl BeaconState {
         Ok((rewards, penalties))
     }
 
-    pub fn process_rewards_and_penalties(&mut self) -> anyhow::Result<()> {
+    pub fn process_rewards_and_penalties(&mut self, spec: &ChainSpec) -> anyhow::Result<()> {
         // No rewards are applied at the end of `GENESIS_EPOCH` because rewards are for work done in
         // the previous epoch
         if self.get_current_epoch() == GENESIS_EPOCH {
@@ -1621,7 +1928,7 @@ impl BeaconState {
             deltas.push(self.get_flag_index_deltas(flag_index as u8)?);
         }
 
-        deltas.push(self.get_inactivity_penalty_deltas()?);
+        deltas.push(self.get_inactivity_penalty_deltas(spec)?);
 
         for (rewards, penalties) in deltas {
             for index in 0..self.validators.len() {
@@ -1633,7 +1940,7 @@ impl BeaconState {
     }
 
     /// Return the next sync committee, with possible pubkey duplicates.
-    pub fn get_next_sync_committee(&self) -> anyhow::Result<SyncCommittee> {
+    pub fn get_next_sync_committee(&self) -> anyhow::Result<SyncCommittee<E>> {
         let indices = self.get_next_sync_committee_indices()?;
         let mut pubkeys = vec![];
 
@@ -1651,7 +1958,7 @@ impl BeaconState {
 
     pub fn process_sync_committee_updates(&mut self) -> anyhow::Result<()> {
         let next_epoch = self.get_current_epoch() + 1;
-        if next_epoch % EPOCHS_PER_SYNC_COMMITTEE_PERIOD == 0 {
+        if next_epoch % E::EPOCHS_PER_SYNC_COMMITTEE_PERIOD == 0 {
             self.current_sync_committee = self.next_sync_committee.clone();
             self.next_sync_committee = Arc::new(self.get_next_sync_committee()?);
         }
@@ -1664,11 +1971,11 @@ impl BeaconState {
         Ok(())
     }
 
-    pub fn process_epoch(&mut self) -> anyhow::Result<()> {
+    pub fn process_epoch(&mut self, spec: &ChainSpec) -> anyhow::Result<()> {
         self.process_justification_and_finalization()?;
-        self.process_inactivity_updates()?;
-        self.process_rewards_and_penalties()?;
-        self.process_registry_updates()?;
+        self.process_inactivity_updates(spec)?;
+        self.process_rewards_and_penalties(spec)?;
+        self.process_registry_updates(spec)?;
         self.process_slashings()?;
         self.process_eth1_data_reset()?;
         self.process_effective_balance_updates()?;
@@ -1680,13 +1987,13 @@ impl BeaconState {
         Ok(())
     }
 
-    pub fn process_slots(&mut self, slot: u64) -> anyhow::Result<()> {
+    pub fn process_slots(&mut self, slot: u64, spec: &ChainSpec) -> anyhow::Result<()> {
         ensure!(self.slot < slot);
         while self.slot < slot {
             self.process_slot()?;
             // Process epoch on the start slot of the next epoch
-            if (self.slot + 1) % SLOTS_PER_EPOCH == 0 {
-                self.process_epoch()?;
+            if (self.slot + 1) % E::SLOTS_PER_EPOCH == 0 {
+                self.process_epoch(spec)?;
             }
             self.slot += 1
         }
@@ -1695,8 +2002,9 @@ impl BeaconState {
 
     pub async fn process_execution_payload(
         &mut self,
-        body: &BeaconBlockBody,
+        body: &BeaconBlockBody<E>,
         execution_engine: &impl ExecutionApi,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
         let payload = &body.execution_payload;
 
@@ -1706,9 +2014,9 @@ impl BeaconState {
         // Verify prev_randao
         ensure!(payload.prev_randao == self.get_randao_mix(self.get_current_epoch()));
         // Verify timestamp
-        ensure!(payload.timestamp == self.compute_timestamp_at_slot(self.slot));
+        ensure!(payload.timestamp == self.compute_timestamp_at_slot(self.slot, spec));
         // Verify commitments are under limit
-        ensure!(body.blob_kzg_commitments.len() <= MAX_BLOBS_PER_BLOCK as usize);
+        ensure!(body.blob_kzg_commitments.len() <= E::MAX_BLOBS_PER_BLOCK as usize);
 
         // Verify the execution payload is valid
         let mut versioned_hashes = vec![];
@@ -1726,55 +2034,41 @@ impl BeaconState {
         );
 
         // Cache execution payload header
-        self.latest_execution_payload_header = ExecutionPayloadHeader {
-            parent_hash: payload.parent_hash,
-            fee_recipient: payload.fee_recipient,
-            state_root: payload.state_root,
-            receipts_root: payload.receipts_root,
-            logs_bloom: payload.logs_bloom.clone(),
-            prev_randao: payload.prev_randao,
-            block_number: payload.block_number,
-            gas_limit: payload.gas_limit,
-            gas_used: payload.gas_used,
-            timestamp: payload.timestamp,
-            extra_data: payload.extra_data.clone(),
-            base_fee_per_gas: payload.base_fee_per_gas,
-            block_hash: payload.block_hash,
-            transactions_root: payload.transactions.tree_hash_root(),
-            withdrawals_root: payload.withdrawals.tree_hash_root(),
-            blob_gas_used: payload.blob_gas_used,
-            excess_blob_gas: payload.excess_blob_gas,
-        };
+        self.latest_execution_payload_header = payload.to_execution_payload_header();
 
         Ok(())
     }
 
     pub async fn process_block(
         &mut self,
-        block: &BeaconBlock,
+        block: &BeaconBlock<E>,
         execution_engine: &impl ExecutionApi,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
+        self.update_pubkey_cache();
         self.process_block_header(block)?;
         self.process_withdrawals(&block.body.execution_payload)?;
-        self.process_execution_payload(&block.body, execution_engine)
+        self.process_execution_payload(&block.body, execution_engine, spec)
             .await?;
         self.process_randao(&block.body)?;
         self.process_eth1_data(&block.body)?;
-        self.process_operations(&block.body)?;
+        self.process_operations(&block.body, spec)?;
         self.process_sync_aggregate(&block.body.sync_aggregate)?;
         Ok(())
     }
 
     pub async fn state_transition(
         &mut self,
-        signed_block: SignedBeaconBlock,
+        signed_block: SignedBeaconBlock<E>,
         validate_result: bool,
         execution_engine: &impl ExecutionApi,
+        spec: &ChainSpec,
     ) -> anyhow::Result<()> {
         let block = &signed_block.message;
 
         // Process slots (including those with no blocks) since block
-        self.process_slots(block.slot)?;
+        self.process_slots(block.slot, spec)?;
+        self.update_pubkey_cache();
 
         // Verify signature
         if validate_result {
@@ -1782,7 +2076,7 @@ impl BeaconState {
         }
 
         // Process block
-        self.process_block(block, execution_engine).await?;
+        self.process_block(block, execution_engine, spec).await?;
 
         // Verify state root
         if validate_result {
@@ -1813,14 +2107,14 @@ pub fn is_valid_merkle_branch(
     value == root
 }
 
-pub fn get_validator_from_deposit(
+pub fn get_validator_from_deposit<E: EthSpec>(
     pubkey: PubKey,
     withdrawal_credentials: B256,
     amount: u64,
 ) -> Validator {
     let effective_balance = min(
-        amount - amount % EFFECTIVE_BALANCE_INCREMENT,
-        MAX_EFFECTIVE_BALANCE,
+        amount - amount % E::EFFECTIVE_BALANCE_INCREMENT,
+        E::MAX_EFFECTIVE_BALANCE,
     );
     Validator {
         pubkey,
@@ -1867,3 +2161,390 @@ pub fn kzg_commitment_to_versioned_hash(kzg_commitment: &KZGCommitment) -> B256
     versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
     B256::from_slice(&versioned_hash)
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::{
+        eth_spec::MinimalEthSpec,
+        sync_committee_contribution::SyncCommitteeContribution,
+        test_utils::{beacon_state, validator},
+    };
+
+    #[test]
+    fn test_process_historical_summaries_update() {
+        // The minimal preset has 8 epochs per historical root, so a summary is appended when
+        // moving into epochs 8, 16, ... and not in between
+        let slots_per_epoch = MinimalEthSpec::SLOTS_PER_EPOCH;
+        for epoch in 0..24 {
+            let mut state = beacon_state::<MinimalEthSpec>(epoch * slots_per_epoch, vec![]);
+            state.process_historical_summaries_update().unwrap();
+            let expected_summaries = if (epoch + 1) % 8 == 0 { 1 } else { 0 };
+            assert_eq!(
+                state.historical_summaries.len(),
+                expected_summaries,
+                "epoch {epoch}"
+            );
+        }
+    }
+
+    fn eth1_block(deposit_count: u64, timestamp: u64) -> Eth1Block {
+        Eth1Block {
+            block_hash: B256::repeat_byte(deposit_count as u8),
+            block_number: deposit_count,
+            timestamp,
+            deposit_root: B256::repeat_byte(0x80 + deposit_count as u8),
+            deposit_count,
+        }
+    }
+
+    #[test]
+    fn test_get_eth1_vote() {
+        // The minimal preset has voting periods of 32 slots of 6 seconds, and follows the eth1
+        // chain at 16 blocks of 14 seconds, so blocks between 448 and 224 seconds before the
+        // start of the period are candidates
+        let spec = ChainSpec::minimal();
+        let mut state = beacon_state::<MinimalEthSpec>(40, vec![]);
+        state.genesis_time = 10_000;
+        let period_start = 10_000 + 32 * 6;
+        assert_eq!(state.voting_period_start_time(&spec), period_start);
+
+        let eth1_chain = [
+            eth1_block(1, period_start - 449),
+            eth1_block(2, period_start - 448),
+            eth1_block(3, period_start - 300),
+            eth1_block(4, period_start - 224),
+            eth1_block(5, period_start - 223),
+        ];
+        let eth1_data = |index: usize| eth1_chain[index].get_eth1_data();
+        let vote = |state: &BeaconState<MinimalEthSpec>, votes: &[usize]| {
+            let mut state = state.clone();
+            for index in votes {
+                state.eth1_data_votes.push(eth1_data(*index)).unwrap();
+            }
+            state.get_eth1_vote(&eth1_chain, &spec)
+        };
+
+        // Without valid votes, vote for the latest candidate block
+        assert_eq!(vote(&state, &[]), eth1_data(3));
+        assert_eq!(vote(&state, &[0, 4, 4]), eth1_data(3));
+
+        // The candidate with the most votes wins
+        assert_eq!(vote(&state, &[2, 3, 1, 2]), eth1_data(2));
+        assert_eq!(vote(&state, &[4, 4, 4, 1]), eth1_data(1));
+
+        // Ties go to the candidate voted for first
+        assert_eq!(vote(&state, &[3, 2, 2, 3]), eth1_data(3));
+        assert_eq!(vote(&state, &[2, 3]), eth1_data(2));
+
+        // Votes can't move back to an earlier deposit count
+        state.eth1_data = eth1_data(2);
+        assert_eq!(vote(&state, &[1, 1, 3]), eth1_data(3));
+        assert_eq!(vote(&state, &[1, 1]), eth1_data(3));
+
+        // Without candidate blocks, keep the current eth1 data
+        state.eth1_data = eth1_data(4);
+        assert_eq!(vote(&state, &[3]), eth1_data(4));
+    }
+
+    #[test]
+    fn test_get_active_validator_indices() {
+        // Validators in the activation queue are eligible but not active yet
+        let queued_validator = Validator {
+            activation_eligibility_epoch: 0,
+            activation_epoch: FAR_FUTURE_EPOCH,
+            ..validator::<MinimalEthSpec>(PubKey::default(), 0)
+        };
+        let state = beacon_state::<MinimalEthSpec>(
+            8,
+            vec![
+                validator::<MinimalEthSpec>(PubKey::default(), 0),
+                queued_validator,
+                validator::<MinimalEthSpec>(PubKey::default(), 1),
+                validator::<MinimalEthSpec>(PubKey::default(), 2),
+            ],
+        );
+        assert_eq!(state.get_active_validator_indices(1), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_activation_queue_estimates() {
+        // With 4 active validators, the minimal configuration activates 2 validators per epoch
+        let spec = ChainSpec::minimal();
+        let queued_validator = |activation_eligibility_epoch| Validator {
+            activation_eligibility_epoch,
+            ..validator::<MinimalEthSpec>(PubKey::default(), FAR_FUTURE_EPOCH)
+        };
+        let mut validators = vec![validator::<MinimalEthSpec>(PubKey::default(), 0); 4];
+        validators.extend([
+            queued_validator(9),
+            queued_validator(8),
+            queued_validator(9),
+            queued_validator(3),
+            queued_validator(12),
+            queued_validator(FAR_FUTURE_EPOCH),
+        ]);
+        let mut state =
+            beacon_state::<MinimalEthSpec>(10 * MinimalEthSpec::SLOTS_PER_EPOCH, validators);
+        state.genesis_time = 1_000;
+        assert_eq!(state.get_validator_activation_churn_limit(&spec), 2);
+
+        // Ordered by activation eligibility epoch, then index
+        assert_eq!(state.get_activation_queue(), vec![7, 5, 4, 6, 8]);
+        assert_eq!(state.get_activation_queue_position(6), Some(3));
+        assert_eq!(state.get_activation_queue_position(9), None);
+
+        // Each pair of queued validators is dequeued an epoch later than the previous pair, but no
+        // earlier than two epochs after becoming eligible, and activated 5 epochs after that
+        let estimated_activation_epochs = (0..10)
+            .map(|index| state.get_estimated_activation_epoch(index, &spec))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            estimated_activation_epochs,
+            vec![
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(16),
+                Some(15),
+                Some(16),
+                Some(15),
+                Some(19),
+                None,
+            ]
+        );
+        assert_eq!(
+            state.get_estimated_activation_time(8, &spec),
+            Some(1_000 + 19 * MinimalEthSpec::SLOTS_PER_EPOCH * spec.seconds_per_slot)
+        );
+
+        assert_eq!(
+            state.get_validator_status(7),
+            Some(ValidatorStatus::PendingQueued)
+        );
+        assert_eq!(
+            state.get_validator_status(9),
+            Some(ValidatorStatus::PendingInitialized)
+        );
+        assert_eq!(
+            state.get_validator_status(0),
+            Some(ValidatorStatus::ActiveOngoing)
+        );
+        assert_eq!(state.get_validator_status(10), None);
+    }
+
+    #[test]
+    fn test_get_slot_signature() {
+        let privkey = SecretKey::from_bytes(&[1; 32]).unwrap();
+        let state = beacon_state::<MinimalEthSpec>(
+            0,
+            vec![validator::<MinimalEthSpec>(privkey.public_key().unwrap(), 0); 64],
+        );
+
+        let slot_signature = state.get_slot_signature(3, &privkey).unwrap();
+        assert_eq!(
+            slot_signature.verify(
+                &privkey.public_key().unwrap(),
+                state.get_slot_signing_root(3).as_ref()
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            slot_signature.verify(
+                &privkey.public_key().unwrap(),
+                state.get_slot_signing_root(4).as_ref()
+            ),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_is_aggregator() {
+        let privkey = SecretKey::from_bytes(&[1; 32]).unwrap();
+        let validators = |count| vec![validator::<MinimalEthSpec>(PubKey::default(), 0); count];
+
+        // Committees smaller than ``TARGET_AGGREGATORS_PER_COMMITTEE`` only have aggregators
+        let state = beacon_state::<MinimalEthSpec>(0, validators(64));
+        assert_eq!(state.get_beacon_committee(0, 0).unwrap().len(), 4);
+        for slot in 0..MinimalEthSpec::SLOTS_PER_EPOCH {
+            let slot_signature = state.get_slot_signature(slot, &privkey).unwrap();
+            assert!(state.is_aggregator(slot, 0, &slot_signature).unwrap());
+        }
+
+        // Committees of 32 select the validators whose slot signature hashes to an even number
+        let state = beacon_state::<MinimalEthSpec>(0, validators(1024));
+        assert_eq!(state.get_beacon_committee(0, 0).unwrap().len(), 32);
+        for slot in 0..MinimalEthSpec::SLOTS_PER_EPOCH {
+            let slot_signature = state.get_slot_signature(slot, &privkey).unwrap();
+            assert_eq!(
+                state.is_aggregator(slot, 0, &slot_signature).unwrap(),
+                hash(slot_signature.to_bytes())[0] % 2 == 0
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_validator_pubkey() {
+        let pubkey = SecretKey::from_bytes(&[1; 32])
+            .unwrap()
+            .public_key()
+            .unwrap();
+        let mut state = beacon_state::<MinimalEthSpec>(
+            0,
+            vec![
+                validator::<MinimalEthSpec>(pubkey.clone(), 0),
+                validator::<MinimalEthSpec>(PubKey::default(), 0),
+            ],
+        );
+
+        // Decompressed on demand while the cache is empty
+        assert!(state.pubkey_cache.is_empty());
+        let validator_pubkey = state.get_validator_pubkey(0).unwrap();
+        assert!(matches!(validator_pubkey, Cow::Owned(_)));
+        assert_eq!(validator_pubkey.pubkey(), &pubkey);
+
+        state.update_pubkey_cache();
+        let validator_pubkey = state.get_validator_pubkey(0).unwrap();
+        assert!(matches!(validator_pubkey, Cow::Borrowed(_)));
+        assert_eq!(validator_pubkey.pubkey(), &pubkey);
+
+        assert_eq!(
+            state.get_validator_pubkey(1).unwrap_err().to_string(),
+            "Invalid validator public key: InvalidPublicKey"
+        );
+        assert_eq!(
+            state.get_validator_pubkey(2).unwrap_err().to_string(),
+            "Invalid validator index"
+        );
+    }
+
+    fn sync_committee(pubkeys: Vec<PubKey>) -> Arc<SyncCommittee<MinimalEthSpec>> {
+        Arc::new(SyncCommittee {
+            pubkeys: FixedVector::from(pubkeys),
+            aggregate_pubkey: PubKey::default(),
+        })
+    }
+
+    #[test]
+    fn test_sync_subcommittees() {
+        let pubkey = |byte| PubKey {
+            inner: FixedVector::from(vec![byte; 48]),
+        };
+        let validators = (1..=3)
+            .map(|byte| validator::<MinimalEthSpec>(pubkey(byte), 0))
+            .collect();
+
+        // Validator 0 sits in subcommittees 0 and 1 of the current sync committee and in
+        // subcommittee 3 of the next one
+        let mut current_pubkeys = vec![pubkey(2); 32];
+        current_pubkeys[0] = pubkey(1);
+        current_pubkeys[9] = pubkey(1);
+        let mut next_pubkeys = vec![pubkey(3); 32];
+        next_pubkeys[31] = pubkey(1);
+
+        // The last slot of a period but one, whose next slot is signed by the current committee
+        let mut state = beacon_state::<MinimalEthSpec>(62, validators);
+        state.current_sync_committee = sync_committee(current_pubkeys.clone());
+        state.next_sync_committee = sync_committee(next_pubkeys.clone());
+        assert_eq!(
+            state.compute_subnets_for_sync_committee(0).unwrap(),
+            HashSet::from([0, 1])
+        );
+        assert_eq!(
+            state.compute_subnets_for_sync_committee(1).unwrap(),
+            HashSet::from([0, 1, 2, 3])
+        );
+        assert!(state
+            .compute_subnets_for_sync_committee(2)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            state.get_sync_subcommittee_pubkeys(1).unwrap(),
+            current_pubkeys[8..16]
+        );
+
+        // The last slot of a period, whose next slot is signed by the next committee
+        state.slot = 63;
+        assert_eq!(
+            state.compute_subnets_for_sync_committee(0).unwrap(),
+            HashSet::from([3])
+        );
+        assert!(state
+            .compute_subnets_for_sync_committee(1)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            state.get_sync_subcommittee_pubkeys(3).unwrap(),
+            next_pubkeys[24..32]
+        );
+
+        assert_eq!(
+            state
+                .compute_subnets_for_sync_committee(3)
+                .unwrap_err()
+                .to_string(),
+            "Validator index 3 is out of range"
+        );
+        assert_eq!(
+            state
+                .get_sync_subcommittee_pubkeys(4)
+                .unwrap_err()
+                .to_string(),
+            "Invalid sync subcommittee index 4"
+        );
+    }
+
+    #[test]
+    fn test_sync_committee_signing_roots() {
+        // A fork at epoch 2 changes the domains of the messages signed from slot 16
+        let mut state = beacon_state::<MinimalEthSpec>(16, vec![]);
+        state.fork = Fork {
+            previous_version: B32::ZERO,
+            current_version: B32::repeat_byte(1),
+            epoch: 2,
+        };
+        let block_root = B256::repeat_byte(2);
+
+        let message_signing_root = state.get_sync_committee_message_signing_root(8, block_root);
+        assert_eq!(
+            message_signing_root,
+            compute_signing_root(block_root, state.get_domain(DOMAIN_SYNC_COMMITTEE, Some(1)))
+        );
+        assert_ne!(
+            message_signing_root,
+            state.get_sync_committee_message_signing_root(16, block_root)
+        );
+
+        let selection_proof_signing_root =
+            state.get_sync_committee_selection_proof_signing_root(8, 1);
+        assert_eq!(
+            selection_proof_signing_root,
+            compute_signing_root(
+                SyncAggregatorSelectionData {
+                    slot: 8,
+                    subcommittee_index: 1,
+                },
+                state.get_domain(DOMAIN_SYNC_COMMITTEE_SELECTION_PROOF, Some(1))
+            )
+        );
+        assert_ne!(
+            selection_proof_signing_root,
+            state.get_sync_committee_selection_proof_signing_root(8, 2)
+        );
+
+        let contribution_and_proof = ContributionAndProof {
+            aggregator_index: 0,
+            contribution: SyncCommitteeContribution::new(16, block_root, 1),
+            selection_proof: BLSSignature::infinity(),
+        };
+        assert_eq!(
+            state.get_contribution_and_proof_signing_root(&contribution_and_proof),
+            compute_signing_root(
+                &contribution_and_proof,
+                state.get_domain(DOMAIN_CONTRIBUTION_AND_PROOF, Some(2))
+            )
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{serde_utils::hex_fixed_vec, typenum::Unsigned, FixedVector};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::{beacon_block::SignedBeaconBlock, beacon_state::is_valid_merkle_branch};
+use crate::{
+    beacon_block_header::SignedBeaconBlockHeader,
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::BLOB_KZG_COMMITMENTS_GINDEX,
+    kzg_commitment::KZGCommitment,
+    kzg_proof::KZGProof,
+    merkle_proof::{compute_merkle_proof_branch, concat_generalized_indices, get_subtree_index},
+};
+
+pub type Blob<E> = FixedVector<u8, <E as EthSpec>::BytesPerBlob>;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct BlobSidecar<E: EthSpec> {
+    pub index: u64,
+    #[serde(with = "hex_fixed_vec")]
+    pub blob: Blob<E>,
+    pub kzg_commitment: KZGCommitment,
+    pub kzg_proof: KZGProof,
+    pub signed_block_header: SignedBeaconBlockHeader,
+    pub kzg_commitment_inclusion_proof: FixedVector<B256, E::KzgCommitmentInclusionProofDepth>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BlobIdentifier {
+    pub block_root: B256,
+    pub index: u64,
+}
+
+/// Return the generalized index of ``blob_kzg_commitments[index]`` in ``BeaconBlockBody``.
+pub fn get_blob_kzg_commitment_generalized_index<E: EthSpec>(index: u64) -> u64 {
+    concat_generalized_indices(&[
+        BLOB_KZG_COMMITMENTS_GINDEX,
+        // ``blob_kzg_commitments`` list data
+        2,
+        <E::MaxBlobCommitmentsPerBlock as Unsigned>::U64 + index,
+    ])
+}
+
+impl<E: EthSpec> BlobSidecar<E> {
+    pub fn blob_identifier(&self) -> BlobIdentifier {
+        BlobIdentifier {
+            block_root: self.signed_block_header.message.tree_hash_root(),
+            index: self.index,
+        }
+    }
+
+    pub fn verify_blob_sidecar_inclusion_proof(&self) -> bool {
+        if self.index >= <E::MaxBlobCommitmentsPerBlock as Unsigned>::U64 {
+            return false;
+        }
+        let generalized_index = get_blob_kzg_commitment_generalized_index::<E>(self.index);
+        is_valid_merkle_branch(
+            self.kzg_commitment.tree_hash_root(),
+            &self.kzg_commitment_inclusion_proof,
+            <E::KzgCommitmentInclusionProofDepth as Unsigned>::U64,
+            get_subtree_index(generalized_index),
+            self.signed_block_header.message.body_root,
+        )
+    }
+}
+
+/// Build the sidecars of ``blobs`` committed to in ``signed_block``.
+pub fn get_blob_sidecars<E: EthSpec>(
+    signed_block: &SignedBeaconBlock<E>,
+    blobs: Vec<Blob<E>>,
+    blob_kzg_proofs: Vec<KZGProof>,
+) -> anyhow::Result<Vec<BlobSidecar<E>>> {
+    let body = &signed_block.message.body;
+    ensure!(
+        blobs.len() == body.blob_kzg_commitments.len()
+            && blob_kzg_proofs.len() == body.blob_kzg_commitments.len(),
+        "Expected {} blobs and proofs, got {} blobs and {} proofs",
+        body.blob_kzg_commitments.len(),
+        blobs.len(),
+        blob_kzg_proofs.len()
+    );
+
+    let signed_block_header = signed_block.compute_signed_block_header();
+    blobs
+        .into_iter()
+        .zip(blob_kzg_proofs)
+        .enumerate()
+        .map(|(index, (blob, kzg_proof))| {
+            Ok(BlobSidecar {
+                index: index as u64,
+                blob,
+                kzg_commitment: *body
+                    .blob_kzg_commitments
+                    .get(index)
+                    .ok_or_else(|| anyhow!("Missing KZG commitment {index}"))?,
+                kzg_proof,
+                signed_block_header: signed_block_header.clone(),
+                kzg_commitment_inclusion_proof: compute_merkle_proof_branch(
+                    body,
+                    get_blob_kzg_commitment_generalized_index::<E>(index as u64),
+                )?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::{eth_spec::MinimalEthSpec, test_utils::signed_beacon_block};
+
+    type E = MinimalEthSpec;
+
+    fn block_with_commitments(count: u8) -> SignedBeaconBlock<E> {
+        signed_beacon_block::<E>(
+            1,
+            (1..=count).map(|byte| KZGCommitment([byte; 48])).collect(),
+        )
+    }
+
+    #[test]
+    fn test_get_blob_sidecars() {
+        let block = block_with_commitments(3);
+        let sidecars = get_blob_sidecars(
+            &block,
+            vec![Blob::<E>::default(); 3],
+            vec![KZGProof::empty_for_testing(); 3],
+        )
+        .unwrap();
+
+        assert_eq!(sidecars.len(), 3);
+        for (index, sidecar) in sidecars.iter().enumerate() {
+            assert_eq!(sidecar.index, index as u64);
+            assert_eq!(
+                sidecar.kzg_commitment,
+                block.message.body.blob_kzg_commitments[index]
+            );
+            assert_eq!(
+                sidecar.blob_identifier().block_root,
+                block.message.tree_hash_root()
+            );
+            assert!(sidecar.verify_blob_sidecar_inclusion_proof());
+        }
+    }
+
+    #[test]
+    fn test_verify_blob_sidecar_inclusion_proof_rejections() {
+        let block = block_with_commitments(3);
+        let sidecar = get_blob_sidecars(
+            &block,
+            vec![Blob::<E>::default(); 3],
+            vec![KZGProof::empty_for_testing(); 3],
+        )
+        .unwrap()
+        .remove(1);
+
+        let mut other_index = sidecar.clone();
+        other_index.index = 2;
+        assert!(!other_index.verify_blob_sidecar_inclusion_proof());
+
+        let mut out_of_range_index = sidecar.clone();
+        out_of_range_index.index = 32;
+        assert!(!out_of_range_index.verify_blob_sidecar_inclusion_proof());
+
+        let mut other_commitment = sidecar.clone();
+        other_commitment.kzg_commitment = KZGCommitment([3; 48]);
+        assert!(!other_commitment.verify_blob_sidecar_inclusion_proof());
+
+        let mut other_body_root = sidecar;
+        other_body_root.signed_block_header.message.body_root = B256::repeat_byte(1);
+        assert!(!other_body_root.verify_blob_sidecar_inclusion_proof());
+    }
+
+    #[test]
+    fn test_get_blob_sidecars_count_mismatch() {
+        let block = block_with_commitments(3);
+        assert_eq!(
+            get_blob_sidecars(
+                &block,
+                vec![Blob::<E>::default(); 2],
+                vec![KZGProof::empty_for_testing(); 3],
+            )
+            .unwrap_err()
+            .to_string(),
+            "Expected 3 blobs and proofs, got 2 blobs and 3 proofs"
+        );
+        assert_eq!(
+            get_blob_sidecars(
+                &block,
+                vec![Blob::<E>::default(); 3],
+                vec![KZGProof::empty_for_testing(); 4],
+            )
+            .unwrap_err()
+            .to_string(),
+            "Expected 3 blobs and proofs, got 3 blobs and 4 proofs"
+        );
+    }
+}
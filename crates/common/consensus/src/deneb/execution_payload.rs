@@ -7,15 +7,18 @@ use ssz_types::{
     serde_utils::{hex_fixed_vec, hex_var_list, list_of_hex_var_list},
     typenum, FixedVector, VariableList,
 };
+use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
-use crate::withdrawal::Withdrawal;
+use super::execution_payload_header::ExecutionPayloadHeader;
+use crate::{eth_spec::EthSpec, withdrawal::Withdrawal};
 
 const EMPTY_UNCLE_ROOT_HASH: B256 =
     b256!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347");
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
-pub struct ExecutionPayload {
+#[serde(bound = "E: EthSpec")]
+pub struct ExecutionPayload<E: EthSpec> {
     // Execution block header fields
     pub parent_hash: B256,
     pub fee_recipient: Address,
@@ -37,12 +40,34 @@ pub struct ExecutionPayload {
     pub block_hash: B256,
     #[serde(with = "list_of_hex_var_list")]
     pub transactions: VariableList<VariableList<u8, typenum::U1073741824>, typenum::U1048576>,
-    pub withdrawals: VariableList<Withdrawal, typenum::U16>,
+    pub withdrawals: VariableList<Withdrawal, E::MaxWithdrawalsPerPayload>,
     pub blob_gas_used: u64,
     pub excess_blob_gas: u64,
 }
 
-impl ExecutionPayload {
+impl<E: EthSpec> ExecutionPayload<E> {
+    pub fn to_execution_payload_header(&self) -> ExecutionPayloadHeader {
+        ExecutionPayloadHeader {
+            parent_hash: self.parent_hash,
+            fee_recipient: self.fee_recipient,
+            state_root: self.state_root,
+            receipts_root: self.receipts_root,
+            logs_bloom: self.logs_bloom.clone(),
+            prev_randao: self.prev_randao,
+            block_number: self.block_number,
+            gas_limit: self.gas_limit,
+            gas_used: self.gas_used,
+            timestamp: self.timestamp,
+            extra_data: self.extra_data.clone(),
+            base_fee_per_gas: self.base_fee_per_gas,
+            block_hash: self.block_hash,
+            transactions_root: self.transactions.tree_hash_root(),
+            withdrawals_root: self.withdrawals.tree_hash_root(),
+            blob_gas_used: self.blob_gas_used,
+            excess_blob_gas: self.excess_blob_gas,
+        }
+    }
+
     pub fn header_hash(&self, parent_beacon_block_root: B256) -> B256 {
         let mut buf = vec![];
         self.encode(&mut buf, parent_beacon_block_root);
@@ -0,0 +1,492 @@
+use alloy_primitives::B256;
+use anyhow::{anyhow, bail, ensure};
+use ethereum_hashing::hash_fixed;
+use ssz_types::{FixedVector, VariableList};
+use tree_hash::TreeHash;
+
+use crate::{
+    deposit::Deposit,
+    deposit_data::DepositData,
+    deposit_tree_snapshot::{mix_in_length, DepositTreeSnapshot},
+    eth_1_data::Eth1Data,
+    fork_choice::helpers::constants::DEPOSIT_CONTRACT_TREE_DEPTH,
+};
+
+/// Return the root of an empty subtree of height ``level``.
+pub fn zero_hash(level: u64) -> B256 {
+    (0..level).fold(B256::ZERO, |root, _| hash_pair(root, root))
+}
+
+fn hash_pair(left: B256, right: B256) -> B256 {
+    B256::from(hash_fixed(&[left.as_slice(), right.as_slice()].concat()))
+}
+
+/// Sparse incremental Merkle tree of the deposit contract as specified in EIP-4881.
+///
+/// ``Finalized`` nodes only keep the root of a full subtree, so proofs can't be generated for
+/// deposits below them.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MerkleTree {
+    Finalized { deposit_count: u64, hash: B256 },
+    Leaf(B256),
+    Node(Box<MerkleTree>, Box<MerkleTree>),
+    Zero(u64),
+}
+
+impl MerkleTree {
+    pub fn create(leaves: &[B256], depth: u64) -> Self {
+        if leaves.is_empty() {
+            return Self::Zero(depth);
+        }
+        if depth == 0 {
+            return Self::Leaf(leaves[0]);
+        }
+        let split = (1usize << (depth - 1)).min(leaves.len());
+        Self::Node(
+            Box::new(Self::create(&leaves[..split], depth - 1)),
+            Box::new(Self::create(&leaves[split..], depth - 1)),
+        )
+    }
+
+    pub fn from_snapshot_parts(finalized: &[B256], deposit_count: u64, level: u64) -> Self {
+        if finalized.is_empty() || deposit_count == 0 {
+            return Self::Zero(level);
+        }
+        if deposit_count == 1 << level {
+            return Self::Finalized {
+                deposit_count,
+                hash: finalized[0],
+            };
+        }
+        let left_subtree = 1 << (level - 1);
+        if deposit_count <= left_subtree {
+            Self::Node(
+                Box::new(Self::from_snapshot_parts(
+                    finalized,
+                    deposit_count,
+                    level - 1,
+                )),
+                Box::new(Self::Zero(level - 1)),
+            )
+        } else {
+            Self::Node(
+                Box::new(Self::Finalized {
+                    deposit_count: left_subtree,
+                    hash: finalized[0],
+                }),
+                Box::new(Self::from_snapshot_parts(
+                    &finalized[1..],
+                    deposit_count - left_subtree,
+                    level - 1,
+                )),
+            )
+        }
+    }
+
+    pub fn get_root(&self) -> B256 {
+        match self {
+            Self::Finalized { hash, .. } | Self::Leaf(hash) => *hash,
+            Self::Node(left, right) => hash_pair(left.get_root(), right.get_root()),
+            Self::Zero(level) => zero_hash(*level),
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        match self {
+            Self::Finalized { .. } | Self::Leaf(_) => true,
+            Self::Node(_, right) => right.is_full(),
+            Self::Zero(_) => false,
+        }
+    }
+
+    pub fn push_leaf(&mut self, leaf: B256, level: u64) -> anyhow::Result<()> {
+        match self {
+            Self::Finalized { .. } | Self::Leaf(_) => {
+                bail!("Can't push a leaf into a full subtree")
+            }
+            Self::Node(left, right) => {
+                if !left.is_full() {
+                    left.push_leaf(leaf, level - 1)
+                } else {
+                    right.push_leaf(leaf, level - 1)
+                }
+            }
+            Self::Zero(_) => {
+                *self = Self::create(&[leaf], level);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn finalize(&mut self, deposits_to_finalize: u64, level: u64) -> anyhow::Result<()> {
+        match self {
+            Self::Finalized { .. } => Ok(()),
+            Self::Leaf(hash) => {
+                *self = Self::Finalized {
+                    deposit_count: 1,
+                    hash: *hash,
+                };
+                Ok(())
+            }
+            Self::Node(left, right) => {
+                let deposits = 1 << level;
+                if deposits <= deposits_to_finalize {
+                    let hash = hash_pair(left.get_root(), right.get_root());
+                    *self = Self::Finalized {
+                        deposit_count: deposits,
+                        hash,
+                    };
+                    return Ok(());
+                }
+                left.finalize(deposits_to_finalize, level - 1)?;
+                if deposits_to_finalize > deposits / 2 {
+                    right.finalize(deposits_to_finalize - deposits / 2, level - 1)?;
+                }
+                Ok(())
+            }
+            Self::Zero(_) => bail!("Can't finalize deposits that aren't in the tree"),
+        }
+    }
+
+    pub fn get_finalized(&self, result: &mut Vec<B256>) -> u64 {
+        match self {
+            Self::Finalized {
+                deposit_count,
+                hash,
+            } => {
+                result.push(*hash);
+                *deposit_count
+            }
+            Self::Leaf(hash) => {
+                result.push(*hash);
+                1
+            }
+            Self::Node(left, right) => left.get_finalized(result) + right.get_finalized(result),
+            Self::Zero(_) => 0,
+        }
+    }
+
+    /// Return the root of this subtree with every leaf from ``deposit_count`` onwards zeroed.
+    fn get_root_at(&self, deposit_count: u64, level: u64) -> anyhow::Result<B256> {
+        if deposit_count == 0 {
+            return Ok(zero_hash(level));
+        }
+        if deposit_count >= 1 << level {
+            return Ok(self.get_root());
+        }
+        match self {
+            Self::Node(left, right) => {
+                let left_subtree = 1 << (level - 1);
+                Ok(hash_pair(
+                    left.get_root_at(deposit_count.min(left_subtree), level - 1)?,
+                    right.get_root_at(deposit_count.saturating_sub(left_subtree), level - 1)?,
+                ))
+            }
+            Self::Zero(_) => Ok(zero_hash(level)),
+            Self::Finalized { .. } | Self::Leaf(_) => {
+                bail!("Can't compute a partial root of a finalized subtree")
+            }
+        }
+    }
+
+    /// Return the leaf at ``index`` and its branch in the tree made of the first
+    /// ``deposit_count`` deposits.
+    pub fn generate_proof(
+        &self,
+        index: u64,
+        deposit_count: u64,
+        depth: u64,
+    ) -> anyhow::Result<(B256, Vec<B256>)> {
+        let mut proof = vec![];
+        let mut node = self;
+        let mut deposit_count = deposit_count;
+        for level in (1..=depth).rev() {
+            let Self::Node(left, right) = node else {
+                bail!("Can't generate a proof for finalized deposit {index}");
+            };
+            let left_subtree = 1 << (level - 1);
+            if (index >> (level - 1)) & 1 == 1 {
+                proof.push(left.get_root_at(deposit_count.min(left_subtree), level - 1)?);
+                deposit_count = deposit_count.saturating_sub(left_subtree);
+                node = right;
+            } else {
+                proof.push(
+                    right.get_root_at(deposit_count.saturating_sub(left_subtree), level - 1)?,
+                );
+                deposit_count = deposit_count.min(left_subtree);
+                node = left;
+            }
+        }
+        let Self::Leaf(leaf) = node else {
+            bail!("Deposit {index} not found in the tree");
+        };
+        proof.reverse();
+        Ok((*leaf, proof))
+    }
+}
+
+/// Deposit contract tree which can be finalized and restored from a ``DepositTreeSnapshot``.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DepositTree {
+    pub tree: MerkleTree,
+    pub mix_in_length: u64,
+    pub finalized_execution_block: Option<(B256, u64)>,
+}
+
+impl Default for DepositTree {
+    fn default() -> Self {
+        Self {
+            tree: MerkleTree::Zero(DEPOSIT_CONTRACT_TREE_DEPTH),
+            mix_in_length: 0,
+            finalized_execution_block: None,
+        }
+    }
+}
+
+impl DepositTree {
+    pub fn get_snapshot(&self) -> anyhow::Result<DepositTreeSnapshot> {
+        let (execution_block_hash, execution_block_height) = self
+            .finalized_execution_block
+            .ok_or_else(|| anyhow!("Deposit tree hasn't been finalized"))?;
+        let mut finalized = vec![];
+        let deposit_count = self.tree.get_finalized(&mut finalized);
+        Ok(DepositTreeSnapshot {
+            finalized: VariableList::new(finalized)
+                .map_err(|err| anyhow!("Too many finalized hashes: {err:?}"))?,
+            deposit_root: self.get_root_at(deposit_count)?,
+            deposit_count,
+            execution_block_hash,
+            execution_block_height,
+        })
+    }
+
+    pub fn from_snapshot(snapshot: &DepositTreeSnapshot) -> anyhow::Result<Self> {
+        // ``calculate_root`` only looks at the low ``DEPOSIT_CONTRACT_TREE_DEPTH`` bits of the
+        // deposit count, so larger counts must be rejected before the root is checked
+        ensure!(
+            snapshot.deposit_count <= 1 << DEPOSIT_CONTRACT_TREE_DEPTH,
+            "Deposit tree snapshot has too many deposits"
+        );
+        ensure!(
+            snapshot.finalized.len() as u32 == snapshot.deposit_count.count_ones(),
+            "Deposit tree snapshot has {} finalized hashes for {} deposits",
+            snapshot.finalized.len(),
+            snapshot.deposit_count
+        );
+        ensure!(
+            snapshot.calculate_root() == Some(snapshot.deposit_root),
+            "Deposit tree snapshot root mismatch"
+        );
+        Ok(Self {
+            tree: MerkleTree::from_snapshot_parts(
+                &snapshot.finalized,
+                snapshot.deposit_count,
+                DEPOSIT_CONTRACT_TREE_DEPTH,
+            ),
+            mix_in_length: snapshot.deposit_count,
+            finalized_execution_block: Some((
+                snapshot.execution_block_hash,
+                snapshot.execution_block_height,
+            )),
+        })
+    }
+
+    pub fn finalize(
+        &mut self,
+        eth1_data: &Eth1Data,
+        execution_block_height: u64,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            eth1_data.deposit_count <= self.mix_in_length,
+            "Can't finalize more deposits than the tree contains"
+        );
+        self.finalized_execution_block = Some((eth1_data.block_hash, execution_block_height));
+        self.tree
+            .finalize(eth1_data.deposit_count, DEPOSIT_CONTRACT_TREE_DEPTH)
+    }
+
+    /// Return the leaf at ``index`` and its branch, including the length mix-in, against the
+    /// deposit root after ``deposit_count`` deposits.
+    pub fn get_proof(&self, index: u64, deposit_count: u64) -> anyhow::Result<(B256, Vec<B256>)> {
+        ensure!(
+            index < deposit_count && deposit_count <= self.mix_in_length,
+            "Deposit {index} is out of range for deposit count {deposit_count}"
+        );
+        let (leaf, mut proof) =
+            self.tree
+                .generate_proof(index, deposit_count, DEPOSIT_CONTRACT_TREE_DEPTH)?;
+        let mut length_bytes = B256::ZERO;
+        length_bytes[..8].copy_from_slice(&deposit_count.to_le_bytes());
+        proof.push(length_bytes);
+        Ok((leaf, proof))
+    }
+
+    /// Build the ``Deposit`` at ``index`` proven against ``eth1_data.deposit_root``.
+    pub fn get_deposit(
+        &self,
+        index: u64,
+        data: DepositData,
+        eth1_data: &Eth1Data,
+    ) -> anyhow::Result<Deposit> {
+        let (leaf, proof) = self.get_proof(index, eth1_data.deposit_count)?;
+        ensure!(
+            leaf == data.tree_hash_root(),
+            "Deposit data doesn't match deposit {index}"
+        );
+        Ok(Deposit {
+            proof: FixedVector::new(proof)
+                .map_err(|err| anyhow!("Invalid deposit proof length: {err:?}"))?,
+            data,
+        })
+    }
+
+    pub fn get_root(&self) -> B256 {
+        mix_in_length(self.tree.get_root(), self.mix_in_length)
+    }
+
+    /// Return the deposit root after the first ``deposit_count`` deposits.
+    pub fn get_root_at(&self, deposit_count: u64) -> anyhow::Result<B256> {
+        ensure!(
+            deposit_count <= self.mix_in_length,
+            "Deposit count {deposit_count} is ahead of the tree"
+        );
+        Ok(mix_in_length(
+            self.tree
+                .get_root_at(deposit_count, DEPOSIT_CONTRACT_TREE_DEPTH)?,
+            deposit_count,
+        ))
+    }
+
+    pub fn push_leaf(&mut self, leaf: B256) -> anyhow::Result<()> {
+        self.mix_in_length += 1;
+        self.tree.push_leaf(leaf, DEPOSIT_CONTRACT_TREE_DEPTH)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::deneb::beacon_state::is_valid_merkle_branch;
+
+    fn leaves(count: u64) -> Vec<B256> {
+        (0..count)
+            .map(|index| B256::from(hash_fixed(&index.to_le_bytes())))
+            .collect()
+    }
+
+    fn deposit_tree(leaves: &[B256]) -> DepositTree {
+        let mut tree = DepositTree::default();
+        for leaf in leaves {
+            tree.push_leaf(*leaf).unwrap();
+        }
+        tree
+    }
+
+    #[test]
+    fn test_deposit_tree_proofs_at_past_deposit_count() {
+        let leaves = leaves(13);
+        let tree = deposit_tree(&leaves);
+        for deposit_count in 1..=leaves.len() as u64 {
+            let root = deposit_tree(&leaves[..deposit_count as usize]).get_root();
+            for index in 0..deposit_count {
+                let (leaf, proof) = tree.get_proof(index, deposit_count).unwrap();
+                assert_eq!(leaf, leaves[index as usize]);
+                assert!(is_valid_merkle_branch(
+                    leaf,
+                    &proof,
+                    DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+                    index,
+                    root,
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deposit_tree_snapshot_roundtrip() {
+        let leaves = leaves(13);
+        let mut tree = deposit_tree(&leaves);
+        let eth1_data = Eth1Data {
+            deposit_root: deposit_tree(&leaves[..10]).get_root(),
+            deposit_count: 10,
+            block_hash: B256::repeat_byte(1),
+        };
+        tree.finalize(&eth1_data, 100).unwrap();
+        assert_eq!(tree.get_root(), deposit_tree(&leaves).get_root());
+        assert!(tree.get_proof(9, 13).is_err());
+
+        let (leaf, proof) = tree.get_proof(11, 13).unwrap();
+        assert!(is_valid_merkle_branch(
+            leaf,
+            &proof,
+            DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+            11,
+            tree.get_root(),
+        ));
+
+        let mut restored = DepositTree::from_snapshot(&deposit_tree_at(&leaves, 10)).unwrap();
+        for leaf in &leaves[10..] {
+            restored.push_leaf(*leaf).unwrap();
+        }
+        assert_eq!(restored.get_root(), tree.get_root());
+        assert_eq!(
+            restored.get_snapshot().unwrap(),
+            tree.get_snapshot().unwrap()
+        );
+
+        // The snapshot only commits to the finalized deposits
+        let snapshot = tree.get_snapshot().unwrap();
+        assert_eq!(snapshot.deposit_root, eth1_data.deposit_root);
+        assert_eq!(
+            DepositTree::from_snapshot(&snapshot).unwrap().get_root(),
+            eth1_data.deposit_root
+        );
+    }
+
+    fn deposit_tree_at(leaves: &[B256], deposit_count: usize) -> DepositTreeSnapshot {
+        let mut tree = deposit_tree(&leaves[..deposit_count]);
+        let eth1_data = Eth1Data {
+            deposit_root: tree.get_root(),
+            deposit_count: deposit_count as u64,
+            block_hash: B256::repeat_byte(1),
+        };
+        tree.finalize(&eth1_data, 100).unwrap();
+        tree.get_snapshot().unwrap()
+    }
+
+    fn snapshot(finalized: Vec<B256>, deposit_count: u64) -> DepositTreeSnapshot {
+        let mut snapshot = DepositTreeSnapshot {
+            finalized: VariableList::new(finalized).unwrap(),
+            deposit_root: B256::ZERO,
+            deposit_count,
+            execution_block_hash: B256::ZERO,
+            execution_block_height: 0,
+        };
+        snapshot.deposit_root = snapshot.calculate_root().unwrap();
+        snapshot
+    }
+
+    #[test]
+    fn test_invalid_snapshots_rejected() {
+        // The root of an empty tree is not the zero hash
+        assert!(DepositTree::from_snapshot(&DepositTreeSnapshot {
+            deposit_root: B256::ZERO,
+            ..snapshot(vec![], 0)
+        })
+        .is_err());
+
+        // Bits of the deposit count above the tree depth don't change the calculated root
+        let oversized = snapshot(
+            leaves(32),
+            (1 << DEPOSIT_CONTRACT_TREE_DEPTH) + (1 << (DEPOSIT_CONTRACT_TREE_DEPTH - 1)) - 1,
+        );
+        assert!(DepositTree::from_snapshot(&oversized).is_err());
+
+        // Extra finalized hashes are ignored by the calculated root
+        let extra_finalized = snapshot(leaves(3), 3);
+        assert!(DepositTree::from_snapshot(&extra_finalized).is_err());
+
+        assert!(DepositTree::from_snapshot(&snapshot(leaves(2), 3)).is_ok());
+    }
+}
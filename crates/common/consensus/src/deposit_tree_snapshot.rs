@@ -0,0 +1,50 @@
+use alloy_primitives::B256;
+use ethereum_hashing::hash_fixed;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{typenum::U32, VariableList};
+use tree_hash_derive::TreeHash;
+
+use crate::{
+    deposit_tree::zero_hash, fork_choice::helpers::constants::DEPOSIT_CONTRACT_TREE_DEPTH,
+};
+
+/// EIP-4881 snapshot of the finalized part of the deposit contract tree.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct DepositTreeSnapshot {
+    pub finalized: VariableList<B256, U32>,
+    pub deposit_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub deposit_count: u64,
+    pub execution_block_hash: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub execution_block_height: u64,
+}
+
+impl DepositTreeSnapshot {
+    pub fn calculate_root(&self) -> Option<B256> {
+        let mut size = self.deposit_count;
+        let mut index = self.finalized.len();
+        let mut root = zero_hash(0);
+        for level in 0..DEPOSIT_CONTRACT_TREE_DEPTH {
+            if size & 1 == 1 {
+                index = index.checked_sub(1)?;
+                root = B256::from(hash_fixed(
+                    &[self.finalized[index].as_slice(), root.as_slice()].concat(),
+                ));
+            } else {
+                root = B256::from(hash_fixed(
+                    &[root.as_slice(), zero_hash(level).as_slice()].concat(),
+                ));
+            }
+            size >>= 1;
+        }
+        Some(mix_in_length(root, self.deposit_count))
+    }
+}
+
+pub fn mix_in_length(root: B256, length: u64) -> B256 {
+    let mut length_bytes = [0u8; 32];
+    length_bytes[..8].copy_from_slice(&length.to_le_bytes());
+    B256::from(hash_fixed(&[root.as_slice(), &length_bytes].concat()))
+}
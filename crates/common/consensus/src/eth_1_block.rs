@@ -0,0 +1,55 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+use crate::{chain_spec::ChainSpec, eth_1_data::Eth1Data};
+
+/// Execution chain block as seen by the deposit contract follower.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Eth1Block {
+    pub block_hash: B256,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub deposit_root: B256,
+    pub deposit_count: u64,
+}
+
+impl Eth1Block {
+    pub fn get_eth1_data(&self) -> Eth1Data {
+        Eth1Data {
+            deposit_root: self.deposit_root,
+            deposit_count: self.deposit_count,
+            block_hash: self.block_hash,
+        }
+    }
+
+    pub fn is_candidate_block(&self, period_start: u64, spec: &ChainSpec) -> bool {
+        let follow_time = spec.seconds_per_eth1_block * spec.eth1_follow_distance;
+        self.timestamp + follow_time <= period_start
+            && self.timestamp + follow_time * 2 >= period_start
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_candidate_block() {
+        // The minimal configuration follows the eth1 chain at 16 blocks of 14 seconds
+        let spec = ChainSpec::minimal();
+        let follow_time = 16 * 14;
+        let period_start = 10_000;
+        let block = |timestamp| Eth1Block {
+            block_hash: B256::ZERO,
+            block_number: 0,
+            timestamp,
+            deposit_root: B256::ZERO,
+            deposit_count: 0,
+        };
+
+        assert!(!block(period_start - follow_time + 1).is_candidate_block(period_start, &spec));
+        assert!(block(period_start - follow_time).is_candidate_block(period_start, &spec));
+        assert!(block(period_start - 2 * follow_time).is_candidate_block(period_start, &spec));
+        assert!(!block(period_start - 2 * follow_time - 1).is_candidate_block(period_start, &spec));
+    }
+}
@@ -0,0 +1,190 @@
+use std::fmt::Debug;
+
+use ssz_types::typenum::{
+    Unsigned, U10, U1099511627776, U128, U131072, U16, U16777216, U17, U2048, U32, U4, U4096, U512,
+    U64, U65536, U8, U8192,
+};
+
+/// Preset values of the consensus specs.
+///
+/// Presets fix the SSZ list and vector lengths of the consensus containers, so they are selected
+/// at compile time through this trait. Runtime configuration values live in
+/// [`ChainSpec`](crate::chain_spec::ChainSpec).
+pub trait EthSpec: 'static + Default + Debug + Clone + Copy + PartialEq + Eq + Send + Sync {
+    // SSZ list and vector lengths
+    type SlotsPerHistoricalRoot: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type HistoricalRootsLimit: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type SlotsPerEth1VotingPeriod: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type ValidatorRegistryLimit: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type EpochsPerHistoricalVector: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type EpochsPerSlashingsVector: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type SyncCommitteeSize: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type SyncSubcommitteeSize: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type MaxWithdrawalsPerPayload: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type MaxBlobCommitmentsPerBlock: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type KzgCommitmentInclusionProofDepth: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type BytesPerBlob: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+
+    /// Name of the preset, matching the directory names of the consensus spec tests.
+    const PRESET_BASE: &'static str;
+
+    // Misc
+    const MAX_COMMITTEES_PER_SLOT: u64;
+    const TARGET_COMMITTEE_SIZE: u64;
+    const SHUFFLE_ROUND_COUNT: u8;
+
+    // Hysteresis parameters
+    const HYSTERESIS_QUOTIENT: u64;
+    const HYSTERESIS_DOWNWARD_MULTIPLIER: u64;
+    const HYSTERESIS_UPWARD_MULTIPLIER: u64;
+
+    // Gwei values
+    const EFFECTIVE_BALANCE_INCREMENT: u64;
+    const MAX_EFFECTIVE_BALANCE: u64;
+
+    // Time parameters
+    const MIN_ATTESTATION_INCLUSION_DELAY: u64;
+    const SLOTS_PER_EPOCH: u64;
+    const MIN_SEED_LOOKAHEAD: u64;
+    const MAX_SEED_LOOKAHEAD: u64;
+    const EPOCHS_PER_ETH1_VOTING_PERIOD: u64;
+    const MIN_EPOCHS_TO_INACTIVITY_PENALTY: u64;
+    const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64;
+    const SLOTS_PER_HISTORICAL_ROOT: u64 = <Self::SlotsPerHistoricalRoot as Unsigned>::U64;
+
+    // State list lengths
+    const EPOCHS_PER_HISTORICAL_VECTOR: u64 = <Self::EpochsPerHistoricalVector as Unsigned>::U64;
+    const EPOCHS_PER_SLASHINGS_VECTOR: u64 = <Self::EpochsPerSlashingsVector as Unsigned>::U64;
+
+    // Rewards and penalties
+    const BASE_REWARD_FACTOR: u64;
+    const WHISTLEBLOWER_REWARD_QUOTIENT: u64;
+    const PROPOSER_REWARD_QUOTIENT: u64;
+    const INACTIVITY_PENALTY_QUOTIENT_ALTAIR: u64;
+    const MIN_SLASHING_PENALTY_QUOTIENT: u64;
+    const PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX: u64;
+
+    // Max operations per block
+    const MAX_DEPOSITS: u64;
+    const MAX_BLOBS_PER_BLOCK: u64;
+
+    // Sync committee
+    const SYNC_COMMITTEE_SIZE: u64 = <Self::SyncCommitteeSize as Unsigned>::U64;
+    const SYNC_SUBCOMMITTEE_SIZE: u64 = <Self::SyncSubcommitteeSize as Unsigned>::U64;
+    const MIN_SYNC_COMMITTEE_PARTICIPANTS: u64;
+    const UPDATE_TIMEOUT: u64;
+
+    // Withdrawals processing
+    const MAX_WITHDRAWALS_PER_PAYLOAD: u64 = <Self::MaxWithdrawalsPerPayload as Unsigned>::U64;
+    const MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP: u64;
+}
+
+/// The `mainnet` preset.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MainnetEthSpec;
+
+impl EthSpec for MainnetEthSpec {
+    type SlotsPerHistoricalRoot = U8192;
+    type HistoricalRootsLimit = U16777216;
+    type SlotsPerEth1VotingPeriod = U2048;
+    type ValidatorRegistryLimit = U1099511627776;
+    type EpochsPerHistoricalVector = U65536;
+    type EpochsPerSlashingsVector = U8192;
+    type SyncCommitteeSize = U512;
+    type SyncSubcommitteeSize = U128;
+    type MaxWithdrawalsPerPayload = U16;
+    type MaxBlobCommitmentsPerBlock = U4096;
+    type KzgCommitmentInclusionProofDepth = U17;
+    type BytesPerBlob = U131072;
+
+    const PRESET_BASE: &'static str = "mainnet";
+
+    const MAX_COMMITTEES_PER_SLOT: u64 = 64;
+    const TARGET_COMMITTEE_SIZE: u64 = 128;
+    const SHUFFLE_ROUND_COUNT: u8 = 90;
+
+    const HYSTERESIS_QUOTIENT: u64 = 4;
+    const HYSTERESIS_DOWNWARD_MULTIPLIER: u64 = 1;
+    const HYSTERESIS_UPWARD_MULTIPLIER: u64 = 5;
+
+    const EFFECTIVE_BALANCE_INCREMENT: u64 = 1_000_000_000;
+    const MAX_EFFECTIVE_BALANCE: u64 = 32_000_000_000;
+
+    const MIN_ATTESTATION_INCLUSION_DELAY: u64 = 1;
+    const SLOTS_PER_EPOCH: u64 = 32;
+    const MIN_SEED_LOOKAHEAD: u64 = 1;
+    const MAX_SEED_LOOKAHEAD: u64 = 4;
+    const EPOCHS_PER_ETH1_VOTING_PERIOD: u64 = 64;
+    const MIN_EPOCHS_TO_INACTIVITY_PENALTY: u64 = 4;
+    const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+
+    const BASE_REWARD_FACTOR: u64 = 64;
+    const WHISTLEBLOWER_REWARD_QUOTIENT: u64 = 512;
+    const PROPOSER_REWARD_QUOTIENT: u64 = 8;
+    const INACTIVITY_PENALTY_QUOTIENT_ALTAIR: u64 = 50331648;
+    const MIN_SLASHING_PENALTY_QUOTIENT: u64 = 32; // updated value in Bellatrix
+    const PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX: u64 = 3;
+
+    const MAX_DEPOSITS: u64 = 16;
+    const MAX_BLOBS_PER_BLOCK: u64 = 6;
+
+    const MIN_SYNC_COMMITTEE_PARTICIPANTS: u64 = 1;
+    const UPDATE_TIMEOUT: u64 = 8192;
+
+    const MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP: u64 = 16384;
+}
+
+/// The `minimal` preset, used by the consensus spec tests and by devnets with short epochs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MinimalEthSpec;
+
+impl EthSpec for MinimalEthSpec {
+    type SlotsPerHistoricalRoot = U64;
+    type HistoricalRootsLimit = U16777216;
+    type SlotsPerEth1VotingPeriod = U32;
+    type ValidatorRegistryLimit = U1099511627776;
+    type EpochsPerHistoricalVector = U64;
+    type EpochsPerSlashingsVector = U64;
+    type SyncCommitteeSize = U32;
+    type SyncSubcommitteeSize = U8;
+    type MaxWithdrawalsPerPayload = U4;
+    type MaxBlobCommitmentsPerBlock = U32;
+    type KzgCommitmentInclusionProofDepth = U10;
+    type BytesPerBlob = U131072;
+
+    const PRESET_BASE: &'static str = "minimal";
+
+    const MAX_COMMITTEES_PER_SLOT: u64 = 4;
+    const TARGET_COMMITTEE_SIZE: u64 = 4;
+    const SHUFFLE_ROUND_COUNT: u8 = 10;
+
+    const HYSTERESIS_QUOTIENT: u64 = 4;
+    const HYSTERESIS_DOWNWARD_MULTIPLIER: u64 = 1;
+    const HYSTERESIS_UPWARD_MULTIPLIER: u64 = 5;
+
+    const EFFECTIVE_BALANCE_INCREMENT: u64 = 1_000_000_000;
+    const MAX_EFFECTIVE_BALANCE: u64 = 32_000_000_000;
+
+    const MIN_ATTESTATION_INCLUSION_DELAY: u64 = 1;
+    const SLOTS_PER_EPOCH: u64 = 8;
+    const MIN_SEED_LOOKAHEAD: u64 = 1;
+    const MAX_SEED_LOOKAHEAD: u64 = 4;
+    const EPOCHS_PER_ETH1_VOTING_PERIOD: u64 = 4;
+    const MIN_EPOCHS_TO_INACTIVITY_PENALTY: u64 = 4;
+    const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 8;
+
+    const BASE_REWARD_FACTOR: u64 = 64;
+    const WHISTLEBLOWER_REWARD_QUOTIENT: u64 = 512;
+    const PROPOSER_REWARD_QUOTIENT: u64 = 8;
+    const INACTIVITY_PENALTY_QUOTIENT_ALTAIR: u64 = 50331648;
+    const MIN_SLASHING_PENALTY_QUOTIENT: u64 = 32; // updated value in Bellatrix
+    const PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX: u64 = 3;
+
+    const MAX_DEPOSITS: u64 = 16;
+    const MAX_BLOBS_PER_BLOCK: u64 = 6;
+
+    const MIN_SYNC_COMMITTEE_PARTICIPANTS: u64 = 1;
+    const UPDATE_TIMEOUT: u64 = 64;
+
+    const MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP: u64 = 16;
+}
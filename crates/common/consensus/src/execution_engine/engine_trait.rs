@@ -1,13 +1,14 @@
 use async_trait::async_trait;
 
 use super::new_payload_request::NewPayloadRequest;
+use crate::eth_spec::EthSpec;
 
 #[async_trait]
 pub trait ExecutionApi {
     /// Return ``True`` if and only if ``new_payload_request`` is valid with respect to
     /// ``self.execution_state``.
-    async fn verify_and_notify_new_payload(
+    async fn verify_and_notify_new_payload<E: EthSpec>(
         &self,
-        new_payload_request: NewPayloadRequest,
+        new_payload_request: NewPayloadRequest<E>,
     ) -> anyhow::Result<bool>;
 }
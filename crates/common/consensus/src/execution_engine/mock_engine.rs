@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use serde::Deserialize;
 
 use super::{engine_trait::ExecutionApi, new_payload_request::NewPayloadRequest};
+use crate::eth_spec::EthSpec;
 
 #[derive(Deserialize, Debug)]
 pub struct MockExecutionEngine {
@@ -19,9 +20,9 @@ impl MockExecutionEngine {
 
 #[async_trait]
 impl ExecutionApi for MockExecutionEngine {
-    async fn verify_and_notify_new_payload(
+    async fn verify_and_notify_new_payload<E: EthSpec>(
         &self,
-        _new_payload_request: NewPayloadRequest,
+        _new_payload_request: NewPayloadRequest<E>,
     ) -> anyhow::Result<bool> {
         Ok(self.execution_valid)
     }
@@ -2,11 +2,12 @@ use alloy_primitives::B256;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 
-use crate::deneb::execution_payload::ExecutionPayload;
+use crate::{deneb::execution_payload::ExecutionPayload, eth_spec::EthSpec};
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
-pub struct NewPayloadRequest {
-    pub execution_payload: ExecutionPayload,
+#[serde(bound = "E: EthSpec")]
+pub struct NewPayloadRequest<E: EthSpec> {
+    pub execution_payload: ExecutionPayload<E>,
     pub versioned_hashes: Vec<B256>,
     pub parent_beacon_block_root: B256,
 }
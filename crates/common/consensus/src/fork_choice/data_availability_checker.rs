@@ -0,0 +1,487 @@
+use std::collections::{BTreeMap, HashMap};
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use tree_hash::TreeHash;
+
+use crate::{
+    deneb::{beacon_block::SignedBeaconBlock, blob_sidecar::BlobSidecar},
+    eth_spec::EthSpec,
+    kzg_commitment::KZGCommitment,
+    polynomial_commitments::{verify_blob_kzg_proof, verify_blob_kzg_proof_batch},
+};
+
+/// A block together with the verified sidecars of all of its ``blob_kzg_commitments``.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AvailableBlock<E: EthSpec> {
+    pub block: SignedBeaconBlock<E>,
+    pub blob_sidecars: Vec<BlobSidecar<E>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Availability<E: EthSpec> {
+    Available(Box<AvailableBlock<E>>),
+    MissingComponents(B256),
+}
+
+#[derive(Debug, Clone)]
+struct PendingComponents<E: EthSpec> {
+    block: Option<SignedBeaconBlock<E>>,
+    blob_sidecars: BTreeMap<u64, BlobSidecar<E>>,
+}
+
+impl<E: EthSpec> Default for PendingComponents<E> {
+    fn default() -> Self {
+        Self {
+            block: None,
+            blob_sidecars: BTreeMap::new(),
+        }
+    }
+}
+
+/// Holds imported blocks until every blob they commit to has been received and verified, as
+/// required by ``is_data_available`` before a block may be applied to fork choice.
+#[derive(Debug, Clone)]
+pub struct DataAvailabilityChecker<E: EthSpec> {
+    pending_components: HashMap<B256, PendingComponents<E>>,
+}
+
+impl<E: EthSpec> Default for DataAvailabilityChecker<E> {
+    fn default() -> Self {
+        Self {
+            pending_components: HashMap::new(),
+        }
+    }
+}
+
+impl<E: EthSpec> DataAvailabilityChecker<E> {
+    /// Verify ``blob_sidecar`` and hold it until its block is available.
+    pub fn put_blob_sidecar(
+        &mut self,
+        blob_sidecar: BlobSidecar<E>,
+    ) -> anyhow::Result<Availability<E>> {
+        ensure!(
+            blob_sidecar.index < E::MAX_BLOBS_PER_BLOCK,
+            "Blob sidecar index {} is out of range",
+            blob_sidecar.index
+        );
+        ensure!(
+            blob_sidecar.verify_blob_sidecar_inclusion_proof(),
+            "Invalid blob sidecar inclusion proof"
+        );
+        ensure!(
+            verify_blob_kzg_proof::<E>(
+                &blob_sidecar.blob,
+                &blob_sidecar.kzg_commitment,
+                &blob_sidecar.kzg_proof
+            )?,
+            "Invalid blob KZG proof"
+        );
+
+        let block_root = blob_sidecar.signed_block_header.message.tree_hash_root();
+        self.insert_blob_sidecars(block_root, vec![blob_sidecar])
+    }
+
+    /// Verify a batch of sidecars of the same block at once.
+    pub fn put_blob_sidecars(
+        &mut self,
+        block_root: B256,
+        blob_sidecars: Vec<BlobSidecar<E>>,
+    ) -> anyhow::Result<Availability<E>> {
+        for blob_sidecar in blob_sidecars.iter() {
+            ensure!(
+                blob_sidecar.signed_block_header.message.tree_hash_root() == block_root,
+                "Blob sidecar {} doesn't belong to block {block_root}",
+                blob_sidecar.index
+            );
+            ensure!(
+                blob_sidecar.index < E::MAX_BLOBS_PER_BLOCK,
+                "Blob sidecar index {} is out of range",
+                blob_sidecar.index
+            );
+            ensure!(
+                blob_sidecar.verify_blob_sidecar_inclusion_proof(),
+                "Invalid blob sidecar inclusion proof"
+            );
+        }
+        ensure!(
+            verify_blob_kzg_proof_batch::<E>(
+                &blob_sidecars
+                    .iter()
+                    .map(|blob_sidecar| blob_sidecar.blob.clone())
+                    .collect::<Vec<_>>(),
+                &blob_sidecars
+                    .iter()
+                    .map(|blob_sidecar| blob_sidecar.kzg_commitment)
+                    .collect::<Vec<_>>(),
+                &blob_sidecars
+                    .iter()
+                    .map(|blob_sidecar| blob_sidecar.kzg_proof)
+                    .collect::<Vec<_>>(),
+            )?,
+            "Invalid blob KZG proofs"
+        );
+
+        self.insert_blob_sidecars(block_root, blob_sidecars)
+    }
+
+    /// Hold ``block`` until all of its blobs are available.
+    pub fn put_block(&mut self, block: SignedBeaconBlock<E>) -> anyhow::Result<Availability<E>> {
+        let block_root = block.message.tree_hash_root();
+        self.pending_components.entry(block_root).or_default().block = Some(block);
+        self.check_availability(block_root)
+    }
+
+    /// Return ``True`` if every commitment in ``blob_kzg_commitments`` has a verified sidecar
+    /// for ``beacon_block_root``.
+    pub fn is_data_available(
+        &self,
+        beacon_block_root: B256,
+        blob_kzg_commitments: &[KZGCommitment],
+    ) -> bool {
+        if blob_kzg_commitments.is_empty() {
+            return true;
+        }
+        let Some(pending_components) = self.pending_components.get(&beacon_block_root) else {
+            return false;
+        };
+        blob_kzg_commitments
+            .iter()
+            .enumerate()
+            .all(|(index, commitment)| {
+                pending_components
+                    .blob_sidecars
+                    .get(&(index as u64))
+                    .is_some_and(|blob_sidecar| blob_sidecar.kzg_commitment == *commitment)
+            })
+    }
+
+    /// Hold verified ``blob_sidecars`` of ``block_root``, replacing any earlier sidecar with the
+    /// same index.
+    fn insert_blob_sidecars(
+        &mut self,
+        block_root: B256,
+        blob_sidecars: Vec<BlobSidecar<E>>,
+    ) -> anyhow::Result<Availability<E>> {
+        let pending_components = self.pending_components.entry(block_root).or_default();
+        for blob_sidecar in blob_sidecars {
+            pending_components
+                .blob_sidecars
+                .insert(blob_sidecar.index, blob_sidecar);
+        }
+        self.check_availability(block_root)
+    }
+
+    fn check_availability(&mut self, block_root: B256) -> anyhow::Result<Availability<E>> {
+        let Some(block) = self
+            .pending_components
+            .get(&block_root)
+            .and_then(|pending_components| pending_components.block.as_ref())
+        else {
+            return Ok(Availability::MissingComponents(block_root));
+        };
+        if !self.is_data_available(block_root, &block.message.body.blob_kzg_commitments) {
+            return Ok(Availability::MissingComponents(block_root));
+        }
+
+        let pending_components = self
+            .pending_components
+            .remove(&block_root)
+            .ok_or_else(|| anyhow!("Pending components of {block_root} not found"))?;
+        let block = pending_components
+            .block
+            .ok_or_else(|| anyhow!("Block {block_root} not found"))?;
+        let blob_sidecars = pending_components
+            .blob_sidecars
+            .into_values()
+            .take(block.message.body.blob_kzg_commitments.len())
+            .collect();
+        Ok(Availability::Available(Box::new(AvailableBlock {
+            block,
+            blob_sidecars,
+        })))
+    }
+
+    /// Drop pending components of blocks at or before ``finalized_slot``.
+    pub fn prune(&mut self, finalized_slot: u64) {
+        self.pending_components.retain(|_, pending_components| {
+            pending_components
+                .block
+                .as_ref()
+                .map(|block| block.message.slot)
+                .or_else(|| {
+                    pending_components
+                        .blob_sidecars
+                        .values()
+                        .next()
+                        .map(|blob_sidecar| blob_sidecar.signed_block_header.message.slot)
+                })
+                .is_some_and(|slot| slot > finalized_slot)
+        });
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::{
+        deneb::blob_sidecar::{get_blob_sidecars, Blob},
+        eth_spec::MinimalEthSpec,
+        polynomial_commitments::{blob_to_kzg_commitment, compute_blob_kzg_proof},
+        test_utils::{load_trusted_setup, signed_beacon_block},
+    };
+
+    type E = MinimalEthSpec;
+
+    /// Return a block at ``slot`` with ``count`` distinct blobs and their valid sidecars.
+    fn block_and_blob_sidecars(
+        slot: u64,
+        count: u8,
+    ) -> (SignedBeaconBlock<E>, Vec<BlobSidecar<E>>) {
+        load_trusted_setup();
+        let blobs = (1..=count)
+            .map(|byte| {
+                let mut blob = Blob::<E>::default();
+                // Keep the field element below the BLS modulus
+                blob[31] = byte;
+                blob
+            })
+            .collect::<Vec<_>>();
+        let commitments = blobs
+            .iter()
+            .map(|blob| blob_to_kzg_commitment::<E>(blob).unwrap())
+            .collect::<Vec<_>>();
+        let proofs = blobs
+            .iter()
+            .zip(commitments.iter())
+            .map(|(blob, commitment)| compute_blob_kzg_proof::<E>(blob, commitment).unwrap())
+            .collect();
+        let block = signed_beacon_block(slot, commitments);
+        let blob_sidecars = get_blob_sidecars(&block, blobs, proofs).unwrap();
+        (block, blob_sidecars)
+    }
+
+    fn assert_available(
+        availability: Availability<E>,
+        block: &SignedBeaconBlock<E>,
+        blob_sidecars: &[BlobSidecar<E>],
+    ) {
+        assert_eq!(
+            availability,
+            Availability::Available(Box::new(AvailableBlock {
+                block: block.clone(),
+                blob_sidecars: blob_sidecars.to_vec(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_block_before_blobs() {
+        let mut checker = DataAvailabilityChecker::<E>::default();
+        let (block, blob_sidecars) = block_and_blob_sidecars(1, 2);
+        let block_root = block.message.tree_hash_root();
+
+        assert_eq!(
+            checker.put_block(block.clone()).unwrap(),
+            Availability::MissingComponents(block_root)
+        );
+        assert_eq!(
+            checker.put_blob_sidecar(blob_sidecars[1].clone()).unwrap(),
+            Availability::MissingComponents(block_root)
+        );
+        assert!(!checker.is_data_available(block_root, &block.message.body.blob_kzg_commitments));
+        assert_available(
+            checker.put_blob_sidecar(blob_sidecars[0].clone()).unwrap(),
+            &block,
+            &blob_sidecars,
+        );
+        assert!(checker.pending_components.is_empty());
+    }
+
+    #[test]
+    fn test_blobs_before_block() {
+        let mut checker = DataAvailabilityChecker::<E>::default();
+        let (block, blob_sidecars) = block_and_blob_sidecars(1, 3);
+        let block_root = block.message.tree_hash_root();
+
+        assert_eq!(
+            checker
+                .put_blob_sidecars(block_root, blob_sidecars.clone())
+                .unwrap(),
+            Availability::MissingComponents(block_root)
+        );
+        assert!(checker.is_data_available(block_root, &block.message.body.blob_kzg_commitments));
+        assert_available(
+            checker.put_block(block.clone()).unwrap(),
+            &block,
+            &blob_sidecars,
+        );
+
+        // A block without blobs is available straight away
+        let block = signed_beacon_block(2, vec![]);
+        assert_available(checker.put_block(block.clone()).unwrap(), &block, &[]);
+        assert!(checker.pending_components.is_empty());
+    }
+
+    #[test]
+    fn test_missing_commitment_index() {
+        let mut checker = DataAvailabilityChecker::<E>::default();
+        let (block, blob_sidecars) = block_and_blob_sidecars(1, 3);
+        let block_root = block.message.tree_hash_root();
+        let mut received_sidecars = blob_sidecars.clone();
+        let missing_sidecar = received_sidecars.remove(1);
+
+        checker.put_block(block.clone()).unwrap();
+        assert_eq!(
+            checker
+                .put_blob_sidecars(block_root, received_sidecars)
+                .unwrap(),
+            Availability::MissingComponents(block_root)
+        );
+        assert!(!checker.is_data_available(block_root, &block.message.body.blob_kzg_commitments));
+        assert_available(
+            checker.put_blob_sidecar(missing_sidecar).unwrap(),
+            &block,
+            &blob_sidecars,
+        );
+    }
+
+    #[test]
+    fn test_duplicate_blob_sidecar() {
+        let mut checker = DataAvailabilityChecker::<E>::default();
+        let (block, blob_sidecars) = block_and_blob_sidecars(1, 2);
+        let block_root = block.message.tree_hash_root();
+        let first = blob_sidecars[0].clone();
+
+        assert_eq!(
+            checker
+                .put_blob_sidecars(block_root, vec![first.clone(), first.clone()])
+                .unwrap(),
+            Availability::MissingComponents(block_root)
+        );
+        assert_eq!(
+            checker.put_blob_sidecar(first).unwrap(),
+            Availability::MissingComponents(block_root)
+        );
+        assert_eq!(
+            checker.pending_components[&block_root].blob_sidecars.len(),
+            1
+        );
+
+        // The available block carries a single sidecar per commitment
+        checker.put_block(block.clone()).unwrap();
+        assert_available(
+            checker
+                .put_blob_sidecars(block_root, blob_sidecars.clone())
+                .unwrap(),
+            &block,
+            &blob_sidecars,
+        );
+    }
+
+    #[test]
+    fn test_invalid_blob_sidecar_rejected() {
+        let mut checker = DataAvailabilityChecker::<E>::default();
+        let (block, blob_sidecars) = block_and_blob_sidecars(1, 2);
+        let block_root = block.message.tree_hash_root();
+
+        let mut out_of_range = blob_sidecars[0].clone();
+        out_of_range.index = E::MAX_BLOBS_PER_BLOCK;
+        assert_eq!(
+            checker
+                .put_blob_sidecar(out_of_range)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "Blob sidecar index {} is out of range",
+                E::MAX_BLOBS_PER_BLOCK
+            )
+        );
+
+        // The commitment is not the one included in the block
+        let mut other_commitment = blob_sidecars[0].clone();
+        other_commitment.kzg_commitment = blob_sidecars[1].kzg_commitment;
+        assert_eq!(
+            checker
+                .put_blob_sidecar(other_commitment)
+                .unwrap_err()
+                .to_string(),
+            "Invalid blob sidecar inclusion proof"
+        );
+
+        // The proof is valid for another blob
+        let mut other_proof = blob_sidecars[0].clone();
+        other_proof.kzg_proof = blob_sidecars[1].kzg_proof;
+        assert_eq!(
+            checker
+                .put_blob_sidecar(other_proof.clone())
+                .unwrap_err()
+                .to_string(),
+            "Invalid blob KZG proof"
+        );
+        assert_eq!(
+            checker
+                .put_blob_sidecars(block_root, vec![other_proof, blob_sidecars[1].clone()])
+                .unwrap_err()
+                .to_string(),
+            "Invalid blob KZG proofs"
+        );
+        assert!(checker.pending_components.is_empty());
+    }
+
+    #[test]
+    fn test_put_blob_sidecars_block_root_mismatch() {
+        let mut checker = DataAvailabilityChecker::<E>::default();
+        let (block, blob_sidecars) = block_and_blob_sidecars(1, 2);
+        let block_root = block.message.tree_hash_root();
+        let (other_block, other_blob_sidecars) = block_and_blob_sidecars(2, 1);
+
+        let mut mixed_sidecars = blob_sidecars.clone();
+        mixed_sidecars.extend(other_blob_sidecars);
+        assert_eq!(
+            checker
+                .put_blob_sidecars(block_root, mixed_sidecars)
+                .unwrap_err()
+                .to_string(),
+            format!("Blob sidecar 0 doesn't belong to block {block_root}")
+        );
+        let other_block_root = other_block.message.tree_hash_root();
+        assert_eq!(
+            checker
+                .put_blob_sidecars(other_block_root, blob_sidecars)
+                .unwrap_err()
+                .to_string(),
+            format!("Blob sidecar 0 doesn't belong to block {other_block_root}")
+        );
+        assert!(checker.pending_components.is_empty());
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut checker = DataAvailabilityChecker::<E>::default();
+        let (old_block, _) = block_and_blob_sidecars(4, 1);
+        let (finalized_block, _) = block_and_blob_sidecars(5, 1);
+        let (unfinalized_block, unfinalized_blob_sidecars) = block_and_blob_sidecars(6, 2);
+        let unfinalized_block_root = unfinalized_block.message.tree_hash_root();
+
+        checker.put_block(old_block).unwrap();
+        checker.put_block(finalized_block).unwrap();
+        checker
+            .put_blob_sidecars(
+                unfinalized_block_root,
+                unfinalized_blob_sidecars.split_at(1).1.to_vec(),
+            )
+            .unwrap();
+
+        checker.prune(5);
+        assert_eq!(
+            checker.pending_components.keys().collect::<Vec<_>>(),
+            vec![&unfinalized_block_root]
+        );
+
+        // Sidecars without a block are pruned by the slot of their header
+        checker.prune(6);
+        assert!(checker.pending_components.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "kzg")]
+pub mod data_availability_checker;
 pub mod helpers;
 pub mod latest_message;
 pub mod store;
@@ -2,20 +2,20 @@ use alloy_primitives::{map::HashMap, B256};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    helpers::constants::{
-        GENESIS_EPOCH, GENESIS_SLOT, INTERVALS_PER_SLOT, REORG_HEAD_WEIGHT_THRESHOLD,
-        REORG_MAX_EPOCHS_SINCE_FINALIZATION, REORG_PARENT_WEIGHT_THRESHOLD, SECONDS_PER_SLOT,
-    },
+    helpers::constants::{GENESIS_EPOCH, GENESIS_SLOT, INTERVALS_PER_SLOT},
     latest_message::LatestMessage,
 };
 use crate::{
+    chain_spec::ChainSpec,
     checkpoint::Checkpoint,
     deneb::{beacon_block::BeaconBlock, beacon_state::BeaconState},
+    eth_spec::EthSpec,
     helpers::{calculate_committee_fraction, get_voting_source, get_weight},
     misc::{compute_epoch_at_slot, compute_start_slot_at_epoch, is_shuffling_stable},
 };
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-pub struct Store {
+#[serde(bound = "E: EthSpec")]
+pub struct Store<E: EthSpec> {
     pub time: u64,
     pub genesis_time: u64,
     pub justified_checkpoint: Checkpoint,
@@ -24,22 +24,22 @@ pub struct Store {
     pub unrealized_finalized_checkpoint: Checkpoint,
     pub proposer_boost_root: B256,
     pub equivocating_indices: Vec<u64>,
-    pub blocks: HashMap<B256, BeaconBlock>,
-    pub block_states: HashMap<B256, BeaconState>,
+    pub blocks: HashMap<B256, BeaconBlock<E>>,
+    pub block_states: HashMap<B256, BeaconState<E>>,
     pub block_timeliness: HashMap<B256, bool>,
-    pub checkpoint_states: HashMap<Checkpoint, BeaconState>,
+    pub checkpoint_states: HashMap<Checkpoint, BeaconState<E>>,
     pub latest_messages: HashMap<u64, LatestMessage>,
     pub unrealized_justifications: HashMap<B256, Checkpoint>,
 }
 
-impl Store {
+impl<E: EthSpec> Store<E> {
     pub fn is_previous_epoch_justified(&self) -> bool {
-        let current_epoch = compute_epoch_at_slot(self.get_current_store_slot());
+        let current_epoch = compute_epoch_at_slot::<E>(self.get_current_store_slot());
         self.justified_checkpoint.epoch + 1 == current_epoch
     }
 
     pub fn get_current_store_slot(&self) -> u64 {
-        compute_epoch_at_slot(self.get_current_slot())
+        compute_epoch_at_slot::<E>(self.get_current_slot())
     }
 
     pub fn get_current_slot(&self) -> u64 {
@@ -63,14 +63,14 @@ impl Store {
     }
 
     pub fn get_checkpoint_block(&self, root: B256, epoch: u64) -> B256 {
-        let epoch_first_slot = compute_start_slot_at_epoch(epoch);
+        let epoch_first_slot = compute_start_slot_at_epoch::<E>(epoch);
         self.get_ancestor(root, epoch_first_slot)
     }
 
     pub fn filter_block_tree(
         &self,
         block_root: B256,
-        blocks: &mut HashMap<B256, BeaconBlock>,
+        blocks: &mut HashMap<B256, BeaconBlock<E>>,
     ) -> bool {
         let block = &self.blocks[&block_root];
 
@@ -94,7 +94,7 @@ impl Store {
             return false;
         }
 
-        let current_epoch = compute_epoch_at_slot(self.get_current_slot());
+        let current_epoch = compute_epoch_at_slot::<E>(self.get_current_slot());
         let voting_source = get_voting_source(self, block_root);
 
         let correct_justified = self.justified_checkpoint.epoch == GENESIS_EPOCH || {
@@ -154,45 +154,47 @@ impl Store {
             == self.unrealized_justifications.get(&parent_root)
     }
 
-    pub fn is_proposing_on_time(&self) -> bool {
-        let time_into_slot = (self.time - self.genesis_time) % SECONDS_PER_SLOT;
-        let proposer_reorg_cutoff = SECONDS_PER_SLOT / INTERVALS_PER_SLOT / 2;
+    pub fn is_proposing_on_time(&self, spec: &ChainSpec) -> bool {
+        let time_into_slot = (self.time - self.genesis_time) % spec.seconds_per_slot;
+        let proposer_reorg_cutoff = spec.seconds_per_slot / INTERVALS_PER_SLOT / 2;
         time_into_slot <= proposer_reorg_cutoff
     }
 
-    pub fn is_finalization_ok(&self, slot: u64) -> bool {
+    pub fn is_finalization_ok(&self, slot: u64, spec: &ChainSpec) -> bool {
         let epochs_since_finalization =
-            compute_epoch_at_slot(slot) - self.finalized_checkpoint.epoch;
-        epochs_since_finalization <= REORG_MAX_EPOCHS_SINCE_FINALIZATION
+            compute_epoch_at_slot::<E>(slot) - self.finalized_checkpoint.epoch;
+        epochs_since_finalization <= spec.reorg_max_epochs_since_finalization
     }
 
-    pub fn is_head_weak(&self, head_root: B256) -> bool {
+    pub fn is_head_weak(&self, head_root: B256, spec: &ChainSpec) -> bool {
         let justified_state = self
             .checkpoint_states
             .get(&self.justified_checkpoint)
             .expect("Justified checkpoint must exist in the store");
 
         let reorg_threshold =
-            calculate_committee_fraction(justified_state.clone(), REORG_HEAD_WEIGHT_THRESHOLD);
-        let head_weight = get_weight(self.clone(), head_root);
+            calculate_committee_fraction(justified_state.clone(), spec.reorg_head_weight_threshold);
+        let head_weight = get_weight(self.clone(), head_root, spec);
 
         head_weight < reorg_threshold
     }
 
-    pub fn is_parent_strong(&self, parent_root: B256) -> bool {
+    pub fn is_parent_strong(&self, parent_root: B256, spec: &ChainSpec) -> bool {
         let justified_state = self
             .checkpoint_states
             .get(&self.justified_checkpoint)
             .expect("Justified checkpoint must exist in the store");
 
-        let parent_threshold =
-            calculate_committee_fraction(justified_state.clone(), REORG_PARENT_WEIGHT_THRESHOLD);
-        let parent_weight = get_weight(self.clone(), parent_root);
+        let parent_threshold = calculate_committee_fraction(
+            justified_state.clone(),
+            spec.reorg_parent_weight_threshold,
+        );
+        let parent_weight = get_weight(self.clone(), parent_root, spec);
 
         parent_weight > parent_threshold
     }
 
-    pub fn get_proposer_head(&self, head_root: B256, slot: u64) -> B256 {
+    pub fn get_proposer_head(&self, head_root: B256, slot: u64, spec: &ChainSpec) -> B256 {
         let head_block = self.blocks.get(&head_root).expect("Head block must exist");
         let parent_root = head_block.parent_root;
         let parent_block = self
@@ -202,22 +204,22 @@ impl Store {
 
         let head_late = self.is_head_late(head_root);
 
-        let shuffling_stable = is_shuffling_stable(slot);
+        let shuffling_stable = is_shuffling_stable::<E>(slot);
 
         let ffg_competitive = self.is_ffg_competitive(head_root, parent_root);
 
-        let finalization_ok = self.is_finalization_ok(slot);
+        let finalization_ok = self.is_finalization_ok(slot, spec);
 
-        let proposing_on_time = self.is_proposing_on_time();
+        let proposing_on_time = self.is_proposing_on_time(spec);
 
         let parent_slot_ok = parent_block.slot + 1 == head_block.slot;
         let current_time_ok = head_block.slot + 1 == slot;
         let single_slot_reorg = parent_slot_ok && current_time_ok;
 
         assert!(self.proposer_boost_root != head_root); // Ensure boost has worn off
-        let head_weak = self.is_head_weak(head_root);
+        let head_weak = self.is_head_weak(head_root, spec);
 
-        let parent_strong = self.is_parent_strong(parent_root);
+        let parent_strong = self.is_parent_strong(parent_root, spec);
 
         if head_late
             && shuffling_stable
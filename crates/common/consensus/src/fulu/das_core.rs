@@ -0,0 +1,281 @@
+use std::collections::BTreeSet;
+
+use alloy_primitives::U256;
+use anyhow::{anyhow, ensure};
+use ethereum_hashing::hash;
+
+#[cfg(feature = "kzg")]
+use super::polynomial_commitments_sampling::{
+    cells_to_blob, compute_cells_and_kzg_proofs, recover_cells_and_kzg_proofs,
+};
+use super::{data_column_sidecar::DataColumnSidecar, polynomial_commitments_sampling::Cell};
+#[cfg(feature = "kzg")]
+use crate::deneb::blob_sidecar::Blob;
+use crate::{
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::{NUMBER_OF_COLUMNS, NUMBER_OF_CUSTODY_GROUPS},
+    kzg_proof::KZGProof,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatrixEntry {
+    pub cell: Cell,
+    pub kzg_proof: KZGProof,
+    pub column_index: u64,
+    pub row_index: u64,
+}
+
+pub fn get_custody_groups(node_id: U256, custody_group_count: u64) -> anyhow::Result<Vec<u64>> {
+    ensure!(
+        custody_group_count <= NUMBER_OF_CUSTODY_GROUPS,
+        "Custody group count {custody_group_count} exceeds {NUMBER_OF_CUSTODY_GROUPS}"
+    );
+
+    // Skip computation if all groups are custodied
+    if custody_group_count == NUMBER_OF_CUSTODY_GROUPS {
+        return Ok((0..NUMBER_OF_CUSTODY_GROUPS).collect());
+    }
+
+    let mut current_id = node_id;
+    let mut custody_groups = BTreeSet::new();
+    while (custody_groups.len() as u64) < custody_group_count {
+        let digest = hash(&current_id.to_le_bytes::<32>());
+        let custody_group = u64::from_le_bytes(
+            digest[0..8]
+                .try_into()
+                .map_err(|_| anyhow!("Digest is too short"))?,
+        ) % NUMBER_OF_CUSTODY_GROUPS;
+        custody_groups.insert(custody_group);
+        current_id = current_id.wrapping_add(U256::from(1));
+    }
+
+    Ok(custody_groups.into_iter().collect())
+}
+
+pub fn compute_columns_for_custody_group(custody_group: u64) -> anyhow::Result<Vec<u64>> {
+    ensure!(
+        custody_group < NUMBER_OF_CUSTODY_GROUPS,
+        "Custody group {custody_group} is out of range"
+    );
+    let columns_per_group = NUMBER_OF_COLUMNS / NUMBER_OF_CUSTODY_GROUPS;
+    Ok((0..columns_per_group)
+        .map(|index| NUMBER_OF_CUSTODY_GROUPS * index + custody_group)
+        .collect())
+}
+
+/// Return the full, flattened sequence of matrix entries.
+///
+/// This helper demonstrates the relationship between blobs and the matrix of cells/proofs.
+#[cfg(feature = "kzg")]
+pub fn compute_matrix<E: EthSpec>(blobs: &[Blob<E>]) -> anyhow::Result<Vec<MatrixEntry>> {
+    let mut matrix = vec![];
+    for (blob_index, blob) in blobs.iter().enumerate() {
+        let (cells, proofs) = compute_cells_and_kzg_proofs::<E>(blob)?;
+        for (cell_index, (cell, kzg_proof)) in cells.into_iter().zip(proofs).enumerate() {
+            matrix.push(MatrixEntry {
+                cell,
+                kzg_proof,
+                column_index: cell_index as u64,
+                row_index: blob_index as u64,
+            });
+        }
+    }
+    Ok(matrix)
+}
+
+/// Recover the full, flattened sequence of matrix entries.
+///
+/// This helper demonstrates how to apply ``recover_cells_and_kzg_proofs``. Each row needs at
+/// least 50% of its columns.
+#[cfg(feature = "kzg")]
+pub fn recover_matrix(
+    partial_matrix: &[MatrixEntry],
+    blob_count: u64,
+) -> anyhow::Result<Vec<MatrixEntry>> {
+    let mut matrix = vec![];
+    for blob_index in 0..blob_count {
+        let mut row = partial_matrix
+            .iter()
+            .filter(|entry| entry.row_index == blob_index)
+            .collect::<Vec<_>>();
+        row.sort_by_key(|entry| entry.column_index);
+        row.dedup_by_key(|entry| entry.column_index);
+        let cell_indices = row
+            .iter()
+            .map(|entry| entry.column_index)
+            .collect::<Vec<_>>();
+        let cells = row
+            .iter()
+            .map(|entry| entry.cell.clone())
+            .collect::<Vec<_>>();
+
+        let (recovered_cells, recovered_proofs) =
+            recover_cells_and_kzg_proofs(&cell_indices, &cells)?;
+        for (cell_index, (cell, kzg_proof)) in recovered_cells
+            .into_iter()
+            .zip(recovered_proofs)
+            .enumerate()
+        {
+            matrix.push(MatrixEntry {
+                cell,
+                kzg_proof,
+                column_index: cell_index as u64,
+                row_index: blob_index,
+            });
+        }
+    }
+    Ok(matrix)
+}
+
+/// Flatten the cells and proofs of ``data_column_sidecars`` into matrix entries.
+pub fn get_partial_matrix<E: EthSpec>(
+    data_column_sidecars: &[DataColumnSidecar<E>],
+) -> Vec<MatrixEntry> {
+    data_column_sidecars
+        .iter()
+        .flat_map(|sidecar| {
+            sidecar
+                .column
+                .iter()
+                .zip(sidecar.kzg_proofs.iter())
+                .enumerate()
+                .map(|(row_index, (cell, kzg_proof))| MatrixEntry {
+                    cell: cell.clone(),
+                    kzg_proof: *kzg_proof,
+                    column_index: sidecar.index,
+                    row_index: row_index as u64,
+                })
+        })
+        .collect()
+}
+
+/// Reconstruct the blobs of a block from at least 50% of its data column sidecars.
+///
+/// The sidecars must have been verified and belong to the same block.
+#[cfg(feature = "kzg")]
+pub fn reconstruct_blobs<E: EthSpec>(
+    data_column_sidecars: &[DataColumnSidecar<E>],
+) -> anyhow::Result<Vec<Blob<E>>> {
+    let first_sidecar = data_column_sidecars
+        .first()
+        .ok_or_else(|| anyhow!("No data column sidecars to reconstruct from"))?;
+    ensure!(
+        data_column_sidecars
+            .iter()
+            .all(|sidecar| { sidecar.signed_block_header == first_sidecar.signed_block_header }),
+        "Data column sidecars belong to different blocks"
+    );
+
+    let blob_count = first_sidecar.kzg_commitments.len();
+    let matrix = recover_matrix(&get_partial_matrix(data_column_sidecars), blob_count as u64)?;
+    matrix
+        .chunks(NUMBER_OF_COLUMNS as usize)
+        .map(|row| {
+            cells_to_blob::<E>(
+                &row.iter()
+                    .map(|entry| entry.cell.clone())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::BLSSignature;
+    use ssz_types::{FixedVector, VariableList};
+
+    use super::*;
+    use crate::{
+        beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader},
+        eth_spec::MinimalEthSpec,
+        kzg_commitment::KZGCommitment,
+    };
+
+    #[test]
+    fn test_get_custody_groups() {
+        let node_id = U256::from_be_bytes(B256::repeat_byte(7).0);
+        let custody_groups = get_custody_groups(node_id, 8).unwrap();
+        assert_eq!(custody_groups.len(), 8);
+        assert!(custody_groups.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(custody_groups
+            .iter()
+            .all(|group| *group < NUMBER_OF_CUSTODY_GROUPS));
+
+        // Custodying more groups keeps the groups already custodied
+        let more_custody_groups = get_custody_groups(node_id, 16).unwrap();
+        assert!(custody_groups
+            .iter()
+            .all(|group| more_custody_groups.contains(group)));
+
+        // The node ID wraps around instead of overflowing
+        assert_eq!(get_custody_groups(U256::MAX, 4).unwrap().len(), 4);
+
+        assert!(get_custody_groups(node_id, 0).unwrap().is_empty());
+        assert_eq!(
+            get_custody_groups(node_id, NUMBER_OF_CUSTODY_GROUPS).unwrap(),
+            (0..NUMBER_OF_CUSTODY_GROUPS).collect::<Vec<_>>()
+        );
+        assert!(get_custody_groups(node_id, NUMBER_OF_CUSTODY_GROUPS + 1).is_err());
+    }
+
+    #[test]
+    fn test_compute_columns_for_custody_group() {
+        let mut columns = (0..NUMBER_OF_CUSTODY_GROUPS)
+            .flat_map(|custody_group| compute_columns_for_custody_group(custody_group).unwrap())
+            .collect::<Vec<_>>();
+        columns.sort();
+        assert_eq!(columns, (0..NUMBER_OF_COLUMNS).collect::<Vec<_>>());
+
+        assert_eq!(compute_columns_for_custody_group(5).unwrap(), vec![5]);
+        assert!(compute_columns_for_custody_group(NUMBER_OF_CUSTODY_GROUPS).is_err());
+    }
+
+    #[test]
+    fn test_get_partial_matrix() {
+        let cell = |byte| FixedVector::from(vec![byte; 2048]);
+        let sidecar = |index: u64| DataColumnSidecar::<MinimalEthSpec> {
+            index,
+            column: VariableList::from(vec![cell(index as u8), cell(index as u8 + 1)]),
+            kzg_commitments: VariableList::from(vec![KZGCommitment([0; 48]); 2]),
+            kzg_proofs: VariableList::from(vec![KZGProof([index as u8; 48]); 2]),
+            signed_block_header: SignedBeaconBlockHeader {
+                message: BeaconBlockHeader::default(),
+                signature: BLSSignature::infinity(),
+            },
+            kzg_commitments_inclusion_proof: FixedVector::default(),
+        };
+
+        assert_eq!(
+            get_partial_matrix(&[sidecar(3), sidecar(9)]),
+            vec![
+                MatrixEntry {
+                    cell: cell(3),
+                    kzg_proof: KZGProof([3; 48]),
+                    column_index: 3,
+                    row_index: 0,
+                },
+                MatrixEntry {
+                    cell: cell(4),
+                    kzg_proof: KZGProof([3; 48]),
+                    column_index: 3,
+                    row_index: 1,
+                },
+                MatrixEntry {
+                    cell: cell(9),
+                    kzg_proof: KZGProof([9; 48]),
+                    column_index: 9,
+                    row_index: 0,
+                },
+                MatrixEntry {
+                    cell: cell(10),
+                    kzg_proof: KZGProof([9; 48]),
+                    column_index: 9,
+                    row_index: 1,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use alloy_primitives::B256;
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    typenum::{U128, U4},
+    FixedVector, VariableList,
+};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+#[cfg(feature = "kzg")]
+use super::polynomial_commitments_sampling::verify_cell_kzg_proof_batch;
+use super::polynomial_commitments_sampling::{Cell, CellsAndKZGProofs};
+use crate::{
+    beacon_block_header::SignedBeaconBlockHeader,
+    chain_spec::ChainSpec,
+    deneb::{beacon_block::SignedBeaconBlock, beacon_state::is_valid_merkle_branch},
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::{
+        BLOB_KZG_COMMITMENTS_GINDEX, KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH, NUMBER_OF_COLUMNS,
+    },
+    kzg_commitment::KZGCommitment,
+    kzg_proof::KZGProof,
+    merkle_proof::{compute_merkle_proof_branch, get_subtree_index},
+    misc::compute_epoch_at_slot,
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct DataColumnSidecar<E: EthSpec> {
+    pub index: u64,
+    pub column: VariableList<Cell, E::MaxBlobCommitmentsPerBlock>,
+    pub kzg_commitments: VariableList<KZGCommitment, E::MaxBlobCommitmentsPerBlock>,
+    pub kzg_proofs: VariableList<KZGProof, E::MaxBlobCommitmentsPerBlock>,
+    pub signed_block_header: SignedBeaconBlockHeader,
+    pub kzg_commitments_inclusion_proof: FixedVector<B256, U4>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct DataColumnsByRootIdentifier {
+    pub block_root: B256,
+    pub columns: VariableList<u64, U128>,
+}
+
+impl<E: EthSpec> DataColumnSidecar<E> {
+    /// Verify if the data column sidecar is valid.
+    pub fn verify_data_column_sidecar(&self, spec: &ChainSpec) -> bool {
+        // The sidecar index must be within the valid range
+        if self.index >= NUMBER_OF_COLUMNS {
+            return false;
+        }
+
+        // A sidecar for zero blobs is invalid
+        if self.kzg_commitments.is_empty() {
+            return false;
+        }
+
+        // Check that the sidecar respects the blob limit
+        let epoch = compute_epoch_at_slot::<E>(self.signed_block_header.message.slot);
+        if self.kzg_commitments.len() as u64 > spec.get_max_blobs_per_block(epoch) {
+            return false;
+        }
+
+        // The column length must be equal to the number of commitments/proofs
+        self.column.len() == self.kzg_commitments.len()
+            && self.column.len() == self.kzg_proofs.len()
+    }
+
+    /// Verify if the KZG proofs are correct.
+    #[cfg(feature = "kzg")]
+    pub fn verify_data_column_sidecar_kzg_proofs(&self) -> anyhow::Result<bool> {
+        // The column index also represents the cell index
+        let cell_indices = vec![self.index; self.column.len()];
+
+        // Batch verify that the cells match the corresponding commitments and proofs
+        verify_cell_kzg_proof_batch(
+            &self.kzg_commitments,
+            &cell_indices,
+            &self.column,
+            &self.kzg_proofs,
+        )
+    }
+
+    /// Verify if the given KZG commitments included in the given beacon block.
+    pub fn verify_data_column_sidecar_inclusion_proof(&self) -> bool {
+        is_valid_merkle_branch(
+            self.kzg_commitments.tree_hash_root(),
+            &self.kzg_commitments_inclusion_proof,
+            KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH,
+            get_subtree_index(BLOB_KZG_COMMITMENTS_GINDEX),
+            self.signed_block_header.message.body_root,
+        )
+    }
+}
+
+/// Given a signed block header and the commitments, inclusion proof, cells/proofs associated with
+/// each blob in the block, assemble the sidecars which can be distributed to peers.
+pub fn get_data_column_sidecars<E: EthSpec>(
+    signed_block_header: SignedBeaconBlockHeader,
+    kzg_commitments: VariableList<KZGCommitment, E::MaxBlobCommitmentsPerBlock>,
+    kzg_commitments_inclusion_proof: FixedVector<B256, U4>,
+    cells_and_kzg_proofs: &[CellsAndKZGProofs],
+) -> anyhow::Result<Vec<DataColumnSidecar<E>>> {
+    ensure!(
+        cells_and_kzg_proofs.len() == kzg_commitments.len(),
+        "Expected cells and proofs for {} blobs, got {}",
+        kzg_commitments.len(),
+        cells_and_kzg_proofs.len()
+    );
+    ensure!(
+        cells_and_kzg_proofs.iter().all(|(cells, proofs)| {
+            cells.len() as u64 == NUMBER_OF_COLUMNS && proofs.len() as u64 == NUMBER_OF_COLUMNS
+        }),
+        "Every blob must have {NUMBER_OF_COLUMNS} cells and proofs"
+    );
+
+    (0..NUMBER_OF_COLUMNS as usize)
+        .map(|column_index| {
+            let (column_cells, column_proofs): (Vec<_>, Vec<_>) = cells_and_kzg_proofs
+                .iter()
+                .map(|(cells, proofs)| (cells[column_index].clone(), proofs[column_index]))
+                .unzip();
+            Ok(DataColumnSidecar {
+                index: column_index as u64,
+                column: VariableList::from(column_cells),
+                kzg_commitments: kzg_commitments.clone(),
+                kzg_proofs: VariableList::from(column_proofs),
+                signed_block_header: signed_block_header.clone(),
+                kzg_commitments_inclusion_proof: kzg_commitments_inclusion_proof.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Given a signed block and the cells/proofs associated with each blob in the block, assemble
+/// the sidecars which can be distributed to peers.
+pub fn get_data_column_sidecars_from_block<E: EthSpec>(
+    signed_block: &SignedBeaconBlock<E>,
+    cells_and_kzg_proofs: &[CellsAndKZGProofs],
+) -> anyhow::Result<Vec<DataColumnSidecar<E>>> {
+    let body = &signed_block.message.body;
+    get_data_column_sidecars(
+        signed_block.compute_signed_block_header(),
+        body.blob_kzg_commitments.clone(),
+        compute_merkle_proof_branch(body, BLOB_KZG_COMMITMENTS_GINDEX)?,
+        cells_and_kzg_proofs,
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::{
+        chain_spec::BlobScheduleEntry, eth_spec::MinimalEthSpec, test_utils::signed_beacon_block,
+    };
+
+    fn cell(byte: u8) -> Cell {
+        FixedVector::from(vec![byte; 2048])
+    }
+
+    fn data_column_sidecar(slot: u64, blob_count: u8) -> DataColumnSidecar<MinimalEthSpec> {
+        let block = signed_beacon_block::<MinimalEthSpec>(slot, vec![]);
+        DataColumnSidecar {
+            index: 0,
+            column: VariableList::from((0..blob_count).map(cell).collect::<Vec<_>>()),
+            kzg_commitments: VariableList::from(vec![KZGCommitment([0; 48]); blob_count as usize]),
+            kzg_proofs: VariableList::from(vec![KZGProof([0; 48]); blob_count as usize]),
+            signed_block_header: block.compute_signed_block_header(),
+            kzg_commitments_inclusion_proof: FixedVector::default(),
+        }
+    }
+
+    #[test]
+    fn test_verify_data_column_sidecar() {
+        let spec = ChainSpec::minimal();
+        assert!(data_column_sidecar(0, 1).verify_data_column_sidecar(&spec));
+
+        let sidecar = DataColumnSidecar {
+            index: NUMBER_OF_COLUMNS,
+            ..data_column_sidecar(0, 1)
+        };
+        assert!(!sidecar.verify_data_column_sidecar(&spec));
+
+        assert!(!data_column_sidecar(0, 0).verify_data_column_sidecar(&spec));
+
+        let mut sidecar = data_column_sidecar(0, 2);
+        sidecar.kzg_proofs = VariableList::from(vec![KZGProof([0; 48])]);
+        assert!(!sidecar.verify_data_column_sidecar(&spec));
+    }
+
+    #[test]
+    fn test_verify_data_column_sidecar_blob_limit() {
+        let spec = ChainSpec {
+            blob_schedule: vec![BlobScheduleEntry {
+                epoch: 2,
+                max_blobs_per_block: 12,
+            }],
+            ..ChainSpec::minimal()
+        };
+        let first_scheduled_slot = 2 * MinimalEthSpec::SLOTS_PER_EPOCH;
+
+        // The Electra limit applies until the first scheduled epoch
+        assert!(data_column_sidecar(0, 9).verify_data_column_sidecar(&spec));
+        assert!(!data_column_sidecar(0, 10).verify_data_column_sidecar(&spec));
+        assert!(
+            !data_column_sidecar(first_scheduled_slot - 1, 10).verify_data_column_sidecar(&spec)
+        );
+        assert!(data_column_sidecar(first_scheduled_slot, 12).verify_data_column_sidecar(&spec));
+        assert!(!data_column_sidecar(first_scheduled_slot, 13).verify_data_column_sidecar(&spec));
+    }
+
+    #[test]
+    fn test_get_data_column_sidecars_from_block() {
+        let block = signed_beacon_block::<MinimalEthSpec>(
+            1,
+            vec![KZGCommitment([1; 48]), KZGCommitment([2; 48])],
+        );
+        let cells_and_kzg_proofs = (0..2)
+            .map(|row| {
+                (
+                    (0..NUMBER_OF_COLUMNS)
+                        .map(|column| cell(row * 128 + column as u8))
+                        .collect::<Vec<_>>(),
+                    (0..NUMBER_OF_COLUMNS)
+                        .map(|column| KZGProof([row * 128 + column as u8; 48]))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let sidecars = get_data_column_sidecars_from_block(&block, &cells_and_kzg_proofs).unwrap();
+        assert_eq!(sidecars.len() as u64, NUMBER_OF_COLUMNS);
+        for (column, sidecar) in sidecars.iter().enumerate() {
+            assert_eq!(sidecar.index, column as u64);
+            assert_eq!(
+                sidecar.column.to_vec(),
+                vec![cell(column as u8), cell(128 + column as u8)]
+            );
+            assert_eq!(
+                sidecar.kzg_proofs.to_vec(),
+                vec![
+                    KZGProof([column as u8; 48]),
+                    KZGProof([128 + column as u8; 48])
+                ]
+            );
+            assert_eq!(
+                sidecar.kzg_commitments,
+                block.message.body.blob_kzg_commitments
+            );
+            assert!(sidecar.verify_data_column_sidecar_inclusion_proof());
+        }
+
+        // The inclusion proof must match the commitments
+        let mut sidecar = sidecars[0].clone();
+        sidecar.kzg_commitments[1] = KZGCommitment([3; 48]);
+        assert!(!sidecar.verify_data_column_sidecar_inclusion_proof());
+
+        assert_eq!(
+            get_data_column_sidecars_from_block(&block, &cells_and_kzg_proofs[..1])
+                .unwrap_err()
+                .to_string(),
+            "Expected cells and proofs for 2 blobs, got 1"
+        );
+        let mut missing_cell = cells_and_kzg_proofs.clone();
+        missing_cell[1].0.pop();
+        assert_eq!(
+            get_data_column_sidecars_from_block(&block, &missing_cell)
+                .unwrap_err()
+                .to_string(),
+            "Every blob must have 128 cells and proofs"
+        );
+    }
+}
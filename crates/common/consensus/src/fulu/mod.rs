@@ -0,0 +1,3 @@
+pub mod das_core;
+pub mod data_column_sidecar;
+pub mod polynomial_commitments_sampling;
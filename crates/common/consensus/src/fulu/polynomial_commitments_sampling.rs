@@ -0,0 +1,212 @@
+use anyhow::{anyhow, ensure};
+#[cfg(feature = "kzg")]
+use kzg::{das::DAS, Fr, G1};
+#[cfg(feature = "kzg")]
+use rust_kzg_blst::types::{fr::FsFr, g1::FsG1};
+use ssz_types::{typenum::U2048, FixedVector};
+
+use crate::{
+    deneb::blob_sidecar::Blob, eth_spec::EthSpec,
+    fork_choice::helpers::constants::CELLS_PER_EXT_BLOB, kzg_proof::KZGProof,
+};
+#[cfg(feature = "kzg")]
+use crate::{
+    fork_choice::helpers::constants::FIELD_ELEMENTS_PER_CELL,
+    kzg_commitment::KZGCommitment,
+    polynomial_commitments::{blob_to_field_elements, bytes_to_g1, kzg_settings},
+};
+
+pub type Cell = FixedVector<u8, U2048>;
+
+/// The cells and proofs of all ``CELLS_PER_EXT_BLOB`` columns of one extended blob.
+pub type CellsAndKZGProofs = (Vec<Cell>, Vec<KZGProof>);
+
+#[cfg(feature = "kzg")]
+fn cell_to_field_elements(cell: &Cell) -> anyhow::Result<[FsFr; FIELD_ELEMENTS_PER_CELL]> {
+    let mut field_elements = [FsFr::default(); FIELD_ELEMENTS_PER_CELL];
+    for (field_element, bytes) in field_elements.iter_mut().zip(cell.chunks_exact(32)) {
+        *field_element =
+            FsFr::from_bytes(bytes).map_err(|err| anyhow!("Invalid cell field element: {err}"))?;
+    }
+    Ok(field_elements)
+}
+
+#[cfg(feature = "kzg")]
+fn field_elements_to_cell(field_elements: &[FsFr; FIELD_ELEMENTS_PER_CELL]) -> Cell {
+    FixedVector::from(
+        field_elements
+            .iter()
+            .flat_map(|field_element| field_element.to_bytes())
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(feature = "kzg")]
+fn g1_to_kzg_proof(proof: &FsG1) -> KZGProof {
+    KZGProof(proof.to_bytes())
+}
+
+/// Compute all the cell proofs for an extended blob.
+#[cfg(feature = "kzg")]
+pub fn compute_cells_and_kzg_proofs<E: EthSpec>(
+    blob: &Blob<E>,
+) -> anyhow::Result<CellsAndKZGProofs> {
+    let mut cells = vec![[FsFr::default(); FIELD_ELEMENTS_PER_CELL]; CELLS_PER_EXT_BLOB as usize];
+    let mut proofs = vec![FsG1::default(); CELLS_PER_EXT_BLOB as usize];
+    kzg_settings()?
+        .compute_cells_and_kzg_proofs(
+            Some(&mut cells),
+            Some(&mut proofs),
+            &blob_to_field_elements::<E>(blob)?,
+        )
+        .map_err(|err| anyhow!("Failed to compute cells and KZG proofs: {err}"))?;
+    Ok((
+        cells.iter().map(field_elements_to_cell).collect(),
+        proofs.iter().map(g1_to_kzg_proof).collect(),
+    ))
+}
+
+/// Verify that a set of cells belong to their corresponding commitments.
+///
+/// Given four lists representing tuples of (``commitment``, ``cell_index``, ``cell``,
+/// ``proof``), the function verifies ``proof`` which shows that ``cell`` are the evaluations of
+/// the polynomial associated with ``commitment``, evaluated over the domain specified by
+/// ``cell_index``.
+#[cfg(feature = "kzg")]
+pub fn verify_cell_kzg_proof_batch(
+    commitments: &[KZGCommitment],
+    cell_indices: &[u64],
+    cells: &[Cell],
+    proofs: &[KZGProof],
+) -> anyhow::Result<bool> {
+    ensure!(
+        commitments.len() == cells.len()
+            && cell_indices.len() == cells.len()
+            && proofs.len() == cells.len(),
+        "Commitments, cell indices, cells and proofs must have the same length"
+    );
+    ensure!(
+        cell_indices
+            .iter()
+            .all(|cell_index| *cell_index < CELLS_PER_EXT_BLOB),
+        "Cell index out of range"
+    );
+    kzg_settings()?
+        .verify_cell_kzg_proof_batch(
+            &commitments
+                .iter()
+                .map(|commitment| bytes_to_g1(&commitment.0))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            &cell_indices
+                .iter()
+                .map(|cell_index| *cell_index as usize)
+                .collect::<Vec<_>>(),
+            &cells
+                .iter()
+                .map(cell_to_field_elements)
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            &proofs
+                .iter()
+                .map(|proof| bytes_to_g1(&proof.0))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        )
+        .map_err(|err| anyhow!("Failed to verify cell KZG proofs: {err}"))
+}
+
+/// Given at least 50% of cells for a blob, recover all the cells and proofs.
+#[cfg(feature = "kzg")]
+pub fn recover_cells_and_kzg_proofs(
+    cell_indices: &[u64],
+    cells: &[Cell],
+) -> anyhow::Result<CellsAndKZGProofs> {
+    ensure!(
+        cell_indices.len() == cells.len(),
+        "Cell indices and cells must have the same length"
+    );
+    ensure!(
+        cell_indices.len() as u64 >= CELLS_PER_EXT_BLOB / 2,
+        "Not enough cells to recover the blob"
+    );
+    ensure!(
+        cell_indices.len() as u64 <= CELLS_PER_EXT_BLOB,
+        "Too many cells"
+    );
+    ensure!(
+        cell_indices.windows(2).all(|pair| pair[0] < pair[1]),
+        "Cell indices must be sorted and unique"
+    );
+    ensure!(
+        cell_indices
+            .iter()
+            .all(|cell_index| *cell_index < CELLS_PER_EXT_BLOB),
+        "Cell index out of range"
+    );
+
+    let mut recovered_cells =
+        vec![[FsFr::default(); FIELD_ELEMENTS_PER_CELL]; CELLS_PER_EXT_BLOB as usize];
+    let mut recovered_proofs = vec![FsG1::default(); CELLS_PER_EXT_BLOB as usize];
+    kzg_settings()?
+        .recover_cells_and_kzg_proofs(
+            &mut recovered_cells,
+            Some(&mut recovered_proofs),
+            &cell_indices
+                .iter()
+                .map(|cell_index| *cell_index as usize)
+                .collect::<Vec<_>>(),
+            &cells
+                .iter()
+                .map(cell_to_field_elements)
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        )
+        .map_err(|err| anyhow!("Failed to recover cells and KZG proofs: {err}"))?;
+    Ok((
+        recovered_cells.iter().map(field_elements_to_cell).collect(),
+        recovered_proofs.iter().map(g1_to_kzg_proof).collect(),
+    ))
+}
+
+/// Recover the blob from the first half of the extended blob's cells, which hold the original
+/// evaluations.
+pub fn cells_to_blob<E: EthSpec>(cells: &[Cell]) -> anyhow::Result<Blob<E>> {
+    let blob_cells = CELLS_PER_EXT_BLOB as usize / 2;
+    ensure!(
+        cells.len() >= blob_cells,
+        "Not enough cells to rebuild the blob"
+    );
+    Blob::<E>::new(
+        cells[..blob_cells]
+            .iter()
+            .flat_map(|cell| cell.iter().copied())
+            .collect(),
+    )
+    .map_err(|err| anyhow!("Invalid blob length: {err:?}"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::eth_spec::MinimalEthSpec;
+
+    #[test]
+    fn test_cells_to_blob() {
+        let cells = (0..CELLS_PER_EXT_BLOB)
+            .map(|index| FixedVector::from(vec![index as u8; 2048]))
+            .collect::<Vec<Cell>>();
+
+        // Only the first half of the extended blob holds the blob
+        let blob = cells_to_blob::<MinimalEthSpec>(&cells).unwrap();
+        assert_eq!(
+            blob.to_vec(),
+            (0..CELLS_PER_EXT_BLOB / 2)
+                .flat_map(|index| vec![index as u8; 2048])
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            cells_to_blob::<MinimalEthSpec>(&cells[..63])
+                .unwrap_err()
+                .to_string(),
+            "Not enough cells to rebuild the blob"
+        );
+    }
+}
@@ -3,16 +3,11 @@ use std::cmp;
 use alloy_primitives::B256;
 
 use crate::{
-    checkpoint::Checkpoint,
-    deneb::beacon_state::BeaconState,
-    fork_choice::{
-        helpers::constants::{EFFECTIVE_BALANCE_INCREMENT, PROPOSER_SCORE_BOOST, SLOTS_PER_EPOCH},
-        store::Store,
-    },
-    misc::compute_epoch_at_slot,
+    chain_spec::ChainSpec, checkpoint::Checkpoint, deneb::beacon_state::BeaconState,
+    eth_spec::EthSpec, fork_choice::store::Store, misc::compute_epoch_at_slot,
 };
 
-pub fn get_total_balance(state: &BeaconState, indices: Vec<u64>) -> u64 {
+pub fn get_total_balance<E: EthSpec>(state: &BeaconState<E>, indices: Vec<u64>) -> u64 {
     let sum = indices
         .iter()
         .map(|&index| {
@@ -23,32 +18,35 @@ pub fn get_total_balance(state: &BeaconState, indices: Vec<u64>) -> u64 {
                 .effective_balance
         })
         .sum();
-    cmp::max(EFFECTIVE_BALANCE_INCREMENT, sum)
+    cmp::max(E::EFFECTIVE_BALANCE_INCREMENT, sum)
 }
 
-pub fn get_total_active_balance(state: BeaconState) -> u64 {
+pub fn get_total_active_balance<E: EthSpec>(state: BeaconState<E>) -> u64 {
     get_total_balance(
         &state,
         state.get_active_validator_indices(state.get_current_epoch()),
     )
 }
 
-pub fn calculate_committee_fraction(state: BeaconState, committee_percent: u64) -> u64 {
-    let committee_weight = get_total_active_balance(state) / SLOTS_PER_EPOCH;
+pub fn calculate_committee_fraction<E: EthSpec>(
+    state: BeaconState<E>,
+    committee_percent: u64,
+) -> u64 {
+    let committee_weight = get_total_active_balance(state) / E::SLOTS_PER_EPOCH;
     (committee_weight * committee_percent) / 100
 }
 
-pub fn get_proposer_score(store: Store) -> u64 {
+pub fn get_proposer_score<E: EthSpec>(store: Store<E>, spec: &ChainSpec) -> u64 {
     let justified_checkpoint_state = store
         .checkpoint_states
         .get(&store.justified_checkpoint)
         .expect("Failed to find checkpoint in checkpoint states");
     let committee_weight =
-        get_total_active_balance(justified_checkpoint_state.clone()) / SLOTS_PER_EPOCH;
-    (committee_weight * PROPOSER_SCORE_BOOST) / 100
+        get_total_active_balance(justified_checkpoint_state.clone()) / E::SLOTS_PER_EPOCH;
+    (committee_weight * spec.proposer_score_boost) / 100
 }
 
-pub fn get_weight(store: Store, root: B256) -> u64 {
+pub fn get_weight<E: EthSpec>(store: Store<E>, root: B256, spec: &ChainSpec) -> u64 {
     let state = &store.checkpoint_states[&store.justified_checkpoint];
 
     let unslashed_and_active_indices: Vec<u64> = state
@@ -74,17 +72,17 @@ pub fn get_weight(store: Store, root: B256) -> u64 {
 
     let mut proposer_score: u64 = 0;
     if store.get_ancestor(store.proposer_boost_root, store.blocks[&root].slot) == root {
-        proposer_score = get_proposer_score(store);
+        proposer_score = get_proposer_score(store, spec);
     }
 
     attestation_score + proposer_score
 }
 
-pub fn get_voting_source(store: &Store, block_root: B256) -> Checkpoint {
+pub fn get_voting_source<E: EthSpec>(store: &Store<E>, block_root: B256) -> Checkpoint {
     let block = &store.blocks[&block_root];
 
     let current_epoch = store.get_current_slot();
-    let block_epoch = compute_epoch_at_slot(block.slot);
+    let block_epoch = compute_epoch_at_slot::<E>(block.slot);
 
     if current_epoch > block_epoch {
         store.unrealized_justifications[&block_root]
@@ -1,12 +1,15 @@
 use alloy_primitives::B256;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{typenum::U8192, FixedVector};
+use ssz_types::FixedVector;
 use tree_hash_derive::TreeHash;
 
+use crate::eth_spec::EthSpec;
+
 // todo: add tests
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
-pub struct HistoricalBatch {
-    pub block_roots: FixedVector<B256, U8192>,
-    pub state_roots: FixedVector<B256, U8192>,
+#[serde(bound = "E: EthSpec")]
+pub struct HistoricalBatch<E: EthSpec> {
+    pub block_roots: FixedVector<B256, E::SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, E::SlotsPerHistoricalRoot>,
 }
@@ -0,0 +1,293 @@
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use serde::{Deserialize, Serialize};
+use ssz_types::typenum::Unsigned;
+use tree_hash::TreeHash;
+
+use crate::{
+    chain_spec::ChainSpec,
+    deneb::beacon_state::{is_valid_merkle_branch, BeaconState},
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::{BLOCK_ROOTS_GINDEX, HISTORICAL_SUMMARIES_GINDEX},
+    historical_batch::HistoricalBatch,
+    merkle_proof::{
+        compute_merkle_proof, concat_generalized_indices, floorlog2, get_subtree_index, PathElement,
+    },
+};
+
+/// Proof that ``block_root`` is the canonical block root at ``slot`` in the history of a
+/// ``BeaconState``.
+///
+/// Recent blocks are proven through ``state.block_roots``. Older blocks are proven through
+/// ``state.historical_summaries[i].block_summary_root`` into the ``block_roots`` vector of the
+/// period that the summary commits to.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct HistoricalBlockRootProof {
+    pub slot: u64,
+    pub block_root: B256,
+    pub branch: Vec<B256>,
+}
+
+impl HistoricalBlockRootProof {
+    /// Verify the proof against the root of a trusted state at ``state_slot``.
+    pub fn verify<E: EthSpec>(&self, state_slot: u64, state_root: B256, spec: &ChainSpec) -> bool {
+        let Ok(generalized_index) =
+            get_historical_block_root_generalized_index::<E>(self.slot, state_slot, spec)
+        else {
+            return false;
+        };
+        self.branch.len() as u64 == floorlog2(generalized_index)
+            && is_valid_merkle_branch(
+                self.block_root,
+                &self.branch,
+                floorlog2(generalized_index),
+                get_subtree_index(generalized_index),
+                state_root,
+            )
+    }
+}
+
+/// Return the index of the historical summary covering ``slot``.
+///
+/// Summaries are appended at the end of every ``SLOTS_PER_HISTORICAL_ROOT`` period starting with
+/// the period that contains the Capella fork.
+pub fn get_historical_summary_index<E: EthSpec>(slot: u64, spec: &ChainSpec) -> Option<u64> {
+    let epochs_per_period = E::SLOTS_PER_HISTORICAL_ROOT / E::SLOTS_PER_EPOCH;
+    let capella_period = spec.capella_fork_epoch / epochs_per_period;
+    (slot / E::SLOTS_PER_HISTORICAL_ROOT).checked_sub(capella_period)
+}
+
+/// Return the generalized index of the block root at ``slot`` in a ``BeaconState`` at
+/// ``state_slot``.
+pub fn get_historical_block_root_generalized_index<E: EthSpec>(
+    slot: u64,
+    state_slot: u64,
+    spec: &ChainSpec,
+) -> anyhow::Result<u64> {
+    ensure!(
+        slot < state_slot,
+        "Block slot must be before the state slot"
+    );
+    let block_roots_index = E::SLOTS_PER_HISTORICAL_ROOT + slot % E::SLOTS_PER_HISTORICAL_ROOT;
+
+    if state_slot <= slot + E::SLOTS_PER_HISTORICAL_ROOT {
+        return Ok(concat_generalized_indices(&[
+            BLOCK_ROOTS_GINDEX,
+            block_roots_index,
+        ]));
+    }
+
+    let summary_index = get_historical_summary_index::<E>(slot, spec)
+        .ok_or_else(|| anyhow!("Slot {slot} is before the first historical summary"))?;
+    Ok(concat_generalized_indices(&[
+        HISTORICAL_SUMMARIES_GINDEX,
+        // ``historical_summaries`` list data
+        2,
+        <E::HistoricalRootsLimit as Unsigned>::U64 + summary_index,
+        // ``block_summary_root`` is the first of the two ``HistoricalSummary`` fields
+        2,
+        block_roots_index,
+    ]))
+}
+
+/// Build a proof that the block root at ``slot`` is canonical in the history of ``state``.
+///
+/// Blocks older than ``SLOTS_PER_HISTORICAL_ROOT`` slots need the ``HistoricalBatch`` of their
+/// period, e.g. from an archive, as ``state`` only keeps its summary.
+pub fn compute_historical_block_root_proof<E: EthSpec>(
+    state: &BeaconState<E>,
+    slot: u64,
+    historical_batch: Option<&HistoricalBatch<E>>,
+    spec: &ChainSpec,
+) -> anyhow::Result<HistoricalBlockRootProof> {
+    let block_roots_index = (slot % E::SLOTS_PER_HISTORICAL_ROOT) as usize;
+
+    let (block_root, branch) = if state.slot <= slot + E::SLOTS_PER_HISTORICAL_ROOT {
+        ensure!(
+            slot < state.slot,
+            "Block slot must be before the state slot"
+        );
+        let proof = compute_merkle_proof(
+            state,
+            &[
+                PathElement::Field("block_roots".to_string()),
+                PathElement::Index(block_roots_index),
+            ],
+        )?;
+        (proof.leaf, proof.branch)
+    } else {
+        let summary_index = get_historical_summary_index::<E>(slot, spec)
+            .ok_or_else(|| anyhow!("Slot {slot} is before the first historical summary"))?
+            as usize;
+        let historical_summary = state
+            .historical_summaries
+            .get(summary_index)
+            .ok_or_else(|| anyhow!("Historical summary {summary_index} not found"))?;
+        let historical_batch = historical_batch
+            .ok_or_else(|| anyhow!("Historical batch is required for slot {slot}"))?;
+        ensure!(
+            historical_batch.block_roots.tree_hash_root() == historical_summary.block_summary_root,
+            "Historical batch does not match historical summary {summary_index}"
+        );
+
+        let block_roots_proof = compute_merkle_proof(
+            &historical_batch.block_roots,
+            &[PathElement::Index(block_roots_index)],
+        )?;
+        let summary_proof = compute_merkle_proof(
+            state,
+            &[
+                PathElement::Field("historical_summaries".to_string()),
+                PathElement::Index(summary_index),
+                PathElement::Field("block_summary_root".to_string()),
+            ],
+        )?;
+        (
+            block_roots_proof.leaf,
+            [block_roots_proof.branch, summary_proof.branch].concat(),
+        )
+    };
+
+    Ok(HistoricalBlockRootProof {
+        slot,
+        block_root,
+        branch,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ssz_types::FixedVector;
+
+    use super::*;
+    use crate::{
+        eth_spec::MinimalEthSpec, historical_summary::HistoricalSummary, test_utils::beacon_state,
+    };
+
+    /// The minimal preset has 64 slots per historical root, so the first summary covers slots
+    /// 0..64 once Capella is active from genesis.
+    fn spec() -> ChainSpec {
+        ChainSpec {
+            capella_fork_epoch: 0,
+            ..ChainSpec::minimal()
+        }
+    }
+
+    fn block_roots(
+        seed: u8,
+    ) -> FixedVector<B256, <MinimalEthSpec as EthSpec>::SlotsPerHistoricalRoot> {
+        FixedVector::from(
+            (0..MinimalEthSpec::SLOTS_PER_HISTORICAL_ROOT)
+                .map(|slot| {
+                    let mut root = B256::repeat_byte(seed);
+                    root.0[31] = slot as u8;
+                    root
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_block_roots_proof() {
+        let spec = spec();
+        let mut state = beacon_state::<MinimalEthSpec>(100, vec![]);
+        state.block_roots = block_roots(1);
+        let state_root = state.tree_hash_root();
+
+        let proof = compute_historical_block_root_proof(&state, 50, None, &spec).unwrap();
+        assert_eq!(proof.block_root, state.block_roots[50]);
+        assert!(proof.verify::<MinimalEthSpec>(100, state_root, &spec));
+
+        // The proof is only valid for its own root, slot and state
+        let wrong_root = HistoricalBlockRootProof {
+            block_root: B256::repeat_byte(0xff),
+            ..proof.clone()
+        };
+        assert!(!wrong_root.verify::<MinimalEthSpec>(100, state_root, &spec));
+        let wrong_slot = HistoricalBlockRootProof {
+            slot: 51,
+            ..proof.clone()
+        };
+        assert!(!wrong_slot.verify::<MinimalEthSpec>(100, state_root, &spec));
+        assert!(!proof.verify::<MinimalEthSpec>(100, B256::ZERO, &spec));
+
+        // A state cannot prove its own slot or later ones
+        assert!(compute_historical_block_root_proof(&state, 100, None, &spec).is_err());
+    }
+
+    #[test]
+    fn test_historical_summaries_proof() {
+        let spec = spec();
+        let historical_batch = HistoricalBatch::<MinimalEthSpec> {
+            block_roots: block_roots(2),
+            state_roots: block_roots(3),
+        };
+        let mut state = beacon_state::<MinimalEthSpec>(200, vec![]);
+        state.block_roots = block_roots(1);
+        state
+            .historical_summaries
+            .push(HistoricalSummary {
+                block_summary_root: historical_batch.block_roots.tree_hash_root(),
+                state_summary_root: historical_batch.state_roots.tree_hash_root(),
+            })
+            .unwrap();
+        let state_root = state.tree_hash_root();
+
+        let proof = compute_historical_block_root_proof(&state, 10, Some(&historical_batch), &spec)
+            .unwrap();
+        assert_eq!(proof.block_root, historical_batch.block_roots[10]);
+        assert!(proof.verify::<MinimalEthSpec>(200, state_root, &spec));
+
+        let wrong_root = HistoricalBlockRootProof {
+            block_root: state.block_roots[10],
+            ..proof.clone()
+        };
+        assert!(!wrong_root.verify::<MinimalEthSpec>(200, state_root, &spec));
+        let wrong_slot = HistoricalBlockRootProof {
+            slot: 11,
+            ..proof.clone()
+        };
+        assert!(!wrong_slot.verify::<MinimalEthSpec>(200, state_root, &spec));
+
+        // The historical batch must be given and match the summary
+        assert!(compute_historical_block_root_proof(&state, 10, None, &spec).is_err());
+        let other_batch = HistoricalBatch::<MinimalEthSpec> {
+            block_roots: block_roots(4),
+            state_roots: block_roots(3),
+        };
+        assert!(
+            compute_historical_block_root_proof(&state, 10, Some(&other_batch), &spec).is_err()
+        );
+    }
+
+    #[test]
+    fn test_pre_capella_slot_rejected() {
+        let spec = ChainSpec::minimal();
+        let historical_batch = HistoricalBatch::<MinimalEthSpec> {
+            block_roots: block_roots(2),
+            state_roots: block_roots(3),
+        };
+        let mut state = beacon_state::<MinimalEthSpec>(200, vec![]);
+        state
+            .historical_summaries
+            .push(HistoricalSummary {
+                block_summary_root: historical_batch.block_roots.tree_hash_root(),
+                state_summary_root: historical_batch.state_roots.tree_hash_root(),
+            })
+            .unwrap();
+
+        assert!(get_historical_summary_index::<MinimalEthSpec>(10, &spec).is_none());
+        assert!(
+            compute_historical_block_root_proof(&state, 10, Some(&historical_batch), &spec)
+                .is_err()
+        );
+
+        // A proof built while Capella was active does not verify against a pre-Capella slot
+        let proof =
+            compute_historical_block_root_proof(&state, 10, Some(&historical_batch), &spec())
+                .unwrap();
+        assert!(proof.verify::<MinimalEthSpec>(200, state.tree_hash_root(), &spec()));
+        assert!(!proof.verify::<MinimalEthSpec>(200, state.tree_hash_root(), &spec));
+    }
+}
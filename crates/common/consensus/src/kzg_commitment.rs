@@ -6,7 +6,6 @@ use std::{
 
 use alloy_primitives::hex;
 use ethereum_hashing::hash_fixed;
-use kzg::eip_4844::BYTES_PER_COMMITMENT;
 use serde::{
     de::{Deserialize, Deserializer},
     ser::{Serialize, Serializer},
@@ -14,6 +13,7 @@ use serde::{
 use ssz_derive::{Decode, Encode};
 use tree_hash::{Hash256, PackedEncoding, TreeHash};
 
+pub const BYTES_PER_COMMITMENT: usize = 48;
 pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
 
 #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Hash)]
@@ -32,6 +32,7 @@ impl KZGCommitment {
     }
 }
 
+#[cfg(feature = "kzg")]
 impl From<KZGCommitment> for kzg::eth::c_bindings::Bytes48 {
     fn from(value: KZGCommitment) -> Self {
         kzg::eth::c_bindings::Bytes48 { bytes: value.0 }
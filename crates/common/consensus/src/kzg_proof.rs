@@ -0,0 +1,94 @@
+use std::{
+    fmt,
+    fmt::{Debug, Formatter},
+    str::FromStr,
+};
+
+use alloy_primitives::hex;
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+use ssz_derive::{Decode, Encode};
+use tree_hash::{PackedEncoding, TreeHash};
+
+pub const BYTES_PER_PROOF: usize = 48;
+
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Hash)]
+#[ssz(struct_behaviour = "transparent")]
+pub struct KZGProof(pub [u8; BYTES_PER_PROOF]);
+
+impl KZGProof {
+    pub fn empty_for_testing() -> Self {
+        KZGProof([0; BYTES_PER_PROOF])
+    }
+}
+
+#[cfg(feature = "kzg")]
+impl From<KZGProof> for kzg::eth::c_bindings::Bytes48 {
+    fn from(value: KZGProof) -> Self {
+        kzg::eth::c_bindings::Bytes48 { bytes: value.0 }
+    }
+}
+
+impl TreeHash for KZGProof {
+    fn tree_hash_type() -> tree_hash::TreeHashType {
+        <[u8; BYTES_PER_PROOF] as TreeHash>::tree_hash_type()
+    }
+
+    fn tree_hash_packed_encoding(&self) -> PackedEncoding {
+        self.0.tree_hash_packed_encoding()
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        <[u8; BYTES_PER_PROOF] as TreeHash>::tree_hash_packing_factor()
+    }
+
+    fn tree_hash_root(&self) -> tree_hash::Hash256 {
+        self.0.tree_hash_root()
+    }
+}
+
+impl Serialize for KZGProof {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("{self:?}"))
+    }
+}
+
+impl<'de> Deserialize<'de> for KZGProof {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        Self::from_str(&string).map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for KZGProof {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|e| e.to_string())?;
+        if bytes.len() == BYTES_PER_PROOF {
+            let mut kzg_proof_bytes = [0; BYTES_PER_PROOF];
+            kzg_proof_bytes[..].copy_from_slice(&bytes);
+            Ok(Self(kzg_proof_bytes))
+        } else {
+            Err(format!(
+                "InvalidByteLength: got {}, expected {}",
+                bytes.len(),
+                BYTES_PER_PROOF
+            ))
+        }
+    }
+}
+
+impl Debug for KZGProof {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
@@ -1,32 +1,51 @@
 #![warn(clippy::unwrap_used)]
 
+pub mod aggregate_and_proof;
 pub mod attestation;
 pub mod attestation_data;
 pub mod attester_slashing;
 pub mod beacon_block_header;
 pub mod bls_to_execution_change;
+pub mod chain_spec;
 pub mod checkpoint;
 pub mod deneb;
 pub mod deposit;
 pub mod deposit_data;
 pub mod deposit_message;
+pub mod deposit_tree;
+pub mod deposit_tree_snapshot;
+pub mod eth_1_block;
 pub mod eth_1_data;
+pub mod eth_spec;
 pub mod execution_engine;
 pub mod fork;
 pub mod fork_choice;
 pub mod fork_data;
+pub mod fulu;
 pub mod helpers;
 pub mod historical_batch;
+pub mod historical_block_proof;
 pub mod historical_summary;
 pub mod indexed_attestation;
 pub mod kzg_commitment;
+pub mod kzg_proof;
+pub mod light_client;
+pub mod merkle_proof;
 pub mod misc;
+pub mod operation_pool;
 pub mod pending_attestation;
+#[cfg(feature = "kzg")]
+pub mod polynomial_commitments;
 pub mod predicates;
 pub mod proposer_slashing;
+pub mod pubkey_cache;
 pub mod signing_data;
 pub mod sync_aggregate;
 pub mod sync_committee;
+pub mod sync_committee_contribution;
+pub mod sync_committee_pool;
+#[cfg(test)]
+pub mod test_utils;
 pub mod validator;
 pub mod voluntary_exit;
 pub mod withdrawal;
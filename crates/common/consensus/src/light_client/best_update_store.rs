@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+
+use super::{
+    light_client_finality_update::LightClientFinalityUpdate,
+    light_client_optimistic_update::LightClientOptimisticUpdate,
+    light_client_update::LightClientUpdate,
+};
+use crate::{
+    eth_spec::EthSpec, misc::compute_sync_committee_period_at_slot, sync_aggregate::SyncAggregate,
+};
+
+/// Light client data served by a full node: the best ``LightClientUpdate`` of each sync committee
+/// period, plus the latest finality and optimistic updates.
+#[derive(Debug, Default, Clone)]
+pub struct BestUpdateStore<E: EthSpec> {
+    best_updates: BTreeMap<u64, LightClientUpdate<E>>,
+    latest_finality_update: Option<LightClientFinalityUpdate<E>>,
+    latest_optimistic_update: Option<LightClientOptimisticUpdate<E>>,
+}
+
+impl<E: EthSpec> BestUpdateStore<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an update derived from a newly imported block.
+    ///
+    /// As with the light client gossip rules, the latest finality update is the one with the
+    /// highest finalized slot, or with a supermajority of the sync committee for the same
+    /// finalized slot, and the latest optimistic update the one with the highest attested slot.
+    ///
+    /// Returns whether ``update`` became the best update of its sync committee period.
+    pub fn insert(&mut self, update: LightClientUpdate<E>) -> bool {
+        let attested_slot = update.attested_header.beacon.slot;
+        let finalized_slot = update.finalized_header.beacon.slot;
+
+        if update.is_finality_update()
+            && self.latest_finality_update.as_ref().map_or(true, |latest| {
+                let latest_finalized_slot = latest.finalized_header.beacon.slot;
+                finalized_slot > latest_finalized_slot
+                    || (finalized_slot == latest_finalized_slot
+                        && has_supermajority(&update.sync_aggregate)
+                        && !has_supermajority(&latest.sync_aggregate))
+            })
+        {
+            self.latest_finality_update = Some(update.clone().into());
+        }
+
+        if self
+            .latest_optimistic_update
+            .as_ref()
+            .map_or(true, |latest| {
+                (attested_slot, update.signature_slot)
+                    > (latest.attested_header.beacon.slot, latest.signature_slot)
+            })
+        {
+            self.latest_optimistic_update = Some(update.clone().into());
+        }
+
+        let period = compute_sync_committee_period_at_slot::<E>(attested_slot);
+        match self.best_updates.get(&period) {
+            Some(best_update) if !update.is_better_update(best_update) => false,
+            _ => {
+                self.best_updates.insert(period, update);
+                true
+            }
+        }
+    }
+
+    /// Return the best update of the sync committee ``period``.
+    pub fn best_update(&self, period: u64) -> Option<&LightClientUpdate<E>> {
+        self.best_updates.get(&period)
+    }
+
+    /// Return the best updates of up to ``count`` consecutive periods from ``start_period``,
+    /// stopping at the first period without an update.
+    pub fn best_updates_by_range(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Vec<&LightClientUpdate<E>> {
+        (start_period..start_period.saturating_add(count))
+            .map_while(|period| self.best_updates.get(&period))
+            .collect()
+    }
+
+    pub fn latest_finality_update(&self) -> Option<&LightClientFinalityUpdate<E>> {
+        self.latest_finality_update.as_ref()
+    }
+
+    pub fn latest_optimistic_update(&self) -> Option<&LightClientOptimisticUpdate<E>> {
+        self.latest_optimistic_update.as_ref()
+    }
+
+    /// Drop the best updates of all periods before ``period``.
+    pub fn prune(&mut self, period: u64) {
+        self.best_updates = self.best_updates.split_off(&period);
+    }
+}
+
+/// Return whether more than two thirds of the sync committee took part in ``sync_aggregate``.
+fn has_supermajority<E: EthSpec>(sync_aggregate: &SyncAggregate<E>) -> bool {
+    sync_aggregate.num_active_participants() * 3 >= E::SYNC_COMMITTEE_SIZE * 2
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::BLSSignature;
+    use ssz_types::{BitVector, FixedVector};
+
+    use super::*;
+    use crate::{
+        beacon_block_header::BeaconBlockHeader, eth_spec::MinimalEthSpec,
+        light_client::light_client_header::LightClientHeader, sync_committee::SyncCommittee,
+    };
+
+    fn update(
+        num_participants: usize,
+        attested_slot: u64,
+        finalized_slot: Option<u64>,
+    ) -> LightClientUpdate<MinimalEthSpec> {
+        let mut sync_committee_bits = BitVector::new();
+        for index in 0..num_participants {
+            sync_committee_bits.set(index, true).unwrap();
+        }
+        LightClientUpdate {
+            attested_header: LightClientHeader {
+                beacon: BeaconBlockHeader {
+                    slot: attested_slot,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            next_sync_committee: SyncCommittee::default(),
+            next_sync_committee_branch: FixedVector::default(),
+            finalized_header: LightClientHeader {
+                beacon: BeaconBlockHeader {
+                    slot: finalized_slot.unwrap_or_default(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            finality_branch: if finalized_slot.is_some() {
+                FixedVector::from(vec![B256::repeat_byte(1); 6])
+            } else {
+                FixedVector::default()
+            },
+            sync_aggregate: SyncAggregate {
+                sync_committee_bits,
+                sync_committee_signature: BLSSignature::infinity(),
+            },
+            signature_slot: attested_slot + 1,
+        }
+    }
+
+    #[test]
+    fn test_insert_replaces_best_update() {
+        let mut store = BestUpdateStore::<MinimalEthSpec>::new();
+        assert!(store.insert(update(24, 10, None)));
+
+        // A worse update of the same period is not kept, but is still the latest update
+        assert!(!store.insert(update(20, 12, Some(0))));
+        assert_eq!(
+            store.best_update(0).unwrap().attested_header.beacon.slot,
+            10
+        );
+        assert_eq!(
+            store
+                .latest_optimistic_update()
+                .unwrap()
+                .attested_header
+                .beacon
+                .slot,
+            12
+        );
+        assert_eq!(
+            store
+                .latest_finality_update()
+                .unwrap()
+                .attested_header
+                .beacon
+                .slot,
+            12
+        );
+
+        // A better update of the same period replaces the best update
+        assert!(store.insert(update(24, 14, Some(0))));
+        assert_eq!(
+            store.best_update(0).unwrap().attested_header.beacon.slot,
+            14
+        );
+
+        // An older update is not the latest one, even when it is the best of its period
+        assert!(store.insert(update(32, 8, Some(0))));
+        assert_eq!(store.best_update(0).unwrap().attested_header.beacon.slot, 8);
+        assert_eq!(
+            store
+                .latest_optimistic_update()
+                .unwrap()
+                .attested_header
+                .beacon
+                .slot,
+            14
+        );
+
+        assert!(store.insert(update(24, 70, None)));
+        assert_eq!(store.best_updates_by_range(0, 3).len(), 2);
+        store.prune(1);
+        assert!(store.best_update(0).is_none());
+        assert_eq!(store.best_updates_by_range(0, 3).len(), 0);
+        assert_eq!(store.best_updates_by_range(1, 3).len(), 1);
+    }
+
+    #[test]
+    fn test_insert_latest_finality_update() {
+        let mut store = BestUpdateStore::<MinimalEthSpec>::new();
+        let latest_finality_update = |store: &BestUpdateStore<MinimalEthSpec>| {
+            let latest = store.latest_finality_update().unwrap();
+            (
+                latest.attested_header.beacon.slot,
+                latest.finalized_header.beacon.slot,
+            )
+        };
+
+        store.insert(update(20, 20, Some(8)));
+        assert_eq!(latest_finality_update(&store), (20, 8));
+
+        // A newer attested header does not replace a later finalized header
+        store.insert(update(20, 22, Some(0)));
+        assert_eq!(latest_finality_update(&store), (20, 8));
+
+        // An older attested header replaces an earlier finalized header
+        store.insert(update(24, 18, Some(16)));
+        assert_eq!(latest_finality_update(&store), (18, 16));
+
+        // The same finalized header only replaces one without a supermajority
+        store.insert(update(30, 24, Some(16)));
+        assert_eq!(latest_finality_update(&store), (18, 16));
+        store.insert(update(20, 26, Some(24)));
+        assert_eq!(latest_finality_update(&store), (26, 24));
+        store.insert(update(24, 25, Some(24)));
+        assert_eq!(latest_finality_update(&store), (25, 24));
+
+        // Updates without finality are never the latest finality update
+        store.insert(update(32, 30, None));
+        assert_eq!(latest_finality_update(&store), (25, 24));
+    }
+}
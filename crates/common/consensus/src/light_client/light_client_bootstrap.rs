@@ -0,0 +1,146 @@
+use alloy_primitives::B256;
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{typenum::U5, FixedVector};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::light_client_header::LightClientHeader;
+use crate::{
+    deneb::{beacon_block::SignedBeaconBlock, beacon_state::BeaconState},
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::CURRENT_SYNC_COMMITTEE_GINDEX,
+    merkle_proof::compute_merkle_proof_branch,
+    sync_committee::SyncCommittee,
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct LightClientBootstrap<E: EthSpec> {
+    /// Header matching the requested beacon block root
+    pub header: LightClientHeader,
+
+    /// Current sync committee corresponding to ``header.beacon.state_root``
+    pub current_sync_committee: SyncCommittee<E>,
+    pub current_sync_committee_branch: FixedVector<B256, U5>,
+}
+
+impl<E: EthSpec> LightClientBootstrap<E> {
+    /// Create the bootstrap for ``block``, whose post state is ``state``.
+    pub fn new(state: &BeaconState<E>, block: &SignedBeaconBlock<E>) -> anyhow::Result<Self> {
+        ensure!(
+            state.slot == state.latest_block_header.slot,
+            "State must be at the slot of its latest block header"
+        );
+
+        let mut header = state.latest_block_header.clone();
+        header.state_root = state.tree_hash_root();
+        ensure!(
+            header.tree_hash_root() == block.message.tree_hash_root(),
+            "Block does not match the state"
+        );
+
+        Ok(Self {
+            header: LightClientHeader::from_block(block)?,
+            current_sync_committee: (*state.current_sync_committee).clone(),
+            current_sync_committee_branch: compute_merkle_proof_branch(
+                state,
+                CURRENT_SYNC_COMMITTEE_GINDEX,
+            )?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::sync::Arc;
+
+    use ream_bls::PubKey;
+
+    use super::*;
+    use crate::{
+        deneb::beacon_state::is_valid_merkle_branch,
+        eth_spec::MinimalEthSpec,
+        fork_choice::helpers::constants::EXECUTION_PAYLOAD_GINDEX,
+        merkle_proof::{floorlog2, get_subtree_index},
+        test_utils::{beacon_state, set_post_state, signed_beacon_block},
+    };
+
+    fn is_valid_branch(leaf: B256, branch: &[B256], generalized_index: u64, root: B256) -> bool {
+        is_valid_merkle_branch(
+            leaf,
+            branch,
+            floorlog2(generalized_index),
+            get_subtree_index(generalized_index),
+            root,
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let mut block = signed_beacon_block::<MinimalEthSpec>(10, vec![]);
+        let mut state = beacon_state::<MinimalEthSpec>(10, vec![]);
+        state.current_sync_committee = Arc::new(SyncCommittee {
+            pubkeys: FixedVector::default(),
+            aggregate_pubkey: PubKey {
+                inner: FixedVector::from(vec![1; 48]),
+            },
+        });
+        set_post_state(&mut state, &mut block);
+
+        let bootstrap = LightClientBootstrap::new(&state, &block).unwrap();
+        assert_eq!(
+            bootstrap.header.beacon.tree_hash_root(),
+            block.message.tree_hash_root()
+        );
+        assert_eq!(
+            bootstrap.current_sync_committee,
+            *state.current_sync_committee
+        );
+        assert!(is_valid_branch(
+            bootstrap.current_sync_committee.tree_hash_root(),
+            &bootstrap.current_sync_committee_branch,
+            CURRENT_SYNC_COMMITTEE_GINDEX,
+            bootstrap.header.beacon.state_root
+        ));
+        assert!(!is_valid_branch(
+            state.next_sync_committee.tree_hash_root(),
+            &bootstrap.current_sync_committee_branch,
+            CURRENT_SYNC_COMMITTEE_GINDEX,
+            bootstrap.header.beacon.state_root
+        ));
+        assert!(is_valid_branch(
+            bootstrap.header.execution.tree_hash_root(),
+            &bootstrap.header.execution_branch,
+            EXECUTION_PAYLOAD_GINDEX,
+            bootstrap.header.beacon.body_root
+        ));
+    }
+
+    #[test]
+    fn test_new_rejections() {
+        let mut block = signed_beacon_block::<MinimalEthSpec>(10, vec![]);
+        let mut state = beacon_state::<MinimalEthSpec>(10, vec![]);
+        set_post_state(&mut state, &mut block);
+
+        let mut advanced_state = state.clone();
+        advanced_state.slot = 11;
+        assert_eq!(
+            LightClientBootstrap::new(&advanced_state, &block)
+                .unwrap_err()
+                .to_string(),
+            "State must be at the slot of its latest block header"
+        );
+
+        let mut other_block = block.clone();
+        other_block.message.proposer_index = 1;
+        assert_eq!(
+            LightClientBootstrap::new(&state, &other_block)
+                .unwrap_err()
+                .to_string(),
+            "Block does not match the state"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{typenum::U6, FixedVector};
+use tree_hash_derive::TreeHash;
+
+use super::{light_client_header::LightClientHeader, light_client_update::LightClientUpdate};
+use crate::{eth_spec::EthSpec, sync_aggregate::SyncAggregate};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct LightClientFinalityUpdate<E: EthSpec> {
+    /// Header attested to by the sync committee
+    pub attested_header: LightClientHeader,
+
+    /// Finalized header corresponding to ``attested_header.beacon.state_root``
+    pub finalized_header: LightClientHeader,
+    pub finality_branch: FixedVector<B256, U6>,
+
+    /// Sync committee aggregate signature
+    pub sync_aggregate: SyncAggregate<E>,
+
+    /// Slot at which the aggregate signature was created (untrusted)
+    pub signature_slot: u64,
+}
+
+impl<E: EthSpec> From<LightClientUpdate<E>> for LightClientFinalityUpdate<E> {
+    fn from(update: LightClientUpdate<E>) -> Self {
+        Self {
+            attested_header: update.attested_header,
+            finalized_header: update.finalized_header,
+            finality_branch: update.finality_branch,
+            sync_aggregate: update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        }
+    }
+}
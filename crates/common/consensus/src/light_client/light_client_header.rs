@@ -0,0 +1,36 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{typenum::U4, FixedVector};
+use tree_hash_derive::TreeHash;
+
+use crate::{
+    beacon_block_header::BeaconBlockHeader,
+    deneb::{beacon_block::SignedBeaconBlock, execution_payload_header::ExecutionPayloadHeader},
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::EXECUTION_PAYLOAD_GINDEX,
+    merkle_proof::compute_merkle_proof_branch,
+};
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct LightClientHeader {
+    /// Beacon block header
+    pub beacon: BeaconBlockHeader,
+
+    /// Execution payload header corresponding to ``beacon.body_root``
+    pub execution: ExecutionPayloadHeader,
+    pub execution_branch: FixedVector<B256, U4>,
+}
+
+impl LightClientHeader {
+    /// Build the header of ``block`` that light clients track, as in the spec's
+    /// ``block_to_light_client_header``.
+    pub fn from_block<E: EthSpec>(block: &SignedBeaconBlock<E>) -> anyhow::Result<Self> {
+        let body = &block.message.body;
+        Ok(Self {
+            beacon: block.message.block_header(),
+            execution: body.execution_payload.to_execution_payload_header(),
+            execution_branch: compute_merkle_proof_branch(body, EXECUTION_PAYLOAD_GINDEX)?,
+        })
+    }
+}
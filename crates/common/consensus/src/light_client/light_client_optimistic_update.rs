@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+use super::{light_client_header::LightClientHeader, light_client_update::LightClientUpdate};
+use crate::{eth_spec::EthSpec, sync_aggregate::SyncAggregate};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct LightClientOptimisticUpdate<E: EthSpec> {
+    /// Header attested to by the sync committee
+    pub attested_header: LightClientHeader,
+
+    /// Sync committee aggregate signature
+    pub sync_aggregate: SyncAggregate<E>,
+
+    /// Slot at which the aggregate signature was created (untrusted)
+    pub signature_slot: u64,
+}
+
+impl<E: EthSpec> From<LightClientUpdate<E>> for LightClientOptimisticUpdate<E> {
+    fn from(update: LightClientUpdate<E>) -> Self {
+        Self {
+            attested_header: update.attested_header,
+            sync_aggregate: update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        }
+    }
+}
@@ -0,0 +1,574 @@
+use alloy_primitives::B256;
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    typenum::{U5, U6},
+    FixedVector,
+};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::light_client_header::LightClientHeader;
+use crate::{
+    deneb::{beacon_block::SignedBeaconBlock, beacon_state::BeaconState},
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::{
+        FINALIZED_ROOT_GINDEX, GENESIS_SLOT, NEXT_SYNC_COMMITTEE_GINDEX,
+    },
+    merkle_proof::compute_merkle_proof_branch,
+    misc::compute_sync_committee_period_at_slot,
+    sync_aggregate::SyncAggregate,
+    sync_committee::SyncCommittee,
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct LightClientUpdate<E: EthSpec> {
+    /// Header attested to by the sync committee
+    pub attested_header: LightClientHeader,
+
+    /// Next sync committee corresponding to ``attested_header.beacon.state_root``
+    pub next_sync_committee: SyncCommittee<E>,
+    pub next_sync_committee_branch: FixedVector<B256, U5>,
+
+    /// Finalized header corresponding to ``attested_header.beacon.state_root``
+    pub finalized_header: LightClientHeader,
+    pub finality_branch: FixedVector<B256, U6>,
+
+    /// Sync committee aggregate signature
+    pub sync_aggregate: SyncAggregate<E>,
+
+    /// Slot at which the aggregate signature was created (untrusted)
+    pub signature_slot: u64,
+}
+
+impl<E: EthSpec> LightClientUpdate<E> {
+    /// Create the update in which the sync committee of ``block`` attests to its parent,
+    /// ``attested_block``.
+    ///
+    /// ``state`` and ``attested_state`` are the post states of the two blocks, and
+    /// ``finalized_block`` is the block at ``attested_state.finalized_checkpoint``, if known.
+    pub fn new(
+        state: &BeaconState<E>,
+        block: &SignedBeaconBlock<E>,
+        attested_state: &BeaconState<E>,
+        attested_block: &SignedBeaconBlock<E>,
+        finalized_block: Option<&SignedBeaconBlock<E>>,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            block.message.body.sync_aggregate.num_active_participants()
+                >= E::MIN_SYNC_COMMITTEE_PARTICIPANTS,
+            "Not enough sync committee participants"
+        );
+
+        ensure!(
+            state.slot == state.latest_block_header.slot,
+            "State must be at the slot of its latest block header"
+        );
+        let mut header = state.latest_block_header.clone();
+        header.state_root = state.tree_hash_root();
+        ensure!(
+            header.tree_hash_root() == block.message.tree_hash_root(),
+            "Block does not match the state"
+        );
+        let update_signature_period =
+            compute_sync_committee_period_at_slot::<E>(block.message.slot);
+
+        ensure!(
+            attested_state.slot == attested_state.latest_block_header.slot,
+            "Attested state must be at the slot of its latest block header"
+        );
+        let mut attested_header = attested_state.latest_block_header.clone();
+        attested_header.state_root = attested_state.tree_hash_root();
+        ensure!(
+            attested_header.tree_hash_root() == attested_block.message.tree_hash_root()
+                && attested_header.tree_hash_root() == block.message.parent_root,
+            "Attested block does not match the attested state or the parent of the block"
+        );
+        let update_attested_period =
+            compute_sync_committee_period_at_slot::<E>(attested_block.message.slot);
+
+        // ``next_sync_committee`` is only useful if the message is signed by the current sync
+        // committee
+        let (next_sync_committee, next_sync_committee_branch) =
+            if update_attested_period == update_signature_period {
+                (
+                    (*attested_state.next_sync_committee).clone(),
+                    compute_merkle_proof_branch(attested_state, NEXT_SYNC_COMMITTEE_GINDEX)?,
+                )
+            } else {
+                (SyncCommittee::default(), FixedVector::default())
+            };
+
+        // Indicate finality whenever possible
+        let (finalized_header, finality_branch) = match finalized_block {
+            Some(finalized_block) => {
+                let finalized_header = if finalized_block.message.slot != GENESIS_SLOT {
+                    let finalized_header = LightClientHeader::from_block(finalized_block)?;
+                    ensure!(
+                        finalized_header.beacon.tree_hash_root()
+                            == attested_state.finalized_checkpoint.root,
+                        "Finalized block does not match the finalized checkpoint"
+                    );
+                    finalized_header
+                } else {
+                    ensure!(
+                        attested_state.finalized_checkpoint.root == B256::ZERO,
+                        "Genesis finalized checkpoint must have an empty root"
+                    );
+                    LightClientHeader::default()
+                };
+                (
+                    finalized_header,
+                    compute_merkle_proof_branch(attested_state, FINALIZED_ROOT_GINDEX)?,
+                )
+            }
+            None => (LightClientHeader::default(), FixedVector::default()),
+        };
+
+        Ok(Self {
+            attested_header: LightClientHeader::from_block(attested_block)?,
+            next_sync_committee,
+            next_sync_committee_branch,
+            finalized_header,
+            finality_branch,
+            sync_aggregate: block.message.body.sync_aggregate.clone(),
+            signature_slot: block.message.slot,
+        })
+    }
+
+    pub fn is_sync_committee_update(&self) -> bool {
+        self.next_sync_committee_branch
+            .iter()
+            .any(|node| *node != B256::ZERO)
+    }
+
+    pub fn is_finality_update(&self) -> bool {
+        self.finality_branch.iter().any(|node| *node != B256::ZERO)
+    }
+
+    /// Return whether ``self`` is a better update than ``old_update`` to keep for the sync
+    /// committee period of its attested header.
+    pub fn is_better_update(&self, old_update: &LightClientUpdate<E>) -> bool {
+        // Compare supermajority (> 2/3) sync committee participation
+        let max_active_participants = E::SYNC_COMMITTEE_SIZE;
+        let new_num_active_participants = self.sync_aggregate.num_active_participants();
+        let old_num_active_participants = old_update.sync_aggregate.num_active_participants();
+        let new_has_supermajority = new_num_active_participants * 3 >= max_active_participants * 2;
+        let old_has_supermajority = old_num_active_participants * 3 >= max_active_participants * 2;
+        if new_has_supermajority != old_has_supermajority {
+            return new_has_supermajority;
+        }
+        if !new_has_supermajority && new_num_active_participants != old_num_active_participants {
+            return new_num_active_participants > old_num_active_participants;
+        }
+
+        // Compare presence of relevant sync committee
+        let new_has_relevant_sync_committee = self.has_relevant_sync_committee();
+        let old_has_relevant_sync_committee = old_update.has_relevant_sync_committee();
+        if new_has_relevant_sync_committee != old_has_relevant_sync_committee {
+            return new_has_relevant_sync_committee;
+        }
+
+        // Compare indication of any finality
+        let new_has_finality = self.is_finality_update();
+        let old_has_finality = old_update.is_finality_update();
+        if new_has_finality != old_has_finality {
+            return new_has_finality;
+        }
+
+        // Compare sync committee finality
+        if new_has_finality {
+            let new_has_sync_committee_finality = self.has_sync_committee_finality();
+            let old_has_sync_committee_finality = old_update.has_sync_committee_finality();
+            if new_has_sync_committee_finality != old_has_sync_committee_finality {
+                return new_has_sync_committee_finality;
+            }
+        }
+
+        // Tiebreaker 1: Sync committee participation beyond supermajority
+        if new_num_active_participants != old_num_active_participants {
+            return new_num_active_participants > old_num_active_participants;
+        }
+
+        // Tiebreaker 2: Prefer older data (fewer changes to best)
+        if self.attested_header.beacon.slot != old_update.attested_header.beacon.slot {
+            return self.attested_header.beacon.slot < old_update.attested_header.beacon.slot;
+        }
+
+        // Tiebreaker 3: Prefer updates with earlier signature slots
+        self.signature_slot < old_update.signature_slot
+    }
+
+    fn has_relevant_sync_committee(&self) -> bool {
+        self.is_sync_committee_update()
+            && compute_sync_committee_period_at_slot::<E>(self.attested_header.beacon.slot)
+                == compute_sync_committee_period_at_slot::<E>(self.signature_slot)
+    }
+
+    fn has_sync_committee_finality(&self) -> bool {
+        compute_sync_committee_period_at_slot::<E>(self.finalized_header.beacon.slot)
+            == compute_sync_committee_period_at_slot::<E>(self.attested_header.beacon.slot)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::sync::Arc;
+
+    use ream_bls::{BLSSignature, PubKey};
+    use ssz_types::BitVector;
+
+    use super::*;
+    use crate::{
+        beacon_block_header::BeaconBlockHeader,
+        checkpoint::Checkpoint,
+        deneb::beacon_state::is_valid_merkle_branch,
+        eth_spec::MinimalEthSpec,
+        fork_choice::helpers::constants::EXECUTION_PAYLOAD_GINDEX,
+        merkle_proof::{floorlog2, get_subtree_index},
+        test_utils::{beacon_state, set_post_state, signed_beacon_block},
+    };
+
+    type E = MinimalEthSpec;
+
+    /// A finalized block, an attested block and the block signing it, with the post states of the
+    /// last two.
+    struct TestChain {
+        state: BeaconState<E>,
+        block: SignedBeaconBlock<E>,
+        attested_state: BeaconState<E>,
+        attested_block: SignedBeaconBlock<E>,
+        finalized_block: SignedBeaconBlock<E>,
+    }
+
+    fn test_chain(attested_slot: u64, finalized_slot: u64) -> TestChain {
+        let finalized_block = signed_beacon_block::<E>(finalized_slot, vec![]);
+
+        let mut attested_block = signed_beacon_block::<E>(attested_slot, vec![]);
+        attested_block.message.parent_root = B256::repeat_byte(1);
+        let mut attested_state = beacon_state::<E>(attested_slot, vec![]);
+        attested_state.next_sync_committee = Arc::new(SyncCommittee {
+            pubkeys: FixedVector::default(),
+            aggregate_pubkey: PubKey {
+                inner: FixedVector::from(vec![1; 48]),
+            },
+        });
+        attested_state.finalized_checkpoint = Checkpoint {
+            epoch: finalized_slot / 8,
+            root: if finalized_slot == GENESIS_SLOT {
+                B256::ZERO
+            } else {
+                finalized_block.message.tree_hash_root()
+            },
+        };
+        set_post_state(&mut attested_state, &mut attested_block);
+
+        let mut block = signed_beacon_block::<E>(attested_slot + 1, vec![]);
+        block.message.parent_root = attested_block.message.tree_hash_root();
+        block
+            .message
+            .body
+            .sync_aggregate
+            .sync_committee_bits
+            .set(0, true)
+            .unwrap();
+        let mut state = beacon_state::<E>(attested_slot + 1, vec![]);
+        set_post_state(&mut state, &mut block);
+
+        TestChain {
+            state,
+            block,
+            attested_state,
+            attested_block,
+            finalized_block,
+        }
+    }
+
+    impl TestChain {
+        fn update(
+            &self,
+            finalized_block: Option<&SignedBeaconBlock<E>>,
+        ) -> anyhow::Result<LightClientUpdate<E>> {
+            LightClientUpdate::new(
+                &self.state,
+                &self.block,
+                &self.attested_state,
+                &self.attested_block,
+                finalized_block,
+            )
+        }
+    }
+
+    fn is_valid_branch(leaf: B256, branch: &[B256], generalized_index: u64, root: B256) -> bool {
+        is_valid_merkle_branch(
+            leaf,
+            branch,
+            floorlog2(generalized_index),
+            get_subtree_index(generalized_index),
+            root,
+        )
+    }
+
+    fn header(slot: u64) -> LightClientHeader {
+        LightClientHeader {
+            beacon: BeaconBlockHeader {
+                slot,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Build an update of the minimal preset, where a sync committee period is 64 slots and the
+    /// sync committee has 32 members.
+    fn update(
+        num_participants: usize,
+        attested_slot: u64,
+        signature_slot: u64,
+        has_next_sync_committee: bool,
+        finalized_slot: Option<u64>,
+    ) -> LightClientUpdate<MinimalEthSpec> {
+        let mut sync_committee_bits = BitVector::new();
+        for index in 0..num_participants {
+            sync_committee_bits.set(index, true).unwrap();
+        }
+        LightClientUpdate {
+            attested_header: header(attested_slot),
+            next_sync_committee: SyncCommittee::default(),
+            next_sync_committee_branch: if has_next_sync_committee {
+                FixedVector::from(vec![B256::repeat_byte(1); 5])
+            } else {
+                FixedVector::default()
+            },
+            finalized_header: header(finalized_slot.unwrap_or_default()),
+            finality_branch: if finalized_slot.is_some() {
+                FixedVector::from(vec![B256::repeat_byte(1); 6])
+            } else {
+                FixedVector::default()
+            },
+            sync_aggregate: SyncAggregate {
+                sync_committee_bits,
+                sync_committee_signature: BLSSignature::infinity(),
+            },
+            signature_slot,
+        }
+    }
+
+    #[test]
+    fn test_is_better_update_participation() {
+        // A supermajority outweighs everything else
+        let supermajority = update(22, 10, 11, false, None);
+        let minority = update(21, 10, 11, true, Some(8));
+        assert!(supermajority.is_better_update(&minority));
+        assert!(!minority.is_better_update(&supermajority));
+
+        // Without a supermajority, more participants outweigh everything else
+        let more_participants = update(20, 10, 11, false, None);
+        let fewer_participants = update(10, 10, 11, true, Some(8));
+        assert!(more_participants.is_better_update(&fewer_participants));
+        assert!(!fewer_participants.is_better_update(&more_participants));
+    }
+
+    #[test]
+    fn test_is_better_update_sync_committee_and_finality() {
+        // A relevant next sync committee outweighs finality
+        let sync_committee_update = update(24, 10, 11, true, None);
+        let finality_update = update(24, 10, 11, false, Some(8));
+        assert!(sync_committee_update.is_better_update(&finality_update));
+        assert!(!finality_update.is_better_update(&sync_committee_update));
+
+        // A next sync committee signed in a later period is not relevant
+        let irrelevant_sync_committee_update = update(24, 63, 64, true, None);
+        let plain_update = update(24, 63, 64, false, None);
+        assert!(!irrelevant_sync_committee_update.is_better_update(&plain_update));
+        assert!(!plain_update.is_better_update(&irrelevant_sync_committee_update));
+
+        // Finality outweighs the tiebreakers
+        let no_finality_update = update(32, 10, 11, false, None);
+        assert!(finality_update.is_better_update(&no_finality_update));
+        assert!(!no_finality_update.is_better_update(&finality_update));
+
+        // Finality within the attested period outweighs finality of an earlier period
+        let sync_committee_finality_update = update(24, 70, 71, false, Some(65));
+        let older_finality_update = update(32, 70, 71, false, Some(60));
+        assert!(sync_committee_finality_update.is_better_update(&older_finality_update));
+        assert!(!older_finality_update.is_better_update(&sync_committee_finality_update));
+    }
+
+    #[test]
+    fn test_is_better_update_tiebreakers() {
+        let base = update(24, 10, 12, false, None);
+        assert!(!base.is_better_update(&base));
+
+        // Participation beyond the supermajority
+        let more_participants = update(30, 11, 13, false, None);
+        assert!(more_participants.is_better_update(&base));
+        assert!(!base.is_better_update(&more_participants));
+
+        // Older attested header
+        let older_attested_header = update(24, 9, 12, false, None);
+        assert!(older_attested_header.is_better_update(&base));
+        assert!(!base.is_better_update(&older_attested_header));
+
+        // Earlier signature slot
+        let earlier_signature_slot = update(24, 10, 11, false, None);
+        assert!(earlier_signature_slot.is_better_update(&base));
+        assert!(!base.is_better_update(&earlier_signature_slot));
+    }
+
+    #[test]
+    fn test_new() {
+        let chain = test_chain(16, 8);
+        let update = chain.update(Some(&chain.finalized_block)).unwrap();
+
+        assert_eq!(
+            update.attested_header.beacon.tree_hash_root(),
+            chain.attested_block.message.tree_hash_root()
+        );
+        assert_eq!(
+            update.finalized_header.beacon.tree_hash_root(),
+            chain.finalized_block.message.tree_hash_root()
+        );
+        assert_eq!(
+            update.sync_aggregate,
+            chain.block.message.body.sync_aggregate
+        );
+        assert_eq!(update.signature_slot, 17);
+        assert!(update.is_sync_committee_update());
+        assert!(update.is_finality_update());
+
+        let attested_state_root = update.attested_header.beacon.state_root;
+        assert_eq!(
+            update.next_sync_committee,
+            *chain.attested_state.next_sync_committee
+        );
+        assert!(is_valid_branch(
+            update.next_sync_committee.tree_hash_root(),
+            &update.next_sync_committee_branch,
+            NEXT_SYNC_COMMITTEE_GINDEX,
+            attested_state_root
+        ));
+        assert!(!is_valid_branch(
+            chain.attested_state.current_sync_committee.tree_hash_root(),
+            &update.next_sync_committee_branch,
+            NEXT_SYNC_COMMITTEE_GINDEX,
+            attested_state_root
+        ));
+        assert!(is_valid_branch(
+            update.finalized_header.beacon.tree_hash_root(),
+            &update.finality_branch,
+            FINALIZED_ROOT_GINDEX,
+            attested_state_root
+        ));
+        for header in [&update.attested_header, &update.finalized_header] {
+            assert!(is_valid_branch(
+                header.execution.tree_hash_root(),
+                &header.execution_branch,
+                EXECUTION_PAYLOAD_GINDEX,
+                header.beacon.body_root
+            ));
+        }
+    }
+
+    #[test]
+    fn test_new_without_finality() {
+        let chain = test_chain(16, 8);
+        let update = chain.update(None).unwrap();
+        assert!(!update.is_finality_update());
+        assert_eq!(update.finalized_header, LightClientHeader::default());
+    }
+
+    #[test]
+    fn test_new_genesis_finalized_checkpoint() {
+        let chain = test_chain(16, GENESIS_SLOT);
+        let update = chain.update(Some(&chain.finalized_block)).unwrap();
+
+        // The genesis block is finalized with an empty root and header
+        assert_eq!(update.finalized_header, LightClientHeader::default());
+        assert!(is_valid_branch(
+            B256::ZERO,
+            &update.finality_branch,
+            FINALIZED_ROOT_GINDEX,
+            update.attested_header.beacon.state_root
+        ));
+    }
+
+    #[test]
+    fn test_new_signed_in_next_period() {
+        // The attested block is the last of the first period and the signing block the first of
+        // the next one
+        let chain = test_chain(63, 8);
+        let update = chain.update(Some(&chain.finalized_block)).unwrap();
+        assert!(!update.is_sync_committee_update());
+        assert_eq!(update.next_sync_committee, SyncCommittee::default());
+        assert!(update.is_finality_update());
+    }
+
+    #[test]
+    fn test_new_rejections() {
+        let chain = test_chain(16, 8);
+
+        let mut no_participants = test_chain(16, 8);
+        no_participants
+            .block
+            .message
+            .body
+            .sync_aggregate
+            .sync_committee_bits = BitVector::new();
+        set_post_state(&mut no_participants.state, &mut no_participants.block);
+        assert_eq!(
+            no_participants.update(None).unwrap_err().to_string(),
+            "Not enough sync committee participants"
+        );
+
+        let mut advanced_state = test_chain(16, 8);
+        advanced_state.state.slot += 1;
+        assert_eq!(
+            advanced_state.update(None).unwrap_err().to_string(),
+            "State must be at the slot of its latest block header"
+        );
+
+        let mut other_block = test_chain(16, 8);
+        other_block.block.message.proposer_index = 1;
+        assert_eq!(
+            other_block.update(None).unwrap_err().to_string(),
+            "Block does not match the state"
+        );
+
+        let mut advanced_attested_state = test_chain(16, 8);
+        advanced_attested_state.attested_state.slot += 1;
+        assert_eq!(
+            advanced_attested_state
+                .update(None)
+                .unwrap_err()
+                .to_string(),
+            "Attested state must be at the slot of its latest block header"
+        );
+
+        let mut other_parent = test_chain(16, 8);
+        other_parent.block.message.parent_root = B256::repeat_byte(2);
+        set_post_state(&mut other_parent.state, &mut other_parent.block);
+        assert_eq!(
+            other_parent.update(None).unwrap_err().to_string(),
+            "Attested block does not match the attested state or the parent of the block"
+        );
+
+        let other_finalized_block = signed_beacon_block::<E>(9, vec![]);
+        assert_eq!(
+            chain
+                .update(Some(&other_finalized_block))
+                .unwrap_err()
+                .to_string(),
+            "Finalized block does not match the finalized checkpoint"
+        );
+
+        let genesis_block = signed_beacon_block::<E>(GENESIS_SLOT, vec![]);
+        assert_eq!(
+            chain.update(Some(&genesis_block)).unwrap_err().to_string(),
+            "Genesis finalized checkpoint must have an empty root"
+        );
+    }
+}
@@ -0,0 +1,6 @@
+pub mod best_update_store;
+pub mod light_client_bootstrap;
+pub mod light_client_finality_update;
+pub mod light_client_header;
+pub mod light_client_optimistic_update;
+pub mod light_client_update;
@@ -0,0 +1,864 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use alloy_primitives::{aliases::B32, Address, B256, U256};
+use anyhow::{anyhow, ensure};
+use ethereum_hashing::hash_fixed;
+use ream_bls::{BLSSignature, PubKey};
+use ssz_types::{typenum::Unsigned, BitList, BitVector, FixedVector, VariableList};
+use tree_hash::{TreeHash, TreeHashType, BYTES_PER_CHUNK};
+
+use crate::{
+    attestation::Attestation,
+    attester_slashing::AttesterSlashing,
+    beacon_block_header::BeaconBlockHeader,
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    checkpoint::Checkpoint,
+    deneb::{
+        beacon_block_body::BeaconBlockBody,
+        beacon_state::{is_valid_merkle_branch, BeaconState},
+        execution_payload::ExecutionPayload,
+        execution_payload_header::ExecutionPayloadHeader,
+    },
+    deposit::Deposit,
+    eth_1_data::Eth1Data,
+    eth_spec::EthSpec,
+    fork::Fork,
+    historical_summary::HistoricalSummary,
+    kzg_commitment::KZGCommitment,
+    proposer_slashing::ProposerSlashing,
+    sync_aggregate::SyncAggregate,
+    sync_committee::SyncCommittee,
+    validator::Validator,
+    voluntary_exit::SignedVoluntaryExit,
+    withdrawal::Withdrawal,
+};
+
+/// One step of a path into an SSZ object, e.g. ``finalized_checkpoint`` or ``[3]``.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PathElement {
+    Field(String),
+    Index(usize),
+}
+
+/// Parse a path such as ``finalized_checkpoint.root`` or ``blob_kzg_commitments[3]``.
+pub fn parse_path(path: &str) -> anyhow::Result<Vec<PathElement>> {
+    let mut elements = vec![];
+    for part in path.split('.') {
+        let (field, mut indices) = match part.find('[') {
+            Some(position) => part.split_at(position),
+            None => (part, ""),
+        };
+        if !field.is_empty() {
+            elements.push(PathElement::Field(field.to_string()));
+        }
+        while !indices.is_empty() {
+            let end = indices
+                .find(']')
+                .ok_or_else(|| anyhow!("Unclosed index in path {path}"))?;
+            ensure!(indices.starts_with('['), "Malformed index in path {path}");
+            elements.push(PathElement::Index(indices[1..end].parse()?));
+            indices = &indices[end + 1..];
+        }
+    }
+    ensure!(!elements.is_empty(), "Empty path");
+    Ok(elements)
+}
+
+/// An SSZ value viewed as the Merkle tree of chunks that its ``hash_tree_root`` is computed from.
+pub trait MerkleNode {
+    /// The chunks merkleized into this value's root, without the zero padding.
+    fn chunks(&self) -> Vec<B256>;
+
+    /// Depth of the chunk tree, i.e. ``ceil(log2(chunk_count))`` where lists use their limit.
+    fn chunk_depth(&self) -> u32;
+
+    /// The length mixed into the root, for lists.
+    fn list_length(&self) -> Option<usize> {
+        None
+    }
+
+    /// The index of the chunk that ``element`` resolves to.
+    fn chunk_index(&self, _element: &PathElement) -> Option<usize> {
+        None
+    }
+
+    /// The value committed to by the chunk at ``chunk_index``, if it can be descended into.
+    fn child(&self, _chunk_index: usize) -> Option<&dyn MerkleNode> {
+        None
+    }
+
+    /// Generate a proof for the value at ``path``, e.g. ``finalized_checkpoint.root``.
+    fn merkle_proof(&self, path: &str) -> anyhow::Result<MerkleProof>
+    where
+        Self: Sized,
+    {
+        compute_merkle_proof(self, &parse_path(path)?)
+    }
+
+    /// Generate a proof for each of ``paths`` and merge them into a single multiproof.
+    fn merkle_multiproof(&self, paths: &[&str]) -> anyhow::Result<MerkleMultiproof>
+    where
+        Self: Sized,
+    {
+        let paths = paths
+            .iter()
+            .map(|path| parse_path(path))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        compute_merkle_multiproof(self, &paths)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MerkleProof {
+    pub generalized_index: u64,
+    pub leaf: B256,
+    pub branch: Vec<B256>,
+}
+
+impl MerkleProof {
+    pub fn depth(&self) -> u64 {
+        floorlog2(self.generalized_index)
+    }
+
+    pub fn subtree_index(&self) -> u64 {
+        get_subtree_index(self.generalized_index)
+    }
+
+    pub fn verify(&self, root: B256) -> bool {
+        self.branch.len() as u64 == self.depth()
+            && is_valid_merkle_branch(
+                self.leaf,
+                &self.branch,
+                self.depth(),
+                self.subtree_index(),
+                root,
+            )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MerkleMultiproof {
+    pub generalized_indices: Vec<u64>,
+    pub leaves: Vec<B256>,
+    pub proof: Vec<B256>,
+}
+
+impl MerkleMultiproof {
+    pub fn verify(&self, root: B256) -> bool {
+        verify_merkle_multiproof(&self.leaves, &self.proof, &self.generalized_indices, root)
+    }
+}
+
+/// Generate a proof for the value at ``path`` inside ``root``.
+pub fn compute_merkle_proof(
+    root: &dyn MerkleNode,
+    path: &[PathElement],
+) -> anyhow::Result<MerkleProof> {
+    let mut node = root;
+    let mut chunk_indices = vec![];
+    for (i, element) in path.iter().enumerate() {
+        let chunk_index = node
+            .chunk_index(element)
+            .ok_or_else(|| anyhow!("Path element {element:?} not found"))?;
+        chunk_indices.push(chunk_index);
+        if i + 1 < path.len() {
+            node = node
+                .child(chunk_index)
+                .ok_or_else(|| anyhow!("Cannot descend into {element:?}"))?;
+        }
+    }
+    compute_merkle_proof_for_chunks(root, &chunk_indices)
+}
+
+/// Generate a proof for the node at ``generalized_index`` inside ``root``.
+///
+/// The index has to point at a chunk of ``root`` or of one of its descendants.
+pub fn compute_merkle_proof_for_generalized_index(
+    root: &dyn MerkleNode,
+    generalized_index: u64,
+) -> anyhow::Result<MerkleProof> {
+    ensure!(generalized_index > 1, "Cannot prove the root itself");
+    let mut remaining_depth = floorlog2(generalized_index) as u32;
+    let mut node = root;
+    let mut chunk_indices = vec![];
+    loop {
+        let is_list = node.list_length().is_some();
+        let depth = node.chunk_depth() + is_list as u32;
+        ensure!(
+            remaining_depth >= depth,
+            "Generalized index {generalized_index} points inside a chunk tree"
+        );
+        let local_index = (generalized_index >> (remaining_depth - depth)) & ((1 << depth) - 1);
+        let chunk_index = local_index & ((1 << node.chunk_depth()) - 1);
+        ensure!(
+            !is_list || chunk_index == local_index,
+            "Generalized index {generalized_index} points at a list length"
+        );
+        chunk_indices.push(chunk_index as usize);
+        remaining_depth -= depth;
+        if remaining_depth == 0 {
+            break;
+        }
+        node = node.child(chunk_index as usize).ok_or_else(|| {
+            anyhow!("Generalized index {generalized_index} points inside a leaf value")
+        })?;
+    }
+    compute_merkle_proof_for_chunks(root, &chunk_indices)
+}
+
+/// Return the branch proving the node at ``generalized_index``, sized for an SSZ container field.
+pub fn compute_merkle_proof_branch<N: Unsigned>(
+    root: &dyn MerkleNode,
+    generalized_index: u64,
+) -> anyhow::Result<FixedVector<B256, N>> {
+    let proof = compute_merkle_proof_for_generalized_index(root, generalized_index)?;
+    FixedVector::new(proof.branch)
+        .map_err(|err| anyhow!("Branch length does not match the generalized index {err:?}"))
+}
+
+fn compute_merkle_proof_for_chunks(
+    root: &dyn MerkleNode,
+    chunk_indices: &[usize],
+) -> anyhow::Result<MerkleProof> {
+    let mut node = root;
+    let mut generalized_index = 1;
+    let mut leaf = B256::ZERO;
+    let mut branches = vec![];
+    for (i, &chunk_index) in chunk_indices.iter().enumerate() {
+        let depth = node.chunk_depth();
+        ensure!(
+            (chunk_index as u64) < 1 << depth,
+            "Chunk index {chunk_index} out of bounds"
+        );
+        let chunks = node.chunks();
+        let mut branch = compute_merkle_branch(&chunks, depth, chunk_index);
+        let mut local_index = (1 << depth) | chunk_index as u64;
+        if let Some(length) = node.list_length() {
+            branch.push(length_chunk(length));
+            local_index = concat_generalized_indices(&[2, local_index]);
+        }
+        generalized_index = concat_generalized_indices(&[generalized_index, local_index]);
+        leaf = chunks.get(chunk_index).copied().unwrap_or_default();
+        branches.push(branch);
+        if i + 1 < chunk_indices.len() {
+            node = node
+                .child(chunk_index)
+                .ok_or_else(|| anyhow!("Cannot descend into chunk {chunk_index}"))?;
+        }
+    }
+    Ok(MerkleProof {
+        generalized_index,
+        leaf,
+        branch: branches.into_iter().rev().flatten().collect(),
+    })
+}
+
+/// Generate a multiproof for the values at ``paths`` inside ``root``.
+pub fn compute_merkle_multiproof(
+    root: &dyn MerkleNode,
+    paths: &[Vec<PathElement>],
+) -> anyhow::Result<MerkleMultiproof> {
+    let proofs = paths
+        .iter()
+        .map(|path| compute_merkle_proof(root, path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // Every helper node is a sibling on the branch of at least one of the leaves.
+    let mut nodes = HashMap::new();
+    for proof in &proofs {
+        for (level, node) in proof.branch.iter().enumerate() {
+            nodes.insert((proof.generalized_index >> level) ^ 1, *node);
+        }
+    }
+
+    let generalized_indices = proofs
+        .iter()
+        .map(|proof| proof.generalized_index)
+        .collect::<Vec<_>>();
+    let proof = get_helper_indices(&generalized_indices)
+        .iter()
+        .map(|index| {
+            nodes
+                .get(index)
+                .copied()
+                .ok_or_else(|| anyhow!("Missing helper node {index}"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(MerkleMultiproof {
+        generalized_indices,
+        leaves: proofs.iter().map(|proof| proof.leaf).collect(),
+        proof,
+    })
+}
+
+/// Return the sibling nodes of ``leaf`` at ``index`` bottom-up, padding ``chunks`` with zero
+/// hashes up to ``2**depth`` chunks.
+fn compute_merkle_branch(chunks: &[B256], depth: u32, index: usize) -> Vec<B256> {
+    let mut branch = vec![];
+    let mut layer = chunks.to_vec();
+    let mut index = index;
+    let mut zero_hash = B256::ZERO;
+    for _ in 0..depth {
+        branch.push(layer.get(index ^ 1).copied().unwrap_or(zero_hash));
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_pair(pair[0], pair.get(1).copied().unwrap_or(zero_hash)))
+            .collect();
+        zero_hash = hash_pair(zero_hash, zero_hash);
+        index /= 2;
+    }
+    branch
+}
+
+fn hash_pair(left: B256, right: B256) -> B256 {
+    B256::from(hash_fixed(&[left.as_slice(), right.as_slice()].concat()))
+}
+
+fn length_chunk(length: usize) -> B256 {
+    B256::from(U256::from(length).to_le_bytes::<32>())
+}
+
+pub fn floorlog2(x: u64) -> u64 {
+    63 - x.leading_zeros() as u64
+}
+
+pub fn get_subtree_index(generalized_index: u64) -> u64 {
+    generalized_index % 2u64.pow(floorlog2(generalized_index) as u32)
+}
+
+/// Given generalized indices i1 for A -> B, i2 for B -> C .... i_n for Y -> Z, returns
+/// the generalized index for A -> Z.
+pub fn concat_generalized_indices(indices: &[u64]) -> u64 {
+    indices.iter().fold(1, |acc, &index| {
+        let depth = floorlog2(index);
+        acc * 2u64.pow(depth as u32) + (index - 2u64.pow(depth as u32))
+    })
+}
+
+pub fn generalized_index_sibling(index: u64) -> u64 {
+    index ^ 1
+}
+
+pub fn generalized_index_parent(index: u64) -> u64 {
+    index / 2
+}
+
+/// Get the generalized indices of the sister chunks along the path from the chunk with the
+/// given tree index to the root.
+pub fn get_branch_indices(tree_index: u64) -> Vec<u64> {
+    let mut branch = vec![];
+    let mut index = tree_index;
+    while index > 1 {
+        branch.push(generalized_index_sibling(index));
+        index = generalized_index_parent(index);
+    }
+    branch
+}
+
+/// Get the generalized indices of the chunks along the path from the chunk with the
+/// given tree index to the root.
+pub fn get_path_indices(tree_index: u64) -> Vec<u64> {
+    let mut path = vec![];
+    let mut index = tree_index;
+    while index > 1 {
+        path.push(index);
+        index = generalized_index_parent(index);
+    }
+    path
+}
+
+/// Get the generalized indices of all "extra" chunks in the tree needed to prove the chunks with
+/// the given generalized indices. Note that the decreasing order is chosen deliberately to ensure
+/// equivalence to the order of hashes in a regular single-item Merkle proof in the single-item
+/// case.
+pub fn get_helper_indices(indices: &[u64]) -> Vec<u64> {
+    let mut all_helper_indices = HashSet::new();
+    let mut all_path_indices = HashSet::new();
+    for &index in indices {
+        all_helper_indices.extend(get_branch_indices(index));
+        all_path_indices.extend(get_path_indices(index));
+    }
+    let mut helper_indices = all_helper_indices
+        .difference(&all_path_indices)
+        .copied()
+        .collect::<Vec<_>>();
+    helper_indices.sort_unstable_by(|a, b| b.cmp(a));
+    helper_indices
+}
+
+pub fn calculate_merkle_root(leaf: B256, proof: &[B256], index: u64) -> anyhow::Result<B256> {
+    ensure!(proof.len() as u64 == floorlog2(index));
+    let mut root = leaf;
+    for (i, node) in proof.iter().enumerate() {
+        if (index / 2u64.pow(i as u32)) % 2 == 1 {
+            root = hash_pair(*node, root);
+        } else {
+            root = hash_pair(root, *node);
+        }
+    }
+    Ok(root)
+}
+
+pub fn verify_merkle_proof(leaf: B256, proof: &[B256], index: u64, root: B256) -> bool {
+    calculate_merkle_root(leaf, proof, index).is_ok_and(|calculated| calculated == root)
+}
+
+pub fn calculate_multi_merkle_root(
+    leaves: &[B256],
+    proof: &[B256],
+    indices: &[u64],
+) -> anyhow::Result<B256> {
+    ensure!(leaves.len() == indices.len());
+    let helper_indices = get_helper_indices(indices);
+    ensure!(proof.len() == helper_indices.len());
+    let mut objects = indices
+        .iter()
+        .copied()
+        .zip(leaves.iter().copied())
+        .chain(helper_indices.iter().copied().zip(proof.iter().copied()))
+        .collect::<HashMap<_, _>>();
+    let mut keys = objects.keys().copied().collect::<Vec<_>>();
+    keys.sort_unstable_by(|a, b| b.cmp(a));
+    let mut position = 0;
+    while position < keys.len() {
+        let key = keys[position];
+        let parent = generalized_index_parent(key);
+        if key > 1 && !objects.contains_key(&parent) {
+            if let (Some(&node), Some(&sibling)) = (
+                objects.get(&(key | 1)),
+                objects.get(&generalized_index_sibling(key | 1)),
+            ) {
+                objects.insert(parent, hash_pair(sibling, node));
+                keys.push(parent);
+            }
+        }
+        position += 1;
+    }
+    objects
+        .get(&1)
+        .copied()
+        .ok_or_else(|| anyhow!("Multiproof does not reach the root"))
+}
+
+pub fn verify_merkle_multiproof(
+    leaves: &[B256],
+    proof: &[B256],
+    indices: &[u64],
+    root: B256,
+) -> bool {
+    calculate_multi_merkle_root(leaves, proof, indices).is_ok_and(|calculated| calculated == root)
+}
+
+fn ceillog2(x: u64) -> u32 {
+    x.next_power_of_two().trailing_zeros()
+}
+
+/// Chunks of a sequence of values, packed for basic types and one root per value otherwise.
+fn sequence_chunks<'a, T: TreeHash + 'a>(values: impl Iterator<Item = &'a T>) -> Vec<B256> {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => values
+            .flat_map(|value| value.tree_hash_packed_encoding())
+            .collect::<Vec<_>>()
+            .chunks(BYTES_PER_CHUNK)
+            .map(B256::right_padding_from)
+            .collect(),
+        _ => values.map(|value| value.tree_hash_root()).collect(),
+    }
+}
+
+fn sequence_chunk_limit<T: TreeHash>(length: usize) -> u64 {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => (length as u64).div_ceil(T::tree_hash_packing_factor() as u64),
+        _ => length as u64,
+    }
+}
+
+/// Chunk index of element ``index`` in a sequence of ``length`` values.
+fn sequence_chunk_index<T: TreeHash>(element: &PathElement, length: usize) -> Option<usize> {
+    match element {
+        PathElement::Index(index) if *index < length => match T::tree_hash_type() {
+            TreeHashType::Basic => Some(index / T::tree_hash_packing_factor()),
+            _ => Some(*index),
+        },
+        _ => None,
+    }
+}
+
+fn sequence_child<T: TreeHash + MerkleNode>(
+    values: &[T],
+    chunk_index: usize,
+) -> Option<&dyn MerkleNode> {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => None,
+        _ => values
+            .get(chunk_index)
+            .map(|value| value as &dyn MerkleNode),
+    }
+}
+
+impl<T: TreeHash + MerkleNode, N: Unsigned> MerkleNode for VariableList<T, N> {
+    fn chunks(&self) -> Vec<B256> {
+        sequence_chunks(self.iter())
+    }
+
+    fn chunk_depth(&self) -> u32 {
+        ceillog2(sequence_chunk_limit::<T>(N::to_usize()))
+    }
+
+    fn list_length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn chunk_index(&self, element: &PathElement) -> Option<usize> {
+        sequence_chunk_index::<T>(element, self.len())
+    }
+
+    fn child(&self, chunk_index: usize) -> Option<&dyn MerkleNode> {
+        sequence_child(self, chunk_index)
+    }
+}
+
+impl<T: TreeHash + MerkleNode, N: Unsigned> MerkleNode for FixedVector<T, N> {
+    fn chunks(&self) -> Vec<B256> {
+        sequence_chunks(self.iter())
+    }
+
+    fn chunk_depth(&self) -> u32 {
+        ceillog2(sequence_chunk_limit::<T>(N::to_usize()))
+    }
+
+    fn chunk_index(&self, element: &PathElement) -> Option<usize> {
+        sequence_chunk_index::<T>(element, N::to_usize())
+    }
+
+    fn child(&self, chunk_index: usize) -> Option<&dyn MerkleNode> {
+        sequence_child(self, chunk_index)
+    }
+}
+
+impl<T: MerkleNode> MerkleNode for Arc<T> {
+    fn chunks(&self) -> Vec<B256> {
+        self.as_ref().chunks()
+    }
+
+    fn chunk_depth(&self) -> u32 {
+        self.as_ref().chunk_depth()
+    }
+
+    fn list_length(&self) -> Option<usize> {
+        self.as_ref().list_length()
+    }
+
+    fn chunk_index(&self, element: &PathElement) -> Option<usize> {
+        self.as_ref().chunk_index(element)
+    }
+
+    fn child(&self, chunk_index: usize) -> Option<&dyn MerkleNode> {
+        self.as_ref().child(chunk_index)
+    }
+}
+
+/// Implement [`MerkleNode`] for values that are proven as a whole and cannot be descended into.
+macro_rules! impl_merkle_node_for_leaf {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl MerkleNode for $ty {
+                fn chunks(&self) -> Vec<B256> {
+                    vec![self.tree_hash_root()]
+                }
+
+                fn chunk_depth(&self) -> u32 {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_merkle_node_for_leaf!(
+    bool,
+    u8,
+    u64,
+    U256,
+    B32,
+    B256,
+    Address,
+    PubKey,
+    BLSSignature,
+    KZGCommitment,
+    ProposerSlashing,
+    AttesterSlashing,
+    Attestation,
+    Deposit,
+    SignedVoluntaryExit,
+    SignedBLSToExecutionChange,
+);
+
+impl<N: Unsigned + Clone> MerkleNode for BitVector<N> {
+    fn chunks(&self) -> Vec<B256> {
+        vec![self.tree_hash_root()]
+    }
+
+    fn chunk_depth(&self) -> u32 {
+        0
+    }
+}
+
+impl<N: Unsigned + Clone> MerkleNode for BitList<N> {
+    fn chunks(&self) -> Vec<B256> {
+        vec![self.tree_hash_root()]
+    }
+
+    fn chunk_depth(&self) -> u32 {
+        0
+    }
+}
+
+/// Implement [`MerkleNode`] for a container, listing all of its fields in order.
+///
+/// Every field type has to implement [`MerkleNode`] so that paths can descend into it.
+macro_rules! impl_merkle_node_for_container {
+    ($(<$generic:ident: $bound:path>)? $ty:ty { $($field:ident),* $(,)? }) => {
+        impl$(<$generic: $bound>)? MerkleNode for $ty {
+            fn chunks(&self) -> Vec<B256> {
+                vec![$(self.$field.tree_hash_root()),*]
+            }
+
+            fn chunk_depth(&self) -> u32 {
+                ceillog2([$(stringify!($field)),*].len() as u64)
+            }
+
+            fn chunk_index(&self, element: &PathElement) -> Option<usize> {
+                match element {
+                    PathElement::Field(name) => {
+                        [$(stringify!($field)),*].iter().position(|field| field == name)
+                    }
+                    PathElement::Index(_) => None,
+                }
+            }
+
+            fn child(&self, chunk_index: usize) -> Option<&dyn MerkleNode> {
+                [$(&self.$field as &dyn MerkleNode),*].get(chunk_index).copied()
+            }
+        }
+    };
+}
+
+impl_merkle_node_for_container!(Checkpoint { epoch, root });
+
+impl_merkle_node_for_container!(Fork {
+    previous_version,
+    current_version,
+    epoch,
+});
+
+impl_merkle_node_for_container!(Eth1Data {
+    deposit_root,
+    deposit_count,
+    block_hash,
+});
+
+impl_merkle_node_for_container!(BeaconBlockHeader {
+    slot,
+    proposer_index,
+    parent_root,
+    state_root,
+    body_root,
+});
+
+impl_merkle_node_for_container!(HistoricalSummary {
+    block_summary_root,
+    state_summary_root,
+});
+
+impl_merkle_node_for_container!(Withdrawal {
+    index,
+    validator_index,
+    address,
+    amount,
+});
+
+impl_merkle_node_for_container!(Validator {
+    pubkey,
+    withdrawal_credentials,
+    effective_balance,
+    slashed,
+    activation_eligibility_epoch,
+    activation_epoch,
+    exit_epoch,
+    withdrawable_epoch,
+});
+
+impl_merkle_node_for_container!(<E: EthSpec> SyncCommittee<E> {
+    pubkeys,
+    aggregate_pubkey,
+});
+
+impl_merkle_node_for_container!(<E: EthSpec> SyncAggregate<E> {
+    sync_committee_bits,
+    sync_committee_signature,
+});
+
+impl_merkle_node_for_container!(ExecutionPayloadHeader {
+    parent_hash,
+    fee_recipient,
+    state_root,
+    receipts_root,
+    logs_bloom,
+    prev_randao,
+    block_number,
+    gas_limit,
+    gas_used,
+    timestamp,
+    extra_data,
+    base_fee_per_gas,
+    block_hash,
+    transactions_root,
+    withdrawals_root,
+    blob_gas_used,
+    excess_blob_gas,
+});
+
+impl_merkle_node_for_container!(<E: EthSpec> ExecutionPayload<E> {
+    parent_hash,
+    fee_recipient,
+    state_root,
+    receipts_root,
+    logs_bloom,
+    prev_randao,
+    block_number,
+    gas_limit,
+    gas_used,
+    timestamp,
+    extra_data,
+    base_fee_per_gas,
+    block_hash,
+    transactions,
+    withdrawals,
+    blob_gas_used,
+    excess_blob_gas,
+});
+
+impl_merkle_node_for_container!(<E: EthSpec> BeaconBlockBody<E> {
+    randao_reveal,
+    eth1_data,
+    graffiti,
+    proposer_slashings,
+    attester_slashings,
+    attestations,
+    deposits,
+    voluntary_exits,
+    sync_aggregate,
+    execution_payload,
+    bls_to_execution_changes,
+    blob_kzg_commitments,
+});
+
+impl_merkle_node_for_container!(<E: EthSpec> BeaconState<E> {
+    genesis_time,
+    genesis_validators_root,
+    slot,
+    fork,
+    latest_block_header,
+    block_roots,
+    state_roots,
+    historical_roots,
+    eth1_data,
+    eth1_data_votes,
+    eth1_deposit_index,
+    validators,
+    balances,
+    randao_mixes,
+    slashings,
+    previous_epoch_participation,
+    current_epoch_participation,
+    justification_bits,
+    previous_justified_checkpoint,
+    current_justified_checkpoint,
+    finalized_checkpoint,
+    inactivity_scores,
+    current_sync_committee,
+    next_sync_committee,
+    latest_execution_payload_header,
+    next_withdrawal_index,
+    next_withdrawal_validator_index,
+    historical_summaries,
+});
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::b256;
+
+    use super::*;
+
+    fn header() -> BeaconBlockHeader {
+        BeaconBlockHeader {
+            slot: 42,
+            proposer_index: 7,
+            parent_root: b256!("0101010101010101010101010101010101010101010101010101010101010101"),
+            state_root: b256!("0202020202020202020202020202020202020202020202020202020202020202"),
+            body_root: b256!("0303030303030303030303030303030303030303030303030303030303030303"),
+        }
+    }
+
+    #[test]
+    fn parse_path_with_indices() {
+        assert_eq!(
+            parse_path("validators[3].effective_balance").unwrap(),
+            vec![
+                PathElement::Field("validators".to_string()),
+                PathElement::Index(3),
+                PathElement::Field("effective_balance".to_string()),
+            ]
+        );
+        assert!(parse_path("blob_kzg_commitments[1").is_err());
+    }
+
+    #[test]
+    fn single_proof_matches_generalized_index() {
+        let header = header();
+        let proof = header.merkle_proof("state_root").unwrap();
+
+        assert_eq!(proof.generalized_index, 11);
+        assert_eq!(proof.leaf, header.state_root);
+        assert!(proof.verify(header.tree_hash_root()));
+        assert_eq!(
+            compute_merkle_proof_for_generalized_index(&header, 11).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn list_element_proof_verifies() {
+        let withdrawals: VariableList<Withdrawal, ssz_types::typenum::U16> = VariableList::from(
+            (0..3)
+                .map(|index| Withdrawal {
+                    index,
+                    validator_index: index * 2,
+                    address: Address::repeat_byte(index as u8),
+                    amount: 32,
+                })
+                .collect::<Vec<_>>(),
+        );
+        let proof = compute_merkle_proof(&withdrawals, &parse_path("[2].amount").unwrap()).unwrap();
+
+        assert_eq!(proof.leaf, 32u64.tree_hash_root());
+        assert!(proof.verify(withdrawals.tree_hash_root()));
+    }
+
+    #[test]
+    fn multiproof_verifies() {
+        let header = header();
+        let multiproof = header
+            .merkle_multiproof(&["slot", "parent_root", "body_root"])
+            .unwrap();
+
+        assert_eq!(multiproof.generalized_indices, vec![8, 10, 12]);
+        assert!(multiproof.verify(header.tree_hash_root()));
+        assert!(!multiproof.verify(B256::ZERO));
+    }
+}
@@ -5,13 +5,7 @@ use anyhow::ensure;
 use ethereum_hashing::hash;
 use tree_hash::TreeHash;
 
-use crate::{
-    fork_choice::helpers::constants::{
-        GENESIS_FORK_VERSION, MAX_SEED_LOOKAHEAD, SHUFFLE_ROUND_COUNT, SLOTS_PER_EPOCH,
-    },
-    fork_data::ForkData,
-    signing_data::SigningData,
-};
+use crate::{eth_spec::EthSpec, fork_data::ForkData, signing_data::SigningData};
 
 pub fn compute_signing_root<SSZObject: TreeHash>(ssz_object: SSZObject, domain: B256) -> B256 {
     SigningData {
@@ -21,13 +15,13 @@ pub fn compute_signing_root<SSZObject: TreeHash>(ssz_object: SSZObject, domain:
     .tree_hash_root()
 }
 
-pub fn compute_shuffled_index(
+pub fn compute_shuffled_index<E: EthSpec>(
     mut index: usize,
     index_count: usize,
     seed: B256,
 ) -> anyhow::Result<usize> {
     ensure!(index < index_count, "Index must be less than index_count");
-    for round in 0..SHUFFLE_ROUND_COUNT {
+    for round in 0..E::SHUFFLE_ROUND_COUNT {
         let seed_with_round = [seed.as_slice(), &round.to_le_bytes()].concat();
         let pivot = bytes_to_int64(&hash(&seed_with_round)[..]) % index_count as u64;
 
@@ -54,7 +48,7 @@ fn bytes_to_int64(slice: &[u8]) -> u64 {
 }
 
 /// Return the committee corresponding to ``indices``, ``seed``, ``index``, and committee ``count``.
-pub fn compute_committee(
+pub fn compute_committee<E: EthSpec>(
     indices: &[u64],
     seed: B256,
     index: u64,
@@ -64,7 +58,7 @@ pub fn compute_committee(
     let end = (indices.len() as u64 * (index + 1)) / count;
     (start..end)
         .map(|i| {
-            let shuffled_index = compute_shuffled_index(i as usize, indices.len(), seed)?;
+            let shuffled_index = compute_shuffled_index::<E>(i as usize, indices.len(), seed)?;
             indices
                 .get(shuffled_index)
                 .copied()
@@ -73,35 +67,46 @@ pub fn compute_committee(
         .collect::<anyhow::Result<Vec<u64>>>()
 }
 
-pub fn is_shuffling_stable(slot: u64) -> bool {
-    slot % SLOTS_PER_EPOCH != 0
+pub fn is_shuffling_stable<E: EthSpec>(slot: u64) -> bool {
+    slot % E::SLOTS_PER_EPOCH != 0
 }
 
 /// Return the epoch number at ``slot``.
-pub fn compute_epoch_at_slot(slot: u64) -> u64 {
-    slot / SLOTS_PER_EPOCH
+pub fn compute_epoch_at_slot<E: EthSpec>(slot: u64) -> u64 {
+    slot / E::SLOTS_PER_EPOCH
 }
 
 /// Return the start slot of ``epoch``.
-pub fn compute_start_slot_at_epoch(epoch: u64) -> u64 {
-    epoch * SLOTS_PER_EPOCH
+pub fn compute_start_slot_at_epoch<E: EthSpec>(epoch: u64) -> u64 {
+    epoch * E::SLOTS_PER_EPOCH
+}
+
+/// Return the sync committee period at ``epoch``.
+pub fn compute_sync_committee_period<E: EthSpec>(epoch: u64) -> u64 {
+    epoch / E::EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// Return the sync committee period at ``slot``.
+pub fn compute_sync_committee_period_at_slot<E: EthSpec>(slot: u64) -> u64 {
+    compute_sync_committee_period::<E>(compute_epoch_at_slot::<E>(slot))
 }
 
 /// Return the epoch during which validator activations and exits initiated in ``epoch`` take
 /// effect.
-pub fn compute_activation_exit_epoch(epoch: u64) -> u64 {
-    epoch + 1 + MAX_SEED_LOOKAHEAD
+pub fn compute_activation_exit_epoch<E: EthSpec>(epoch: u64) -> u64 {
+    epoch + 1 + E::MAX_SEED_LOOKAHEAD
 }
 
-/// Return the domain for the ``domain_type`` and ``fork_version``
+/// Return the domain for the ``domain_type`` and ``fork_version``.
+/// The spec defaults ``fork_version`` to ``GENESIS_FORK_VERSION``, which is part of the
+/// ``ChainSpec``, so callers always pass it explicitly.
 pub fn compute_domain(
     domain_type: B32,
-    fork_version: Option<B32>,
+    fork_version: B32,
     genesis_validators_root: Option<B256>,
 ) -> B256 {
     let fork_data = ForkData {
-        current_version: fork_version.unwrap_or(GENESIS_FORK_VERSION), /* Fork version for
-                                                                        * Ethereum mainnet */
+        current_version: fork_version,
         genesis_validators_root: genesis_validators_root.unwrap_or_default(),
     };
     let fork_data_root = fork_data.compute_fork_data_root();
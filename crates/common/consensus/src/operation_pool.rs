@@ -0,0 +1,739 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use alloy_primitives::B256;
+use anyhow::anyhow;
+use ethereum_hashing::hash;
+use itertools::Itertools;
+use ssz_types::{
+    typenum::{Unsigned, U128, U16, U2},
+    VariableList,
+};
+use tree_hash::TreeHash;
+
+use crate::{
+    attestation::Attestation,
+    attester_slashing::AttesterSlashing,
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    chain_spec::ChainSpec,
+    deneb::beacon_state::BeaconState,
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::{
+        BLS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH, PARTICIPATION_FLAG_WEIGHTS,
+    },
+    misc::compute_epoch_at_slot,
+    predicates::is_slashable_attestation_data,
+    proposer_slashing::ProposerSlashing,
+    voluntary_exit::SignedVoluntaryExit,
+};
+
+/// The operations packed into a block body, bounded by the ``BeaconBlockBody`` list limits.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct BlockOperations {
+    pub proposer_slashings: VariableList<ProposerSlashing, U16>,
+    pub attester_slashings: VariableList<AttesterSlashing, U2>,
+    pub attestations: VariableList<Attestation, U128>,
+    pub voluntary_exits: VariableList<SignedVoluntaryExit, U16>,
+    pub bls_to_execution_changes: VariableList<SignedBLSToExecutionChange, U16>,
+}
+
+/// Holds the operations received over gossip until they are included in a block.
+///
+/// Operations are expected to have passed gossip validation, including signature verification,
+/// before they are inserted. When packing a block they are checked again against the head state so
+/// that operations made stale by the chain are never included.
+#[derive(Debug, Default, Clone)]
+pub struct OperationPool {
+    attestations: HashMap<B256, Vec<Attestation>>,
+    proposer_slashings: HashMap<u64, ProposerSlashing>,
+    attester_slashings: Vec<AttesterSlashing>,
+    voluntary_exits: HashMap<u64, SignedVoluntaryExit>,
+    bls_to_execution_changes: HashMap<u64, SignedBLSToExecutionChange>,
+}
+
+impl OperationPool {
+    /// Add ``attestation`` to the pool, aggregating it with a stored attestation of the same data
+    /// if their participants are disjoint.
+    pub fn insert_attestation(&mut self, attestation: Attestation) -> anyhow::Result<()> {
+        let aggregates = self
+            .attestations
+            .entry(attestation.data.tree_hash_root())
+            .or_default();
+
+        let is_subset = |subset: &Attestation, superset: &Attestation| {
+            subset.aggregation_bits.len() == superset.aggregation_bits.len()
+                && subset
+                    .aggregation_bits
+                    .intersection(&superset.aggregation_bits)
+                    == subset.aggregation_bits
+        };
+        if aggregates
+            .iter()
+            .any(|aggregate| is_subset(&attestation, aggregate))
+        {
+            return Ok(());
+        }
+
+        // Update a copy, so that an attestation failing to aggregate leaves the pool untouched
+        let mut updated_aggregates = aggregates
+            .iter()
+            .filter(|aggregate| !is_subset(aggregate, &attestation))
+            .cloned()
+            .collect::<Vec<_>>();
+        match updated_aggregates.iter_mut().find(|aggregate| {
+            aggregate.aggregation_bits.len() == attestation.aggregation_bits.len()
+                && aggregate.is_disjoint(&attestation)
+        }) {
+            Some(aggregate) => aggregate.aggregate(&attestation)?,
+            None => updated_aggregates.push(attestation),
+        }
+        *aggregates = updated_aggregates;
+        Ok(())
+    }
+
+    pub fn insert_proposer_slashing(&mut self, proposer_slashing: ProposerSlashing) {
+        self.proposer_slashings.insert(
+            proposer_slashing.signed_header_1.message.proposer_index,
+            proposer_slashing,
+        );
+    }
+
+    pub fn insert_attester_slashing(&mut self, attester_slashing: AttesterSlashing) {
+        if !self.attester_slashings.contains(&attester_slashing) {
+            self.attester_slashings.push(attester_slashing);
+        }
+    }
+
+    pub fn insert_voluntary_exit(&mut self, voluntary_exit: SignedVoluntaryExit) {
+        self.voluntary_exits
+            .insert(voluntary_exit.message.validator_index, voluntary_exit);
+    }
+
+    pub fn insert_bls_to_execution_change(
+        &mut self,
+        bls_to_execution_change: SignedBLSToExecutionChange,
+    ) {
+        self.bls_to_execution_changes.insert(
+            bls_to_execution_change.message.validator_index,
+            bls_to_execution_change,
+        );
+    }
+
+    /// Return the attestations to include in a block at ``state.slot``, chosen greedily to
+    /// maximize the proposer reward of the participation flags they newly set.
+    pub fn get_attestations<E: EthSpec>(
+        &self,
+        state: &BeaconState<E>,
+    ) -> anyhow::Result<VariableList<Attestation, U128>> {
+        let base_reward_per_increment = state.get_base_reward_per_increment();
+        let mut candidates = vec![];
+        // Visit the attestations in a fixed order so that ties are packed deterministically
+        for attestation in self
+            .attestations
+            .iter()
+            .sorted_by_key(|(data_root, _)| **data_root)
+            .flat_map(|(_, aggregates)| aggregates)
+        {
+            if !is_attestation_includable(state, attestation) {
+                continue;
+            }
+            let Ok(participation_flag_indices) = state.get_attestation_participation_flag_indices(
+                &attestation.data,
+                state.slot - attestation.data.slot,
+            ) else {
+                continue;
+            };
+            let epoch_participation = if attestation.data.target.epoch == state.get_current_epoch()
+            {
+                &state.current_epoch_participation
+            } else {
+                &state.previous_epoch_participation
+            };
+
+            let mut covering_set = HashMap::new();
+            for index in state.get_attesting_indices(attestation)? {
+                let participation = *epoch_participation
+                    .get(index as usize)
+                    .ok_or_else(|| anyhow!("Index {index} out of bounds in epoch_participation"))?;
+                let base_reward = state.validators[index as usize].effective_balance
+                    / E::EFFECTIVE_BALANCE_INCREMENT
+                    * base_reward_per_increment;
+                let reward = participation_flag_indices
+                    .iter()
+                    .filter(|&&flag_index| !BeaconState::<E>::has_flag(participation, flag_index))
+                    .map(|&flag_index| {
+                        base_reward * PARTICIPATION_FLAG_WEIGHTS[flag_index as usize]
+                    })
+                    .sum::<u64>();
+                if reward > 0 {
+                    covering_set.insert((attestation.data.target.epoch, index), reward);
+                }
+            }
+            candidates.push((attestation, covering_set));
+        }
+
+        Ok(VariableList::from(
+            maximum_cover(candidates, U128::USIZE)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Return the proposer slashings whose proposer is still slashable in ``state``.
+    pub fn get_proposer_slashings<E: EthSpec>(
+        &self,
+        state: &BeaconState<E>,
+    ) -> VariableList<ProposerSlashing, U16> {
+        let current_epoch = state.get_current_epoch();
+        VariableList::from(
+            self.proposer_slashings
+                .iter()
+                .sorted_by_key(|(proposer_index, _)| **proposer_index)
+                .filter(|(proposer_index, _)| {
+                    state
+                        .validators
+                        .get(**proposer_index as usize)
+                        .is_some_and(|validator| validator.is_slashable_validator(current_epoch))
+                })
+                .map(|(_, proposer_slashing)| proposer_slashing.clone())
+                .take(U16::USIZE)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Return the attester slashings that slash the most validators of ``state`` that are not
+    /// already slashed by ``proposer_slashings``.
+    pub fn get_attester_slashings<E: EthSpec>(
+        &self,
+        state: &BeaconState<E>,
+        proposer_slashings: &[ProposerSlashing],
+    ) -> VariableList<AttesterSlashing, U2> {
+        let mut slashed_indices: HashSet<u64> = proposer_slashings
+            .iter()
+            .map(|proposer_slashing| proposer_slashing.signed_header_1.message.proposer_index)
+            .collect();
+        let mut candidates: Vec<(&AttesterSlashing, HashSet<u64>)> = self
+            .attester_slashings
+            .iter()
+            .map(|attester_slashing| {
+                (
+                    attester_slashing,
+                    get_slashable_indices(state, attester_slashing),
+                )
+            })
+            .collect();
+
+        let mut attester_slashings = vec![];
+        while attester_slashings.len() < U2::USIZE {
+            candidates
+                .iter_mut()
+                .for_each(|(_, indices)| indices.retain(|index| !slashed_indices.contains(index)));
+            let Some(best) = candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, indices))| !indices.is_empty())
+                .max_by_key(|(_, (_, indices))| indices.len())
+                .map(|(position, _)| position)
+            else {
+                break;
+            };
+            let (attester_slashing, indices) = candidates.swap_remove(best);
+            slashed_indices.extend(indices);
+            attester_slashings.push(attester_slashing.clone());
+        }
+        VariableList::from(attester_slashings)
+    }
+
+    /// Return the voluntary exits that are valid in ``state`` and whose validator isn't in
+    /// ``slashed_indices``.
+    pub fn get_voluntary_exits<E: EthSpec>(
+        &self,
+        state: &BeaconState<E>,
+        slashed_indices: &HashSet<u64>,
+        spec: &ChainSpec,
+    ) -> VariableList<SignedVoluntaryExit, U16> {
+        let current_epoch = state.get_current_epoch();
+        VariableList::from(
+            self.voluntary_exits
+                .iter()
+                .sorted_by_key(|(validator_index, _)| **validator_index)
+                .filter(|(validator_index, voluntary_exit)| {
+                    !slashed_indices.contains(validator_index)
+                        && state
+                            .validators
+                            .get(**validator_index as usize)
+                            .is_some_and(|validator| {
+                                validator.is_active_validator(current_epoch)
+                                    && validator.exit_epoch == FAR_FUTURE_EPOCH
+                                    && current_epoch >= voluntary_exit.message.epoch
+                                    && current_epoch
+                                        >= validator
+                                            .activation_epoch
+                                            .saturating_add(spec.shard_committee_period)
+                            })
+                })
+                .map(|(_, voluntary_exit)| voluntary_exit.clone())
+                .take(U16::USIZE)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Return the BLS to execution changes whose validator still has BLS withdrawal credentials
+    /// matching ``from_bls_pubkey`` in ``state``.
+    pub fn get_bls_to_execution_changes<E: EthSpec>(
+        &self,
+        state: &BeaconState<E>,
+    ) -> VariableList<SignedBLSToExecutionChange, U16> {
+        VariableList::from(
+            self.bls_to_execution_changes
+                .iter()
+                .sorted_by_key(|(validator_index, _)| **validator_index)
+                .filter(|(validator_index, bls_to_execution_change)| {
+                    is_bls_to_execution_change_valid(
+                        state,
+                        **validator_index,
+                        bls_to_execution_change,
+                    )
+                })
+                .map(|(_, bls_to_execution_change)| bls_to_execution_change.clone())
+                .take(U16::USIZE)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Return all the operations to include in a block at ``state.slot``.
+    pub fn get_block_operations<E: EthSpec>(
+        &self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> anyhow::Result<BlockOperations> {
+        let proposer_slashings = self.get_proposer_slashings(state);
+        let attester_slashings = self.get_attester_slashings(state, &proposer_slashings);
+
+        let mut slashed_indices: HashSet<u64> = proposer_slashings
+            .iter()
+            .map(|proposer_slashing| proposer_slashing.signed_header_1.message.proposer_index)
+            .collect();
+        for attester_slashing in attester_slashings.iter() {
+            slashed_indices.extend(get_slashable_indices(state, attester_slashing));
+        }
+
+        Ok(BlockOperations {
+            attestations: self.get_attestations(state)?,
+            voluntary_exits: self.get_voluntary_exits(state, &slashed_indices, spec),
+            bls_to_execution_changes: self.get_bls_to_execution_changes(state),
+            proposer_slashings,
+            attester_slashings,
+        })
+    }
+
+    /// Remove the attestations that can no longer be included after ``current_epoch`` and the
+    /// operations that are permanently invalid in ``finalized_state``.
+    pub fn prune<E: EthSpec>(&mut self, finalized_state: &BeaconState<E>, current_epoch: u64) {
+        self.attestations.retain(|_, aggregates| {
+            aggregates.retain(|attestation| attestation.data.target.epoch + 1 >= current_epoch);
+            !aggregates.is_empty()
+        });
+
+        let finalized_epoch = finalized_state.get_current_epoch();
+        self.proposer_slashings.retain(|proposer_index, _| {
+            finalized_state
+                .validators
+                .get(*proposer_index as usize)
+                .map_or(true, |validator| {
+                    !validator.slashed && validator.withdrawable_epoch > finalized_epoch
+                })
+        });
+        self.attester_slashings.retain(|attester_slashing| {
+            !get_slashable_indices(finalized_state, attester_slashing).is_empty()
+        });
+        self.voluntary_exits.retain(|validator_index, _| {
+            finalized_state
+                .validators
+                .get(*validator_index as usize)
+                .map_or(true, |validator| validator.exit_epoch == FAR_FUTURE_EPOCH)
+        });
+        self.bls_to_execution_changes
+            .retain(|validator_index, bls_to_execution_change| {
+                finalized_state.validators.len() <= *validator_index as usize
+                    || is_bls_to_execution_change_valid(
+                        finalized_state,
+                        *validator_index,
+                        bls_to_execution_change,
+                    )
+            });
+    }
+}
+
+/// Check the conditions of ``process_attestation`` that don't depend on signatures or
+/// participation.
+fn is_attestation_includable<E: EthSpec>(
+    state: &BeaconState<E>,
+    attestation: &Attestation,
+) -> bool {
+    let data = &attestation.data;
+    (data.target.epoch == state.get_previous_epoch()
+        || data.target.epoch == state.get_current_epoch())
+        && data.target.epoch == compute_epoch_at_slot::<E>(data.slot)
+        && data.slot + E::MIN_ATTESTATION_INCLUSION_DELAY <= state.slot
+        && data.index < state.get_committee_count_per_slot(data.target.epoch)
+        && state
+            .get_beacon_committee(data.slot, data.index)
+            .is_ok_and(|committee| committee.len() == attestation.aggregation_bits.len())
+}
+
+/// Return the indices that ``attester_slashing`` would slash in ``state``.
+fn get_slashable_indices<E: EthSpec>(
+    state: &BeaconState<E>,
+    attester_slashing: &AttesterSlashing,
+) -> HashSet<u64> {
+    let attestation_1 = &attester_slashing.attestation_1;
+    let attestation_2 = &attester_slashing.attestation_2;
+    if !is_slashable_attestation_data(&attestation_1.data, &attestation_2.data) {
+        return HashSet::new();
+    }
+
+    let current_epoch = state.get_current_epoch();
+    let indices_2: HashSet<&u64> = attestation_2.attesting_indices.iter().collect();
+    attestation_1
+        .attesting_indices
+        .iter()
+        .filter(|index| indices_2.contains(index))
+        .filter(|&&index| {
+            state
+                .validators
+                .get(index as usize)
+                .is_some_and(|validator| validator.is_slashable_validator(current_epoch))
+        })
+        .copied()
+        .collect()
+}
+
+fn is_bls_to_execution_change_valid<E: EthSpec>(
+    state: &BeaconState<E>,
+    validator_index: u64,
+    bls_to_execution_change: &SignedBLSToExecutionChange,
+) -> bool {
+    state
+        .validators
+        .get(validator_index as usize)
+        .is_some_and(|validator| {
+            &validator.withdrawal_credentials[..1] == BLS_WITHDRAWAL_PREFIX
+                && validator.withdrawal_credentials[1..]
+                    == hash(bls_to_execution_change.message.from_bls_pubkey.to_bytes())[1..]
+        })
+}
+
+/// Greedily pick at most ``limit`` of ``candidates`` so that the total weight of the elements they
+/// cover is maximized, counting each element once.
+fn maximum_cover<T, K: Eq + Hash + Copy>(
+    mut candidates: Vec<(T, HashMap<K, u64>)>,
+    limit: usize,
+) -> Vec<T> {
+    let mut result = vec![];
+    while result.len() < limit {
+        let Some(best) = candidates
+            .iter()
+            .enumerate()
+            .map(|(position, (_, covering_set))| (position, covering_set.values().sum::<u64>()))
+            .filter(|(_, score)| *score > 0)
+            .max_by_key(|(_, score)| *score)
+            .map(|(position, _)| position)
+        else {
+            break;
+        };
+
+        let (object, covered) = candidates.swap_remove(best);
+        for (_, covering_set) in candidates.iter_mut() {
+            covering_set.retain(|key, _| !covered.contains_key(key));
+        }
+        result.push(object);
+    }
+    result
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_bls::{BLSSignature, PubKey};
+    use ssz_types::BitList;
+
+    use super::*;
+    use crate::{
+        attestation_data::AttestationData,
+        beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader},
+        bls_to_execution_change::BLSToExecutionChange,
+        checkpoint::Checkpoint,
+        eth_spec::MinimalEthSpec,
+        indexed_attestation::IndexedAttestation,
+        test_utils::{beacon_state, validator},
+        validator::Validator,
+        voluntary_exit::VoluntaryExit,
+    };
+
+    type E = MinimalEthSpec;
+
+    fn attestation_data(slot: u64, index: u64, target_root: B256) -> AttestationData {
+        AttestationData {
+            slot,
+            index,
+            beacon_block_root: B256::ZERO,
+            source: Checkpoint {
+                epoch: 0,
+                root: B256::ZERO,
+            },
+            target: Checkpoint {
+                epoch: compute_epoch_at_slot::<E>(slot),
+                root: target_root,
+            },
+        }
+    }
+
+    /// Return an attestation to ``data`` by the members at ``participants`` of a committee of 4.
+    fn attestation(data: AttestationData, participants: &[usize]) -> Attestation {
+        let mut aggregation_bits = BitList::with_capacity(4).unwrap();
+        for &participant in participants {
+            aggregation_bits.set(participant, true).unwrap();
+        }
+        Attestation {
+            aggregation_bits,
+            data,
+            signature: BLSSignature::infinity(),
+        }
+    }
+
+    fn attester_slashing(attesting_indices: Vec<u64>) -> AttesterSlashing {
+        let indexed_attestation = |target_root| IndexedAttestation {
+            attesting_indices: VariableList::from(attesting_indices.clone()),
+            data: attestation_data(16, 0, target_root),
+            signature: BLSSignature::infinity(),
+        };
+        AttesterSlashing {
+            attestation_1: indexed_attestation(B256::ZERO),
+            attestation_2: indexed_attestation(B256::repeat_byte(1)),
+        }
+    }
+
+    fn proposer_slashing(proposer_index: u64) -> ProposerSlashing {
+        let signed_header = |state_root| SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                proposer_index,
+                state_root,
+                ..Default::default()
+            },
+            signature: BLSSignature::infinity(),
+        };
+        ProposerSlashing {
+            signed_header_1: signed_header(B256::ZERO),
+            signed_header_2: signed_header(B256::repeat_byte(1)),
+        }
+    }
+
+    /// Return a state at the second slot of epoch 2 with 64 validators in committees of 4.
+    fn state() -> BeaconState<E> {
+        beacon_state::<E>(17, vec![validator::<E>(PubKey::default(), 0); 64])
+    }
+
+    #[test]
+    fn test_insert_attestation() {
+        let mut pool = OperationPool::default();
+        let data = attestation_data(16, 0, B256::ZERO);
+        let aggregates = |pool: &OperationPool| pool.attestations[&data.tree_hash_root()].clone();
+
+        // Disjoint attestations are aggregated
+        pool.insert_attestation(attestation(data.clone(), &[0]))
+            .unwrap();
+        pool.insert_attestation(attestation(data.clone(), &[1]))
+            .unwrap();
+        assert_eq!(aggregates(&pool), vec![attestation(data.clone(), &[0, 1])]);
+
+        // Subsets of a stored aggregate are dropped
+        pool.insert_attestation(attestation(data.clone(), &[0]))
+            .unwrap();
+        assert_eq!(aggregates(&pool), vec![attestation(data.clone(), &[0, 1])]);
+
+        // Supersets replace the aggregates they contain
+        pool.insert_attestation(attestation(data.clone(), &[0, 1, 2]))
+            .unwrap();
+        assert_eq!(
+            aggregates(&pool),
+            vec![attestation(data.clone(), &[0, 1, 2])]
+        );
+
+        // Overlapping attestations are stored next to each other
+        pool.insert_attestation(attestation(data.clone(), &[2, 3]))
+            .unwrap();
+        pool.insert_attestation(attestation(data.clone(), &[3]))
+            .unwrap();
+        assert_eq!(
+            aggregates(&pool),
+            vec![
+                attestation(data.clone(), &[0, 1, 2]),
+                attestation(data.clone(), &[2, 3])
+            ]
+        );
+        assert_eq!(pool.attestations.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_undecodable_attestation() {
+        let mut pool = OperationPool::default();
+        let data = attestation_data(16, 0, B256::ZERO);
+        pool.insert_attestation(attestation(data.clone(), &[0, 1]))
+            .unwrap();
+        pool.insert_attestation(attestation(data.clone(), &[1, 2]))
+            .unwrap();
+        let expected = pool.attestations[&data.tree_hash_root()].clone();
+
+        let mut undecodable = attestation(data.clone(), &[3]);
+        undecodable.signature = BLSSignature::default();
+        assert_eq!(
+            pool.insert_attestation(undecodable)
+                .unwrap_err()
+                .to_string(),
+            "Failed to aggregate signatures: InvalidSignature"
+        );
+        assert_eq!(pool.attestations[&data.tree_hash_root()], expected);
+    }
+
+    #[test]
+    fn test_get_attestations() {
+        let mut state = state();
+        let fresh = attestation(attestation_data(16, 0, B256::ZERO), &[2]);
+        let stale_target = attestation(attestation_data(16, 1, B256::repeat_byte(1)), &[0, 1]);
+        let stale_epoch = attestation(attestation_data(7, 0, B256::ZERO), &[0, 1, 2, 3]);
+        let mut pool = OperationPool::default();
+        for attestation in [&stale_target, &fresh, &stale_epoch] {
+            pool.insert_attestation(attestation.clone()).unwrap();
+        }
+
+        // A single vote for the right target and head outweighs two votes for a stale target, and
+        // attestations of epochs before the previous one are never included
+        assert_eq!(
+            pool.get_attestations(&state).unwrap().to_vec(),
+            vec![fresh.clone(), stale_target.clone()]
+        );
+
+        // Votes that don't set new participation flags earn nothing
+        let attester_index = state.get_attesting_indices(&fresh).unwrap()[0];
+        state.current_epoch_participation[attester_index as usize] = 0b111;
+        assert_eq!(
+            pool.get_attestations(&state).unwrap().to_vec(),
+            vec![stale_target]
+        );
+    }
+
+    #[test]
+    fn test_get_attester_slashings() {
+        let state = state();
+        let mut pool = OperationPool::default();
+        let slashing_1 = attester_slashing(vec![1, 2, 3]);
+        let slashing_2 = attester_slashing(vec![3, 4]);
+        pool.insert_attester_slashing(slashing_1.clone());
+        pool.insert_attester_slashing(slashing_2.clone());
+        pool.insert_attester_slashing(slashing_1.clone());
+        assert_eq!(pool.attester_slashings.len(), 2);
+
+        assert_eq!(
+            pool.get_attester_slashings(&state, &[]).to_vec(),
+            vec![slashing_1.clone(), slashing_2.clone()]
+        );
+
+        // Validators slashed by a proposer slashing don't count
+        assert_eq!(
+            pool.get_attester_slashings(&state, &[proposer_slashing(1), proposer_slashing(2)])
+                .to_vec(),
+            vec![slashing_2]
+        );
+        assert!(pool
+            .get_attester_slashings(&state, &[1, 2, 3, 4].map(proposer_slashing))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut finalized_state = state();
+        finalized_state.validators[1].slashed = true;
+        finalized_state.validators[2].exit_epoch = 4;
+        let from_bls_pubkey = PubKey::default();
+        let mut withdrawal_credentials = B256::from_slice(&hash(from_bls_pubkey.to_bytes()));
+        withdrawal_credentials[..1].copy_from_slice(BLS_WITHDRAWAL_PREFIX);
+        finalized_state.validators[3] = Validator {
+            withdrawal_credentials,
+            ..finalized_state.validators[3].clone()
+        };
+
+        let previous_epoch_attestation = attestation(attestation_data(8, 0, B256::ZERO), &[0]);
+        let mut pool = OperationPool::default();
+        pool.insert_attestation(attestation(attestation_data(0, 0, B256::ZERO), &[0]))
+            .unwrap();
+        pool.insert_attestation(previous_epoch_attestation.clone())
+            .unwrap();
+        for proposer_index in [0, 1, 100] {
+            pool.insert_proposer_slashing(proposer_slashing(proposer_index));
+        }
+        pool.insert_attester_slashing(attester_slashing(vec![0, 1]));
+        pool.insert_attester_slashing(attester_slashing(vec![1]));
+        for validator_index in [0, 2] {
+            pool.insert_voluntary_exit(SignedVoluntaryExit {
+                message: VoluntaryExit {
+                    epoch: 0,
+                    validator_index,
+                },
+                signature: BLSSignature::infinity(),
+            });
+        }
+        for validator_index in [3, 4] {
+            pool.insert_bls_to_execution_change(SignedBLSToExecutionChange {
+                message: BLSToExecutionChange {
+                    validator_index,
+                    from_bls_pubkey: from_bls_pubkey.clone(),
+                    to_execution_address: Default::default(),
+                },
+                signature: BLSSignature::infinity(),
+            });
+        }
+
+        pool.prune(&finalized_state, 2);
+        assert_eq!(
+            pool.attestations
+                .into_values()
+                .flatten()
+                .collect::<Vec<_>>(),
+            vec![previous_epoch_attestation]
+        );
+        assert_eq!(
+            pool.proposer_slashings
+                .into_keys()
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec![0, 100]
+        );
+        assert_eq!(pool.attester_slashings, vec![attester_slashing(vec![0, 1])]);
+        assert_eq!(
+            pool.voluntary_exits.into_keys().collect::<Vec<_>>(),
+            vec![0]
+        );
+        assert_eq!(
+            pool.bls_to_execution_changes
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn test_maximum_cover() {
+        let candidates = vec![
+            ("a", HashMap::from([(0, 10), (1, 10), (2, 10)])),
+            ("b", HashMap::from([(2, 10), (3, 10)])),
+            ("c", HashMap::from([(0, 10), (1, 10)])),
+            ("d", HashMap::from([(4, 5)])),
+        ];
+        assert_eq!(maximum_cover(candidates.clone(), 2), vec!["a", "b"]);
+        assert_eq!(maximum_cover(candidates, 4), vec!["a", "b", "d"]);
+    }
+}
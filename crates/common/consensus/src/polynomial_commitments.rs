@@ -0,0 +1,126 @@
+use std::{path::Path, sync::OnceLock};
+
+use anyhow::{anyhow, ensure};
+use kzg::{
+    eip_4844::{
+        blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust,
+        verify_blob_kzg_proof_batch_rust, verify_blob_kzg_proof_rust,
+    },
+    G1,
+};
+use rust_kzg_blst::{
+    eip_4844::load_trusted_setup_filename_rust,
+    types::{fr::FsFr, g1::FsG1, kzg_settings::FsKZGSettings},
+};
+
+use crate::{
+    deneb::blob_sidecar::Blob, eth_spec::EthSpec, kzg_commitment::KZGCommitment,
+    kzg_proof::KZGProof,
+};
+
+/// Download location of the mainnet trusted setup in the format expected by
+/// [`load_trusted_setup`].
+pub const MAINNET_TRUSTED_SETUP_URL: &str =
+    "https://raw.githubusercontent.com/ethereum/c-kzg-4844/v2.1.1/src/trusted_setup.txt";
+
+static KZG_SETTINGS: OnceLock<FsKZGSettings> = OnceLock::new();
+
+/// Load the KZG trusted setup from ``path``.
+///
+/// This must be called before any blob is verified. Once a trusted setup is loaded, later calls
+/// are no-ops.
+pub fn load_trusted_setup(path: &Path) -> anyhow::Result<()> {
+    if KZG_SETTINGS.get().is_some() {
+        return Ok(());
+    }
+    let path = path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid trusted setup path {path:?}"))?;
+    let kzg_settings = load_trusted_setup_filename_rust(path)
+        .map_err(|err| anyhow!("Failed to load trusted setup: {err}"))?;
+    // Another thread may have loaded the trusted setup in the meantime
+    let _ = KZG_SETTINGS.set(kzg_settings);
+    Ok(())
+}
+
+pub(crate) fn kzg_settings() -> anyhow::Result<&'static FsKZGSettings> {
+    KZG_SETTINGS
+        .get()
+        .ok_or_else(|| anyhow!("Trusted setup hasn't been loaded"))
+}
+
+pub(crate) fn blob_to_field_elements<E: EthSpec>(blob: &Blob<E>) -> anyhow::Result<Vec<FsFr>> {
+    bytes_to_blob(blob).map_err(|err| anyhow!("Invalid blob: {err}"))
+}
+
+pub(crate) fn bytes_to_g1(bytes: &[u8]) -> anyhow::Result<FsG1> {
+    FsG1::from_bytes(bytes).map_err(|err| anyhow!("Invalid G1 point: {err}"))
+}
+
+/// Return the KZG commitment to ``blob``.
+pub fn blob_to_kzg_commitment<E: EthSpec>(blob: &Blob<E>) -> anyhow::Result<KZGCommitment> {
+    let commitment =
+        blob_to_kzg_commitment_rust(&blob_to_field_elements::<E>(blob)?, kzg_settings()?)
+            .map_err(|err| anyhow!("Failed to compute KZG commitment: {err}"))?;
+    Ok(KZGCommitment(commitment.to_bytes()))
+}
+
+/// Given a blob, return the KZG proof that is used to verify it against the commitment.
+/// This method does not verify that the commitment is correct with respect to ``blob``.
+pub fn compute_blob_kzg_proof<E: EthSpec>(
+    blob: &Blob<E>,
+    commitment: &KZGCommitment,
+) -> anyhow::Result<KZGProof> {
+    let proof = compute_blob_kzg_proof_rust(
+        &blob_to_field_elements::<E>(blob)?,
+        &bytes_to_g1(&commitment.0)?,
+        kzg_settings()?,
+    )
+    .map_err(|err| anyhow!("Failed to compute blob KZG proof: {err}"))?;
+    Ok(KZGProof(proof.to_bytes()))
+}
+
+/// Given a blob and a KZG proof, verify that the blob data corresponds to the provided
+/// commitment.
+pub fn verify_blob_kzg_proof<E: EthSpec>(
+    blob: &Blob<E>,
+    commitment: &KZGCommitment,
+    proof: &KZGProof,
+) -> anyhow::Result<bool> {
+    verify_blob_kzg_proof_rust(
+        &blob_to_field_elements::<E>(blob)?,
+        &bytes_to_g1(&commitment.0)?,
+        &bytes_to_g1(&proof.0)?,
+        kzg_settings()?,
+    )
+    .map_err(|err| anyhow!("Failed to verify blob KZG proof: {err}"))
+}
+
+/// Given a list of blobs and blob KZG proofs, verify that they correspond to the provided
+/// commitments.
+pub fn verify_blob_kzg_proof_batch<E: EthSpec>(
+    blobs: &[Blob<E>],
+    commitments: &[KZGCommitment],
+    proofs: &[KZGProof],
+) -> anyhow::Result<bool> {
+    ensure!(
+        blobs.len() == commitments.len() && blobs.len() == proofs.len(),
+        "Blobs, commitments and proofs must have the same length"
+    );
+    verify_blob_kzg_proof_batch_rust(
+        &blobs
+            .iter()
+            .map(blob_to_field_elements::<E>)
+            .collect::<anyhow::Result<Vec<_>>>()?,
+        &commitments
+            .iter()
+            .map(|commitment| bytes_to_g1(&commitment.0))
+            .collect::<anyhow::Result<Vec<_>>>()?,
+        &proofs
+            .iter()
+            .map(|proof| bytes_to_g1(&proof.0))
+            .collect::<anyhow::Result<Vec<_>>>()?,
+        kzg_settings()?,
+    )
+    .map_err(|err| anyhow!("Failed to verify blob KZG proofs: {err}"))
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use ream_bls::{DecompressedPubKey, PubKey};
+
+use crate::validator::Validator;
+
+/// Decompressed and validated public keys of the validators of a ``BeaconState``, indexed by
+/// validator index, so that each key is decompressed and subgroup checked once rather than once per
+/// signature.
+///
+/// Validators are never removed from the registry and their public keys never change, so entries
+/// stay valid as the state advances and only new validators have to be added.
+#[derive(Clone, Default)]
+pub struct PubkeyCache {
+    /// ``None`` for the public keys failing ``KeyValidate``.
+    pubkeys: Vec<Option<DecompressedPubKey>>,
+}
+
+impl PubkeyCache {
+    /// Decompress the public keys of the validators added since the last update. A public key
+    /// failing ``KeyValidate`` is left out of the cache, so the error surfaces with the signature
+    /// check using it, without holding back the validators after it.
+    pub fn update(&mut self, validators: &[Validator]) {
+        for validator in validators.iter().skip(self.pubkeys.len()) {
+            self.pubkeys
+                .push(DecompressedPubKey::try_from(&validator.pubkey).ok());
+        }
+    }
+
+    /// Return the decompressed public key of the validator at ``index``, if it is cached.
+    pub fn get(&self, index: usize) -> Option<&DecompressedPubKey> {
+        self.pubkeys.get(index).and_then(Option::as_ref)
+    }
+
+    /// Return the decompressed public key of the validator at ``index`` if it is cached and
+    /// matches ``pubkey``.
+    pub fn get_matching(&self, index: usize, pubkey: &PubKey) -> Option<&DecompressedPubKey> {
+        self.get(index)
+            .filter(|decompressed_pubkey| decompressed_pubkey.pubkey() == pubkey)
+    }
+
+    /// The number of validators the cache was updated with, including those with an invalid
+    /// public key.
+    pub fn len(&self) -> usize {
+        self.pubkeys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pubkeys.is_empty()
+    }
+}
+
+impl fmt::Debug for PubkeyCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PubkeyCache")
+            .field("len", &self.pubkeys.len())
+            .finish()
+    }
+}
+
+/// The cache is derived from the validator registry, so it never makes two states differ.
+impl PartialEq for PubkeyCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_bls::{traits::Signable, SecretKey};
+
+    use super::*;
+    use crate::{eth_spec::MinimalEthSpec, test_utils::validator};
+
+    fn pubkey(byte: u8) -> PubKey {
+        SecretKey::from_bytes(&[byte; 32])
+            .unwrap()
+            .public_key()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_update() {
+        let mut validators = vec![
+            validator::<MinimalEthSpec>(pubkey(1), 0),
+            validator::<MinimalEthSpec>(pubkey(2), 0),
+        ];
+        let mut cache = PubkeyCache::default();
+        cache.update(&validators);
+        assert_eq!(cache.len(), 2);
+
+        validators.push(validator::<MinimalEthSpec>(pubkey(3), 0));
+        cache.update(&validators);
+        assert_eq!(cache.len(), 3);
+        for (index, validator) in validators.iter().enumerate() {
+            assert_eq!(cache.get(index).unwrap().pubkey(), &validator.pubkey);
+        }
+        assert!(cache.get(3).is_none());
+    }
+
+    #[test]
+    fn test_update_skips_invalid_pubkeys() {
+        let validators = vec![
+            validator::<MinimalEthSpec>(pubkey(1), 0),
+            validator::<MinimalEthSpec>(PubKey::default(), 0),
+            validator::<MinimalEthSpec>(pubkey(3), 0),
+        ];
+        let mut cache = PubkeyCache::default();
+        cache.update(&validators);
+
+        assert_eq!(cache.len(), 3);
+        assert!(cache.get(0).is_some());
+        assert!(cache.get(1).is_none());
+        assert_eq!(cache.get(2).unwrap().pubkey(), &pubkey(3));
+    }
+
+    #[test]
+    fn test_get_matching() {
+        let mut cache = PubkeyCache::default();
+        cache.update(&[validator::<MinimalEthSpec>(pubkey(1), 0)]);
+
+        assert!(cache.get_matching(0, &pubkey(1)).is_some());
+        assert!(cache.get_matching(0, &pubkey(2)).is_none());
+        assert!(cache.get_matching(1, &pubkey(1)).is_none());
+    }
+}
@@ -1,11 +1,20 @@
 use ream_bls::BLSSignature;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{typenum, BitVector};
+use ssz_types::BitVector;
 use tree_hash_derive::TreeHash;
 
+use crate::eth_spec::EthSpec;
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
-pub struct SyncAggregate {
-    pub sync_committee_bits: BitVector<typenum::U512>,
+#[serde(bound = "E: EthSpec")]
+pub struct SyncAggregate<E: EthSpec> {
+    pub sync_committee_bits: BitVector<E::SyncCommitteeSize>,
     pub sync_committee_signature: BLSSignature,
 }
+
+impl<E: EthSpec> SyncAggregate<E> {
+    pub fn num_active_participants(&self) -> u64 {
+        self.sync_committee_bits.num_set_bits() as u64
+    }
+}
@@ -1,11 +1,14 @@
 use ream_bls::PubKey;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{typenum::U512, FixedVector};
+use ssz_types::FixedVector;
 use tree_hash_derive::TreeHash;
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
-pub struct SyncCommittee {
-    pub pubkeys: FixedVector<PubKey, U512>,
+use crate::eth_spec::EthSpec;
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct SyncCommittee<E: EthSpec> {
+    pub pubkeys: FixedVector<PubKey, E::SyncCommitteeSize>,
     pub aggregate_pubkey: PubKey,
 }
@@ -0,0 +1,301 @@
+use alloy_primitives::B256;
+use anyhow::ensure;
+use ethereum_hashing::hash;
+use ream_bls::{traits::Aggregatable, AggregateSignature, BLSSignature};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::BitVector;
+use tree_hash_derive::TreeHash;
+
+use crate::{
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::{
+        SYNC_COMMITTEE_SUBNET_COUNT, TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE,
+    },
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SyncCommitteeMessage {
+    pub slot: u64,
+    pub beacon_block_root: B256,
+    pub validator_index: u64,
+    pub signature: BLSSignature,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct SyncCommitteeContribution<E: EthSpec> {
+    pub slot: u64,
+    pub beacon_block_root: B256,
+    pub subcommittee_index: u64,
+    pub aggregation_bits: BitVector<E::SyncSubcommitteeSize>,
+    pub signature: BLSSignature,
+}
+
+impl<E: EthSpec> SyncCommitteeContribution<E> {
+    /// Create an empty contribution of ``subcommittee_index`` for ``beacon_block_root`` at
+    /// ``slot``.
+    pub fn new(slot: u64, beacon_block_root: B256, subcommittee_index: u64) -> Self {
+        Self {
+            slot,
+            beacon_block_root,
+            subcommittee_index,
+            aggregation_bits: BitVector::new(),
+            signature: BLSSignature::infinity(),
+        }
+    }
+
+    pub fn num_participants(&self) -> u64 {
+        self.aggregation_bits.num_set_bits() as u64
+    }
+
+    /// Add ``message`` from the member at ``index_in_subcommittee`` to the contribution.
+    pub fn add_message(
+        &mut self,
+        message: &SyncCommitteeMessage,
+        index_in_subcommittee: u64,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            message.slot == self.slot && message.beacon_block_root == self.beacon_block_root,
+            "Sync committee message is for a different slot or block root"
+        );
+        ensure!(
+            !self
+                .aggregation_bits
+                .get(index_in_subcommittee as usize)
+                .map_err(|err| anyhow::anyhow!("Invalid subcommittee index: {err:?}"))?,
+            "Sync committee member {index_in_subcommittee} is already in the contribution"
+        );
+
+        let signature = AggregateSignature::aggregate(&[&self.signature, &message.signature])
+            .map_err(|err| anyhow::anyhow!("Failed to aggregate signatures: {err:?}"))?
+            .to_signature();
+        self.aggregation_bits
+            .set(index_in_subcommittee as usize, true)
+            .map_err(|err| anyhow::anyhow!("Invalid subcommittee index: {err:?}"))?;
+        self.signature = signature;
+        Ok(())
+    }
+
+    /// Merge ``other`` into ``self`` if they are for the same subcommittee and block and have
+    /// disjoint participants.
+    pub fn aggregate(&mut self, other: &SyncCommitteeContribution<E>) -> anyhow::Result<()> {
+        ensure!(
+            self.slot == other.slot
+                && self.beacon_block_root == other.beacon_block_root
+                && self.subcommittee_index == other.subcommittee_index,
+            "Can't aggregate contributions of different subcommittees or block roots"
+        );
+        ensure!(
+            self.aggregation_bits
+                .intersection(&other.aggregation_bits)
+                .is_zero(),
+            "Can't aggregate contributions with overlapping participants"
+        );
+
+        let signature = AggregateSignature::aggregate(&[&self.signature, &other.signature])
+            .map_err(|err| anyhow::anyhow!("Failed to aggregate signatures: {err:?}"))?
+            .to_signature();
+        self.aggregation_bits = self.aggregation_bits.union(&other.aggregation_bits);
+        self.signature = signature;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct ContributionAndProof<E: EthSpec> {
+    pub aggregator_index: u64,
+    pub contribution: SyncCommitteeContribution<E>,
+    pub selection_proof: BLSSignature,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[serde(bound = "E: EthSpec")]
+pub struct SignedContributionAndProof<E: EthSpec> {
+    pub message: ContributionAndProof<E>,
+    pub signature: BLSSignature,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SyncAggregatorSelectionData {
+    pub slot: u64,
+    pub subcommittee_index: u64,
+}
+
+/// Check if the sync committee member with selection proof ``signature`` is an aggregator of its
+/// subcommittee.
+pub fn is_sync_committee_aggregator<E: EthSpec>(signature: &BLSSignature) -> bool {
+    let modulo = u64::max(
+        1,
+        E::SYNC_COMMITTEE_SIZE
+            / SYNC_COMMITTEE_SUBNET_COUNT
+            / TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE,
+    );
+    let signature_hash = hash(signature.to_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&signature_hash[0..8]);
+    u64::from_le_bytes(bytes) % modulo == 0
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_bls::{traits::Signable, SecretKey};
+
+    use super::*;
+    use crate::eth_spec::{MainnetEthSpec, MinimalEthSpec};
+
+    fn signature(byte: u8) -> BLSSignature {
+        SecretKey::from_bytes(&[byte; 32])
+            .unwrap()
+            .sign(B256::ZERO.as_slice())
+            .unwrap()
+    }
+
+    fn message(slot: u64, beacon_block_root: B256, byte: u8) -> SyncCommitteeMessage {
+        SyncCommitteeMessage {
+            slot,
+            beacon_block_root,
+            validator_index: byte as u64,
+            signature: signature(byte),
+        }
+    }
+
+    #[test]
+    fn test_add_message() {
+        let mut contribution = SyncCommitteeContribution::<MinimalEthSpec>::new(1, B256::ZERO, 0);
+        contribution
+            .add_message(&message(1, B256::ZERO, 1), 0)
+            .unwrap();
+        contribution
+            .add_message(&message(1, B256::ZERO, 2), 5)
+            .unwrap();
+
+        assert_eq!(contribution.num_participants(), 2);
+        assert!(contribution.aggregation_bits.get(0).unwrap());
+        assert!(contribution.aggregation_bits.get(5).unwrap());
+        assert_eq!(
+            contribution.signature,
+            AggregateSignature::aggregate(&[&signature(1), &signature(2)])
+                .unwrap()
+                .to_signature()
+        );
+    }
+
+    #[test]
+    fn test_add_message_rejections() {
+        let mut contribution = SyncCommitteeContribution::<MinimalEthSpec>::new(1, B256::ZERO, 0);
+        contribution
+            .add_message(&message(1, B256::ZERO, 1), 0)
+            .unwrap();
+        let expected = contribution.clone();
+
+        for other_message in [
+            message(2, B256::ZERO, 2),
+            message(1, B256::repeat_byte(1), 2),
+        ] {
+            assert_eq!(
+                contribution
+                    .add_message(&other_message, 1)
+                    .unwrap_err()
+                    .to_string(),
+                "Sync committee message is for a different slot or block root"
+            );
+        }
+        assert_eq!(
+            contribution
+                .add_message(&message(1, B256::ZERO, 2), 0)
+                .unwrap_err()
+                .to_string(),
+            "Sync committee member 0 is already in the contribution"
+        );
+        assert_eq!(
+            contribution
+                .add_message(&message(1, B256::ZERO, 2), 8)
+                .unwrap_err()
+                .to_string(),
+            "Invalid subcommittee index: OutOfBounds { i: 8, len: 8 }"
+        );
+
+        let mut undecodable = message(1, B256::ZERO, 2);
+        undecodable.signature = BLSSignature::default();
+        assert_eq!(
+            contribution
+                .add_message(&undecodable, 1)
+                .unwrap_err()
+                .to_string(),
+            "Failed to aggregate signatures: InvalidSignature"
+        );
+
+        // Rejected messages leave the contribution untouched
+        assert_eq!(contribution, expected);
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut contribution = SyncCommitteeContribution::<MinimalEthSpec>::new(1, B256::ZERO, 0);
+        contribution
+            .add_message(&message(1, B256::ZERO, 1), 0)
+            .unwrap();
+        let mut other = SyncCommitteeContribution::<MinimalEthSpec>::new(1, B256::ZERO, 0);
+        other.add_message(&message(1, B256::ZERO, 2), 3).unwrap();
+
+        let mut aggregate = contribution.clone();
+        aggregate.aggregate(&other).unwrap();
+        let mut expected = contribution.clone();
+        expected.add_message(&message(1, B256::ZERO, 2), 3).unwrap();
+        assert_eq!(aggregate, expected);
+
+        assert_eq!(
+            aggregate.aggregate(&other).unwrap_err().to_string(),
+            "Can't aggregate contributions with overlapping participants"
+        );
+        assert_eq!(
+            contribution
+                .aggregate(&SyncCommitteeContribution::new(1, B256::ZERO, 1))
+                .unwrap_err()
+                .to_string(),
+            "Can't aggregate contributions of different subcommittees or block roots"
+        );
+
+        let mut undecodable = other.clone();
+        undecodable.signature = BLSSignature::default();
+        let expected = contribution.clone();
+        assert_eq!(
+            contribution
+                .aggregate(&undecodable)
+                .unwrap_err()
+                .to_string(),
+            "Failed to aggregate signatures: InvalidSignature"
+        );
+        assert_eq!(contribution, expected);
+    }
+
+    #[test]
+    fn test_is_sync_committee_aggregator() {
+        // Minimal subcommittees of 8 are smaller than
+        // ``TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE``, so every member is an aggregator
+        for byte in 1..=16 {
+            assert!(is_sync_committee_aggregator::<MinimalEthSpec>(&signature(
+                byte
+            )));
+        }
+
+        // Mainnet subcommittees of 128 select the members whose selection proof hashes to a
+        // multiple of 8
+        let mut aggregators = 0;
+        for byte in 1..=16 {
+            let signature = signature(byte);
+            let signature_hash = hash(signature.to_bytes());
+            let is_aggregator =
+                u64::from_le_bytes(signature_hash[0..8].try_into().unwrap()) % 8 == 0;
+            assert_eq!(
+                is_sync_committee_aggregator::<MainnetEthSpec>(&signature),
+                is_aggregator
+            );
+            aggregators += is_aggregator as usize;
+        }
+        assert!(aggregators < 16);
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, HashMap};
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use ream_bls::{traits::Aggregatable, AggregateSignature, BLSSignature};
+use ssz_types::BitVector;
+
+use crate::{
+    eth_spec::EthSpec, fork_choice::helpers::constants::SYNC_COMMITTEE_SUBNET_COUNT,
+    sync_aggregate::SyncAggregate, sync_committee_contribution::SyncCommitteeContribution,
+};
+
+/// Collects the sync committee contributions received from aggregators and packs the best of them
+/// into the ``SyncAggregate`` of the next block.
+#[derive(Debug, Clone)]
+pub struct SyncCommitteeContributionPool<E: EthSpec> {
+    contributions: HashMap<(u64, B256), BTreeMap<u64, SyncCommitteeContribution<E>>>,
+}
+
+impl<E: EthSpec> Default for SyncCommitteeContributionPool<E> {
+    fn default() -> Self {
+        Self {
+            contributions: HashMap::new(),
+        }
+    }
+}
+
+impl<E: EthSpec> SyncCommitteeContributionPool<E> {
+    /// Add ``contribution`` to the pool, merging it with the one already held for its
+    /// subcommittee when their participants are disjoint, or otherwise keeping whichever has more
+    /// participants.
+    pub fn insert_contribution(
+        &mut self,
+        contribution: SyncCommitteeContribution<E>,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            contribution.subcommittee_index < SYNC_COMMITTEE_SUBNET_COUNT,
+            "Invalid sync subcommittee index {}",
+            contribution.subcommittee_index
+        );
+
+        let subcommittee_contributions = self
+            .contributions
+            .entry((contribution.slot, contribution.beacon_block_root))
+            .or_default();
+        match subcommittee_contributions.get_mut(&contribution.subcommittee_index) {
+            Some(best) => {
+                if best
+                    .aggregation_bits
+                    .intersection(&contribution.aggregation_bits)
+                    .is_zero()
+                {
+                    best.aggregate(&contribution)?;
+                } else if contribution.num_participants() > best.num_participants() {
+                    *best = contribution;
+                }
+            }
+            None => {
+                subcommittee_contributions.insert(contribution.subcommittee_index, contribution);
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the best ``SyncAggregate`` for ``beacon_block_root`` at ``slot``, which is included
+    /// in the block at ``slot + 1``.
+    pub fn get_sync_aggregate(
+        &self,
+        slot: u64,
+        beacon_block_root: B256,
+    ) -> anyhow::Result<SyncAggregate<E>> {
+        let Some(subcommittee_contributions) = self.contributions.get(&(slot, beacon_block_root))
+        else {
+            return Ok(SyncAggregate {
+                sync_committee_bits: BitVector::new(),
+                sync_committee_signature: BLSSignature::infinity(),
+            });
+        };
+
+        let mut sync_committee_bits = BitVector::new();
+        for contribution in subcommittee_contributions.values() {
+            let offset = contribution.subcommittee_index * E::SYNC_SUBCOMMITTEE_SIZE;
+            for (index, bit) in contribution.aggregation_bits.iter().enumerate() {
+                if bit {
+                    sync_committee_bits
+                        .set(offset as usize + index, true)
+                        .map_err(|err| anyhow!("Invalid sync committee index: {err:?}"))?;
+                }
+            }
+        }
+        let sync_committee_signature = AggregateSignature::aggregate(
+            &subcommittee_contributions
+                .values()
+                .map(|contribution| &contribution.signature)
+                .collect::<Vec<_>>(),
+        )
+        .map_err(|err| anyhow!("Failed to aggregate signatures: {err:?}"))?
+        .to_signature();
+
+        Ok(SyncAggregate {
+            sync_committee_bits,
+            sync_committee_signature,
+        })
+    }
+
+    /// Remove the contributions for slots before ``slot``.
+    pub fn prune(&mut self, slot: u64) {
+        self.contributions
+            .retain(|(contribution_slot, _), _| *contribution_slot >= slot);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::eth_spec::MinimalEthSpec;
+
+    fn contribution(
+        subcommittee_index: u64,
+        participants: &[usize],
+    ) -> SyncCommitteeContribution<MinimalEthSpec> {
+        let mut contribution = SyncCommitteeContribution::new(1, B256::ZERO, subcommittee_index);
+        for index in participants {
+            contribution.aggregation_bits.set(*index, true).unwrap();
+        }
+        contribution
+    }
+
+    #[test]
+    fn test_get_sync_aggregate() {
+        let mut pool = SyncCommitteeContributionPool::<MinimalEthSpec>::default();
+        pool.insert_contribution(contribution(0, &[0, 1])).unwrap();
+        pool.insert_contribution(contribution(0, &[2])).unwrap();
+        pool.insert_contribution(contribution(0, &[1, 2, 3]))
+            .unwrap();
+        pool.insert_contribution(contribution(3, &[7])).unwrap();
+
+        let sync_aggregate = pool.get_sync_aggregate(1, B256::ZERO).unwrap();
+        let participants = sync_aggregate
+            .sync_committee_bits
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bit)| bit.then_some(index))
+            .collect::<Vec<_>>();
+        assert_eq!(participants, vec![0, 1, 2, 31]);
+
+        pool.prune(2);
+        assert_eq!(
+            pool.get_sync_aggregate(1, B256::ZERO)
+                .unwrap()
+                .num_active_participants(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_insert_undecodable_contribution() {
+        let mut pool = SyncCommitteeContributionPool::<MinimalEthSpec>::default();
+        pool.insert_contribution(contribution(0, &[0, 1])).unwrap();
+
+        let mut undecodable = contribution(0, &[2]);
+        undecodable.signature = BLSSignature::default();
+        assert_eq!(
+            pool.insert_contribution(undecodable)
+                .unwrap_err()
+                .to_string(),
+            "Failed to aggregate signatures: InvalidSignature"
+        );
+
+        // The contribution already held is untouched
+        assert_eq!(
+            pool.get_sync_aggregate(1, B256::ZERO)
+                .unwrap()
+                .num_active_participants(),
+            2
+        );
+    }
+}
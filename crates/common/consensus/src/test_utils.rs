@@ -0,0 +1,173 @@
+//! Builders for the containers used by unit tests.
+
+#[cfg(feature = "kzg")]
+use std::path::Path;
+use std::sync::Arc;
+
+use alloy_primitives::{aliases::B32, Address, B256, U256};
+use ream_bls::{BLSSignature, PubKey};
+use ssz_types::{BitVector, FixedVector, VariableList};
+use tree_hash::TreeHash;
+
+use crate::{
+    beacon_block_header::BeaconBlockHeader,
+    checkpoint::Checkpoint,
+    deneb::{
+        beacon_block::{BeaconBlock, SignedBeaconBlock},
+        beacon_block_body::BeaconBlockBody,
+        beacon_state::BeaconState,
+        execution_payload::ExecutionPayload,
+        execution_payload_header::ExecutionPayloadHeader,
+    },
+    eth_1_data::Eth1Data,
+    eth_spec::EthSpec,
+    fork::Fork,
+    fork_choice::helpers::constants::FAR_FUTURE_EPOCH,
+    kzg_commitment::KZGCommitment,
+    sync_aggregate::SyncAggregate,
+    validator::Validator,
+};
+
+/// Return a validator with ``pubkey`` that is active from ``activation_epoch`` and never exits.
+pub fn validator<E: EthSpec>(pubkey: PubKey, activation_epoch: u64) -> Validator {
+    Validator {
+        pubkey,
+        withdrawal_credentials: B256::ZERO,
+        effective_balance: E::MAX_EFFECTIVE_BALANCE,
+        slashed: false,
+        activation_eligibility_epoch: 0,
+        activation_epoch,
+        exit_epoch: FAR_FUTURE_EPOCH,
+        withdrawable_epoch: FAR_FUTURE_EPOCH,
+    }
+}
+
+pub fn eth1_data(deposit_count: u64, block_hash: B256) -> Eth1Data {
+    Eth1Data {
+        deposit_root: B256::ZERO,
+        deposit_count,
+        block_hash,
+    }
+}
+
+/// Return an otherwise empty state at ``slot`` with ``validators``, each with a balance equal to
+/// its effective balance.
+pub fn beacon_state<E: EthSpec>(slot: u64, validators: Vec<Validator>) -> BeaconState<E> {
+    let balances = validators
+        .iter()
+        .map(|validator| validator.effective_balance)
+        .collect::<Vec<_>>();
+    let validator_count = validators.len();
+    let checkpoint = Checkpoint {
+        epoch: 0,
+        root: B256::ZERO,
+    };
+
+    BeaconState {
+        genesis_time: 0,
+        genesis_validators_root: B256::ZERO,
+        slot,
+        fork: Fork {
+            previous_version: B32::ZERO,
+            current_version: B32::ZERO,
+            epoch: 0,
+        },
+        latest_block_header: BeaconBlockHeader {
+            slot,
+            ..Default::default()
+        },
+        block_roots: FixedVector::default(),
+        state_roots: FixedVector::default(),
+        historical_roots: VariableList::default(),
+        eth1_data: eth1_data(0, B256::ZERO),
+        eth1_data_votes: VariableList::default(),
+        eth1_deposit_index: 0,
+        validators: VariableList::from(validators),
+        balances: VariableList::from(balances),
+        randao_mixes: FixedVector::default(),
+        slashings: FixedVector::default(),
+        previous_epoch_participation: VariableList::from(vec![0; validator_count]),
+        current_epoch_participation: VariableList::from(vec![0; validator_count]),
+        justification_bits: BitVector::new(),
+        previous_justified_checkpoint: checkpoint,
+        current_justified_checkpoint: checkpoint,
+        finalized_checkpoint: checkpoint,
+        inactivity_scores: VariableList::from(vec![0; validator_count]),
+        current_sync_committee: Arc::default(),
+        next_sync_committee: Arc::default(),
+        latest_execution_payload_header: ExecutionPayloadHeader::default(),
+        next_withdrawal_index: 0,
+        next_withdrawal_validator_index: 0,
+        historical_summaries: VariableList::default(),
+        pubkey_cache: Arc::default(),
+    }
+}
+
+/// Return an otherwise empty block at ``slot`` committing to ``blob_kzg_commitments``.
+pub fn signed_beacon_block<E: EthSpec>(
+    slot: u64,
+    blob_kzg_commitments: Vec<KZGCommitment>,
+) -> SignedBeaconBlock<E> {
+    SignedBeaconBlock {
+        message: BeaconBlock {
+            slot,
+            proposer_index: 0,
+            parent_root: B256::ZERO,
+            state_root: B256::ZERO,
+            body: BeaconBlockBody {
+                randao_reveal: BLSSignature::infinity(),
+                eth1_data: eth1_data(0, B256::ZERO),
+                graffiti: B256::ZERO,
+                proposer_slashings: VariableList::default(),
+                attester_slashings: VariableList::default(),
+                attestations: VariableList::default(),
+                deposits: VariableList::default(),
+                voluntary_exits: VariableList::default(),
+                sync_aggregate: SyncAggregate {
+                    sync_committee_bits: BitVector::new(),
+                    sync_committee_signature: BLSSignature::infinity(),
+                },
+                execution_payload: ExecutionPayload {
+                    parent_hash: B256::ZERO,
+                    fee_recipient: Address::ZERO,
+                    state_root: B256::ZERO,
+                    receipts_root: B256::ZERO,
+                    logs_bloom: FixedVector::default(),
+                    prev_randao: B256::ZERO,
+                    block_number: 0,
+                    gas_limit: 0,
+                    gas_used: 0,
+                    timestamp: 0,
+                    extra_data: VariableList::default(),
+                    base_fee_per_gas: U256::ZERO,
+                    block_hash: B256::ZERO,
+                    transactions: VariableList::default(),
+                    withdrawals: VariableList::default(),
+                    blob_gas_used: 0,
+                    excess_blob_gas: 0,
+                },
+                bls_to_execution_changes: VariableList::default(),
+                blob_kzg_commitments: VariableList::from(blob_kzg_commitments),
+            },
+        },
+        signature: BLSSignature::infinity(),
+    }
+}
+
+/// Make ``state`` the post state of ``block``: the latest block header of ``state`` becomes the
+/// header of ``block``, and the state root of ``block`` the root of ``state``.
+pub fn set_post_state<E: EthSpec>(state: &mut BeaconState<E>, block: &mut SignedBeaconBlock<E>) {
+    state.slot = block.message.slot;
+    block.message.state_root = B256::ZERO;
+    state.latest_block_header = block.message.block_header();
+    block.message.state_root = state.tree_hash_root();
+}
+
+/// Load the mainnet trusted setup downloaded by ``make -C testing/ef-tests trusted_setup.txt``.
+#[cfg(feature = "kzg")]
+pub fn load_trusted_setup() {
+    crate::polynomial_commitments::load_trusted_setup(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../testing/ef-tests/trusted_setup.txt"),
+    )
+    .expect("Run `make -C testing/ef-tests trusted_setup.txt` to download the trusted setup");
+}
@@ -4,10 +4,26 @@ use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use tree_hash_derive::TreeHash;
 
-use crate::fork_choice::helpers::constants::{
-    ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH, MAX_EFFECTIVE_BALANCE,
+use crate::{
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::{ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH},
 };
 
+/// Validator status as defined by the Beacon API.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    PendingInitialized,
+    PendingQueued,
+    ActiveOngoing,
+    ActiveExiting,
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    WithdrawalPossible,
+    WithdrawalDone,
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct Validator {
     pub pubkey: PubKey,
@@ -40,10 +56,10 @@ impl Validator {
     }
 
     /// Check if ``validator`` is partially withdrawable.
-    pub fn is_partially_withdrawable_validator(&self, balance: u64) -> bool {
+    pub fn is_partially_withdrawable_validator<E: EthSpec>(&self, balance: u64) -> bool {
         self.has_eth1_withdrawal_credential()
-            && self.effective_balance == MAX_EFFECTIVE_BALANCE
-            && balance > MAX_EFFECTIVE_BALANCE
+            && self.effective_balance == E::MAX_EFFECTIVE_BALANCE
+            && balance > E::MAX_EFFECTIVE_BALANCE
     }
 
     pub fn is_slashable_validator(&self, epoch: u64) -> bool {
@@ -51,12 +67,106 @@ impl Validator {
     }
 
     pub fn is_active_validator(&self, epoch: u64) -> bool {
-        self.activation_eligibility_epoch <= epoch && epoch < self.exit_epoch
+        self.activation_epoch <= epoch && epoch < self.exit_epoch
     }
 
     /// Check if ``validator`` is eligible to be placed into the activation queue.
-    pub fn is_eligible_for_activation_queue(&self) -> bool {
+    pub fn is_eligible_for_activation_queue<E: EthSpec>(&self) -> bool {
         self.activation_eligibility_epoch == FAR_FUTURE_EPOCH
-            && self.effective_balance == MAX_EFFECTIVE_BALANCE
+            && self.effective_balance == E::MAX_EFFECTIVE_BALANCE
+    }
+
+    /// Return the Beacon API status of ``validator`` with ``balance`` at ``epoch``.
+    pub fn status(&self, balance: u64, epoch: u64) -> ValidatorStatus {
+        if self.activation_epoch > epoch {
+            if self.activation_eligibility_epoch == FAR_FUTURE_EPOCH {
+                ValidatorStatus::PendingInitialized
+            } else {
+                ValidatorStatus::PendingQueued
+            }
+        } else if self.is_active_validator(epoch) {
+            if self.slashed {
+                ValidatorStatus::ActiveSlashed
+            } else if self.exit_epoch == FAR_FUTURE_EPOCH {
+                ValidatorStatus::ActiveOngoing
+            } else {
+                ValidatorStatus::ActiveExiting
+            }
+        } else if epoch < self.withdrawable_epoch {
+            if self.slashed {
+                ValidatorStatus::ExitedSlashed
+            } else {
+                ValidatorStatus::ExitedUnslashed
+            }
+        } else if balance > 0 {
+            ValidatorStatus::WithdrawalPossible
+        } else {
+            ValidatorStatus::WithdrawalDone
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{eth_spec::MinimalEthSpec, test_utils::validator};
+
+    #[test]
+    fn test_is_active_validator() {
+        // Eligible for activation at epoch 2, but only activated at epoch 7
+        let validator = Validator {
+            activation_eligibility_epoch: 2,
+            exit_epoch: 10,
+            ..validator::<MinimalEthSpec>(PubKey::default(), 7)
+        };
+        assert!(!validator.is_active_validator(2));
+        assert!(!validator.is_active_validator(6));
+        assert!(validator.is_active_validator(7));
+        assert!(validator.is_active_validator(9));
+        assert!(!validator.is_active_validator(10));
+    }
+
+    #[test]
+    fn test_status() {
+        let active = validator::<MinimalEthSpec>(PubKey::default(), 0);
+        let pending = Validator {
+            activation_eligibility_epoch: FAR_FUTURE_EPOCH,
+            activation_epoch: FAR_FUTURE_EPOCH,
+            ..active.clone()
+        };
+        let exiting = Validator {
+            exit_epoch: 6,
+            withdrawable_epoch: 10,
+            ..active.clone()
+        };
+        let slashed = Validator {
+            slashed: true,
+            ..exiting.clone()
+        };
+        let balance = active.effective_balance;
+
+        assert_eq!(
+            pending.status(balance, 5),
+            ValidatorStatus::PendingInitialized
+        );
+        assert_eq!(
+            Validator {
+                activation_eligibility_epoch: 3,
+                ..pending
+            }
+            .status(balance, 5),
+            ValidatorStatus::PendingQueued
+        );
+        assert_eq!(active.status(balance, 5), ValidatorStatus::ActiveOngoing);
+        assert_eq!(exiting.status(balance, 5), ValidatorStatus::ActiveExiting);
+        assert_eq!(slashed.status(balance, 5), ValidatorStatus::ActiveSlashed);
+        assert_eq!(exiting.status(balance, 6), ValidatorStatus::ExitedUnslashed);
+        assert_eq!(slashed.status(balance, 9), ValidatorStatus::ExitedSlashed);
+        assert_eq!(
+            exiting.status(balance, 10),
+            ValidatorStatus::WithdrawalPossible
+        );
+        assert_eq!(slashed.status(1, 10), ValidatorStatus::WithdrawalPossible);
+        assert_eq!(exiting.status(0, 10), ValidatorStatus::WithdrawalDone);
     }
 }
@@ -0,0 +1,82 @@
+use alloy_primitives::{b256, B256};
+use anyhow::{anyhow, ensure};
+use ream_bls::{BLSSignature, PubKey};
+use ream_consensus::deposit_data::DepositData;
+use ssz_types::FixedVector;
+
+use crate::rpc_types::get_logs::Log;
+
+/// ``keccak256("DepositEvent(bytes,bytes,bytes,bytes,bytes)")``
+pub const DEPOSIT_EVENT_TOPIC: B256 =
+    b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5");
+
+const PUBKEY_LENGTH: usize = 48;
+const WITHDRAWAL_CREDENTIALS_LENGTH: usize = 32;
+const AMOUNT_LENGTH: usize = 8;
+const SIGNATURE_LENGTH: usize = 96;
+const INDEX_LENGTH: usize = 8;
+
+/// A ``DepositEvent`` emitted by the deposit contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepositLog {
+    pub deposit_data: DepositData,
+    pub block_number: u64,
+    pub index: u64,
+}
+
+impl TryFrom<&Log> for DepositLog {
+    type Error = anyhow::Error;
+
+    fn try_from(log: &Log) -> anyhow::Result<Self> {
+        ensure!(
+            log.topics.first() == Some(&DEPOSIT_EVENT_TOPIC),
+            "Log is not a DepositEvent"
+        );
+
+        let pubkey = read_abi_bytes(&log.data, 0, PUBKEY_LENGTH)?;
+        let withdrawal_credentials = read_abi_bytes(&log.data, 1, WITHDRAWAL_CREDENTIALS_LENGTH)?;
+        let amount = read_abi_bytes(&log.data, 2, AMOUNT_LENGTH)?;
+        let signature = read_abi_bytes(&log.data, 3, SIGNATURE_LENGTH)?;
+        let index = read_abi_bytes(&log.data, 4, INDEX_LENGTH)?;
+
+        Ok(Self {
+            deposit_data: DepositData {
+                pubkey: PubKey {
+                    inner: FixedVector::from(pubkey.to_vec()),
+                },
+                withdrawal_credentials: B256::from_slice(withdrawal_credentials),
+                amount: u64::from_le_bytes(amount.try_into()?),
+                signature: BLSSignature {
+                    inner: FixedVector::from(signature.to_vec()),
+                },
+            },
+            block_number: log.block_number,
+            index: u64::from_le_bytes(index.try_into()?),
+        })
+    }
+}
+
+/// Read the ``bytes`` argument at ``position`` of ABI encoded event data, checking that it is
+/// ``expected_length`` long.
+fn read_abi_bytes(data: &[u8], position: usize, expected_length: usize) -> anyhow::Result<&[u8]> {
+    let offset = read_abi_word(data, position * 32)?;
+    let length = read_abi_word(data, offset)?;
+    ensure!(
+        length == expected_length,
+        "DepositEvent argument {position} has length {length}, expected {expected_length}"
+    );
+    data.get(offset + 32..offset + 32 + length)
+        .ok_or_else(|| anyhow!("DepositEvent argument {position} is out of bounds"))
+}
+
+fn read_abi_word(data: &[u8], offset: usize) -> anyhow::Result<usize> {
+    let word = offset
+        .checked_add(32)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| anyhow!("DepositEvent word at {offset} is out of bounds"))?;
+    ensure!(
+        word[..24].iter().all(|byte| *byte == 0),
+        "DepositEvent word at {offset} is too large"
+    );
+    Ok(u64::from_be_bytes(word[24..].try_into()?) as usize)
+}
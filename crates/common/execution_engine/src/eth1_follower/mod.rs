@@ -0,0 +1,382 @@
+pub mod deposit_log;
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, U64};
+use anyhow::{anyhow, ensure};
+use deposit_log::{DepositLog, DEPOSIT_EVENT_TOPIC};
+use ream_consensus::{
+    chain_spec::ChainSpec, deneb::beacon_state::BeaconState, deposit::Deposit,
+    deposit_tree::DepositTree, eth_1_block::Eth1Block, eth_1_data::Eth1Data, eth_spec::EthSpec,
+};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use tree_hash::TreeHash;
+
+use crate::{
+    rpc_types::{eth_block::EthBlock, get_logs::Log},
+    utils::{JsonRpcRequest, JsonRpcResponse},
+};
+
+/// Maximum number of blocks requested in a single ``eth_getLogs`` call.
+pub const MAX_LOG_REQUEST_BLOCK_RANGE: u64 = 1000;
+
+/// Follows the deposit contract of the execution chain, ``ETH1_FOLLOW_DISTANCE`` blocks behind
+/// the head, to build deposit proofs and vote on ``Eth1Data``.
+pub struct Eth1Follower {
+    http_client: Client,
+    eth1_api_url: String,
+    deposit_contract_address: Address,
+    pub deposit_tree: DepositTree,
+    pub deposit_logs: Vec<DepositLog>,
+    pub block_cache: BTreeMap<u64, Eth1Block>,
+    next_block_number: u64,
+}
+
+impl Eth1Follower {
+    pub fn new(
+        eth1_api_url: String,
+        deposit_contract_address: Address,
+        deposit_contract_deploy_block: u64,
+    ) -> Self {
+        Self {
+            http_client: Client::new(),
+            eth1_api_url,
+            deposit_contract_address,
+            deposit_tree: DepositTree::default(),
+            deposit_logs: vec![],
+            block_cache: BTreeMap::new(),
+            next_block_number: deposit_contract_deploy_block,
+        }
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<Value>,
+    ) -> anyhow::Result<T> {
+        let request_body = JsonRpcRequest {
+            id: 1,
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        };
+
+        self.http_client
+            .post(&self.eth1_api_url)
+            .json(&request_body)
+            .send()
+            .await?
+            .json::<JsonRpcResponse<T>>()
+            .await?
+            .to_result()
+    }
+
+    pub async fn eth_block_number(&self) -> anyhow::Result<u64> {
+        Ok(self.request::<U64>("eth_blockNumber", vec![]).await?.to())
+    }
+
+    pub async fn eth_get_block_by_number(&self, block_number: u64) -> anyhow::Result<EthBlock> {
+        self.request::<Option<EthBlock>>(
+            "eth_getBlockByNumber",
+            vec![json!(U64::from(block_number)), json!(false)],
+        )
+        .await?
+        .ok_or_else(|| anyhow!("Block {block_number} not found"))
+    }
+
+    pub async fn eth_get_deposit_logs(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> anyhow::Result<Vec<Log>> {
+        self.request(
+            "eth_getLogs",
+            vec![json!({
+                "address": self.deposit_contract_address,
+                "fromBlock": U64::from(from_block),
+                "toBlock": U64::from(to_block),
+                "topics": [DEPOSIT_EVENT_TOPIC],
+            })],
+        )
+        .await
+    }
+
+    /// Import deposit logs and blocks up to ``ETH1_FOLLOW_DISTANCE`` blocks behind the head.
+    ///
+    /// Only blocks that can still become candidates for an ``Eth1Data`` vote are kept in
+    /// ``block_cache``.
+    pub async fn update<E: EthSpec>(&mut self, spec: &ChainSpec) -> anyhow::Result<()> {
+        let head_block_number = self.eth_block_number().await?;
+        let Some(follow_block_number) = head_block_number.checked_sub(spec.eth1_follow_distance)
+        else {
+            return Ok(());
+        };
+        if follow_block_number < self.next_block_number {
+            return Ok(());
+        }
+
+        let voting_period_blocks =
+            E::EPOCHS_PER_ETH1_VOTING_PERIOD * E::SLOTS_PER_EPOCH * spec.seconds_per_slot
+                / spec.seconds_per_eth1_block;
+        let block_cache_size = spec.eth1_follow_distance * 2 + voting_period_blocks;
+        let first_cached_block_number = follow_block_number
+            .saturating_sub(block_cache_size)
+            .max(self.next_block_number);
+
+        let mut from_block = self.next_block_number;
+        while from_block <= follow_block_number {
+            let to_block = (from_block + MAX_LOG_REQUEST_BLOCK_RANGE - 1).min(follow_block_number);
+            for log in self.eth_get_deposit_logs(from_block, to_block).await? {
+                if log.removed {
+                    continue;
+                }
+                self.import_deposit_log(DepositLog::try_from(&log)?)?;
+            }
+            from_block = to_block + 1;
+        }
+
+        for block_number in first_cached_block_number..=follow_block_number {
+            let block = self.eth_get_block_by_number(block_number).await?;
+            let deposit_count =
+                self.deposit_logs
+                    .partition_point(|log| log.block_number <= block_number) as u64;
+            self.block_cache.insert(
+                block_number,
+                Eth1Block {
+                    block_hash: block.hash,
+                    block_number,
+                    timestamp: block.timestamp,
+                    deposit_root: self.deposit_tree.get_root_at(deposit_count)?,
+                    deposit_count,
+                },
+            );
+        }
+        self.block_cache = self
+            .block_cache
+            .split_off(&follow_block_number.saturating_sub(block_cache_size));
+        self.next_block_number = follow_block_number + 1;
+
+        Ok(())
+    }
+
+    fn import_deposit_log(&mut self, deposit_log: DepositLog) -> anyhow::Result<()> {
+        ensure!(
+            deposit_log.index == self.deposit_logs.len() as u64,
+            "Expected deposit {} but got deposit {}",
+            self.deposit_logs.len(),
+            deposit_log.index
+        );
+        self.deposit_tree
+            .push_leaf(deposit_log.deposit_data.tree_hash_root())?;
+        self.deposit_logs.push(deposit_log);
+        Ok(())
+    }
+
+    pub fn get_eth1_vote<E: EthSpec>(&self, state: &BeaconState<E>, spec: &ChainSpec) -> Eth1Data {
+        state.get_eth1_vote(
+            &self.block_cache.values().cloned().collect::<Vec<_>>(),
+            spec,
+        )
+    }
+
+    /// Return the deposits a block built on ``state`` must include, proven against ``eth1_data``.
+    pub fn get_deposits<E: EthSpec>(
+        &self,
+        state: &BeaconState<E>,
+        eth1_data: &Eth1Data,
+    ) -> anyhow::Result<Vec<Deposit>> {
+        let end_index = eth1_data
+            .deposit_count
+            .min(state.eth1_deposit_index + E::MAX_DEPOSITS);
+        (state.eth1_deposit_index..end_index)
+            .map(|index| {
+                let deposit_log = self
+                    .deposit_logs
+                    .get(index as usize)
+                    .ok_or_else(|| anyhow!("Deposit {index} hasn't been imported"))?;
+                self.deposit_tree
+                    .get_deposit(index, deposit_log.deposit_data.clone(), eth1_data)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::{Bytes, B256, U256};
+    use ream_bls::{BLSSignature, PubKey};
+    use ream_consensus::{
+        deneb::beacon_state::is_valid_merkle_branch, deposit_data::DepositData,
+        eth_spec::MinimalEthSpec, fork_choice::helpers::constants::DEPOSIT_CONTRACT_TREE_DEPTH,
+    };
+    use ssz_types::FixedVector;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    const HEAD_BLOCK_NUMBER: u64 = 40;
+    const DEPOSIT_BLOCK_NUMBERS: [u64; 3] = [5, 10, 20];
+
+    fn deposit_data(index: u64) -> DepositData {
+        DepositData {
+            pubkey: PubKey {
+                inner: FixedVector::from(vec![index as u8; 48]),
+            },
+            withdrawal_credentials: B256::repeat_byte(index as u8),
+            amount: 32_000_000_000,
+            signature: BLSSignature {
+                inner: FixedVector::from(vec![index as u8; 96]),
+            },
+        }
+    }
+
+    fn encode_deposit_event(deposit_data: &DepositData, index: u64) -> Bytes {
+        let arguments = [
+            deposit_data.pubkey.to_bytes().to_vec(),
+            deposit_data.withdrawal_credentials.to_vec(),
+            deposit_data.amount.to_le_bytes().to_vec(),
+            deposit_data.signature.to_bytes().to_vec(),
+            index.to_le_bytes().to_vec(),
+        ];
+        let mut head = vec![];
+        let mut tail = vec![];
+        for argument in arguments.iter() {
+            let offset = arguments.len() * 32 + tail.len();
+            head.extend_from_slice(&U256::from(offset).to_be_bytes::<32>());
+            tail.extend_from_slice(&U256::from(argument.len()).to_be_bytes::<32>());
+            tail.extend_from_slice(argument);
+            tail.resize(tail.len().div_ceil(32) * 32, 0);
+        }
+        Bytes::from([head, tail].concat())
+    }
+
+    fn handle_request(request: &Value) -> Value {
+        let result = match request["method"].as_str().unwrap() {
+            "eth_blockNumber" => json!(U64::from(HEAD_BLOCK_NUMBER)),
+            "eth_getBlockByNumber" => {
+                let number = serde_json::from_value::<U64>(request["params"][0].clone())
+                    .unwrap()
+                    .to::<u64>();
+                json!(EthBlock {
+                    hash: B256::left_padding_from(&number.to_be_bytes()),
+                    parent_hash: B256::left_padding_from(&number.saturating_sub(1).to_be_bytes()),
+                    number,
+                    timestamp: number * 14,
+                })
+            }
+            "eth_getLogs" => {
+                let filter = &request["params"][0];
+                let from_block = serde_json::from_value::<U64>(filter["fromBlock"].clone())
+                    .unwrap()
+                    .to::<u64>();
+                let to_block = serde_json::from_value::<U64>(filter["toBlock"].clone())
+                    .unwrap()
+                    .to::<u64>();
+                json!(DEPOSIT_BLOCK_NUMBERS
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, block_number)| (from_block..=to_block).contains(*block_number))
+                    .map(|(index, block_number)| Log {
+                        address: Address::ZERO,
+                        topics: vec![DEPOSIT_EVENT_TOPIC],
+                        data: encode_deposit_event(&deposit_data(index as u64), index as u64),
+                        block_number: *block_number,
+                        block_hash: B256::left_padding_from(&block_number.to_be_bytes()),
+                        transaction_hash: B256::ZERO,
+                        log_index: 0,
+                        removed: false,
+                    })
+                    .collect::<Vec<_>>())
+            }
+            method => panic!("Unexpected method {method}"),
+        };
+        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+    }
+
+    async fn spawn_mock_eth1_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![];
+                let mut chunk = [0u8; 4096];
+                let body = loop {
+                    let read = stream.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..read]);
+                    let request = String::from_utf8_lossy(&buffer).to_string();
+                    if let Some((headers, body)) = request.split_once("\r\n\r\n") {
+                        let content_length = headers
+                            .lines()
+                            .find_map(|line| {
+                                line.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|length| length.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= content_length {
+                            break body.to_string();
+                        }
+                    }
+                };
+                let response =
+                    handle_request(&serde_json::from_str::<Value>(&body).unwrap()).to_string();
+                stream
+                    .write_all(
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                            response.len()
+                        )
+                        .as_bytes(),
+                    )
+                    .await
+                    .unwrap();
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn eth1_follower_imports_deposits_and_blocks() {
+        let spec = ChainSpec::minimal();
+        let mut follower = Eth1Follower::new(spawn_mock_eth1_server().await, Address::ZERO, 0);
+        follower.update::<MinimalEthSpec>(&spec).await.unwrap();
+
+        let follow_block_number = HEAD_BLOCK_NUMBER - spec.eth1_follow_distance;
+        assert_eq!(follower.deposit_logs.len(), 3);
+        assert_eq!(
+            follower.block_cache.keys().last(),
+            Some(&follow_block_number)
+        );
+        assert_eq!(follower.block_cache[&7].deposit_count, 1);
+        assert_eq!(follower.block_cache[&follow_block_number].deposit_count, 3);
+
+        let eth1_block = &follower.block_cache[&12];
+        let mut deposit_tree = DepositTree::default();
+        for index in 0..2 {
+            deposit_tree
+                .push_leaf(deposit_data(index).tree_hash_root())
+                .unwrap();
+        }
+        assert_eq!(eth1_block.deposit_root, deposit_tree.get_root());
+
+        let eth1_data = eth1_block.get_eth1_data();
+        let deposit = follower
+            .deposit_tree
+            .get_deposit(1, deposit_data(1), &eth1_data)
+            .unwrap();
+        assert!(is_valid_merkle_branch(
+            deposit.data.tree_hash_root(),
+            &deposit.proof,
+            DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+            1,
+            eth1_data.deposit_root,
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod eth1_follower;
 mod rpc_types;
 pub mod transaction;
 pub mod utils;
@@ -9,6 +10,7 @@ use async_trait::async_trait;
 use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
 use ream_consensus::{
     deneb::execution_payload::ExecutionPayload,
+    eth_spec::EthSpec,
     execution_engine::{engine_trait::ExecutionApi, new_payload_request::NewPayloadRequest},
 };
 use reqwest::{Client, Request};
@@ -54,17 +56,17 @@ impl ExecutionEngine {
     }
 
     /// Return ``True`` if and only if ``execution_payload.block_hash`` is computed correctly.
-    pub fn is_valid_block_hash(
+    pub fn is_valid_block_hash<E: EthSpec>(
         &self,
-        execution_payload: &ExecutionPayload,
+        execution_payload: &ExecutionPayload<E>,
         parent_beacon_block_root: B256,
     ) -> bool {
         execution_payload.block_hash == execution_payload.header_hash(parent_beacon_block_root)
     }
 
-    pub fn blob_versioned_hashes(
+    pub fn blob_versioned_hashes<E: EthSpec>(
         &self,
-        execution_payload: &ExecutionPayload,
+        execution_payload: &ExecutionPayload<E>,
     ) -> anyhow::Result<Vec<B256>> {
         let mut blob_versioned_hashes = vec![];
         for transaction in execution_payload.transactions.iter() {
@@ -82,9 +84,9 @@ impl ExecutionEngine {
 
     /// Return ``True`` if and only if the version hashes computed by the blob transactions of
     /// ``new_payload_request.execution_payload`` matches ``new_payload_request.versioned_hashes``.
-    pub fn is_valid_versioned_hashes(
+    pub fn is_valid_versioned_hashes<E: EthSpec>(
         &self,
-        new_payload_request: &NewPayloadRequest,
+        new_payload_request: &NewPayloadRequest<E>,
     ) -> anyhow::Result<bool> {
         Ok(
             self.blob_versioned_hashes(&new_payload_request.execution_payload)?
@@ -93,9 +95,9 @@ impl ExecutionEngine {
     }
 
     /// Return ``PayloadStatus`` of execution payload``.
-    pub async fn notify_new_payload(
+    pub async fn notify_new_payload<E: EthSpec>(
         &self,
-        new_payload_request: NewPayloadRequest,
+        new_payload_request: NewPayloadRequest<E>,
     ) -> anyhow::Result<PayloadStatus> {
         let NewPayloadRequest {
             execution_payload,
@@ -254,9 +256,9 @@ impl ExecutionEngine {
 
 #[async_trait]
 impl ExecutionApi for ExecutionEngine {
-    async fn verify_and_notify_new_payload(
+    async fn verify_and_notify_new_payload<E: EthSpec>(
         &self,
-        new_payload_request: NewPayloadRequest,
+        new_payload_request: NewPayloadRequest<E>,
     ) -> anyhow::Result<bool> {
         if new_payload_request
             .execution_payload
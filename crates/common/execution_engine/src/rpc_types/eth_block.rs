@@ -0,0 +1,14 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// The subset of ``eth_getBlockByNumber`` fields needed to vote on ``Eth1Data``.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthBlock {
+    pub hash: B256,
+    pub parent_hash: B256,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub number: u64,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub timestamp: u64,
+}
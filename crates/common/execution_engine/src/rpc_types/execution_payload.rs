@@ -1,5 +1,7 @@
 use alloy_primitives::{Address, B256, U256};
-use ream_consensus::{deneb::execution_payload::ExecutionPayload, withdrawal::Withdrawal};
+use ream_consensus::{
+    deneb::execution_payload::ExecutionPayload, eth_spec::EthSpec, withdrawal::Withdrawal,
+};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
@@ -41,8 +43,8 @@ pub struct ExecutionPayloadV3 {
     pub excess_blob_gas: u64,
 }
 
-impl From<ExecutionPayload> for ExecutionPayloadV3 {
-    fn from(value: ExecutionPayload) -> Self {
+impl<E: EthSpec> From<ExecutionPayload<E>> for ExecutionPayloadV3 {
+    fn from(value: ExecutionPayload<E>) -> Self {
         ExecutionPayloadV3 {
             parent_hash: value.parent_hash,
             fee_recipient: value.fee_recipient,
@@ -58,7 +60,7 @@ impl From<ExecutionPayload> for ExecutionPayloadV3 {
             base_fee_per_gas: value.base_fee_per_gas,
             block_hash: value.block_hash,
             transactions: value.transactions,
-            withdrawals: value.withdrawals,
+            withdrawals: VariableList::from(value.withdrawals.to_vec()),
             blob_gas_used: value.blob_gas_used,
             excess_blob_gas: value.excess_blob_gas,
         }
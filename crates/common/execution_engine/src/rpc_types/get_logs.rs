@@ -0,0 +1,18 @@
+use alloy_primitives::{Address, Bytes, B256};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub block_number: u64,
+    pub block_hash: B256,
+    pub transaction_hash: B256,
+    #[serde(with = "serde_utils::u64_hex_be")]
+    pub log_index: u64,
+    #[serde(default)]
+    pub removed: bool,
+}
@@ -1,6 +1,8 @@
+pub mod eth_block;
 pub mod eth_syncing;
 pub mod execution_payload;
 pub mod forkchoice_update;
 pub mod get_blobs;
+pub mod get_logs;
 pub mod get_payload;
 pub mod payload_status;
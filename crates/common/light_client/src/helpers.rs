@@ -0,0 +1,49 @@
+use alloy_primitives::B256;
+use ream_consensus::{
+    chain_spec::ChainSpec,
+    deneb::{
+        beacon_state::is_valid_merkle_branch, execution_payload_header::ExecutionPayloadHeader,
+    },
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::EXECUTION_PAYLOAD_GINDEX,
+    light_client::light_client_header::LightClientHeader,
+    merkle_proof::{floorlog2, get_subtree_index},
+    misc::compute_epoch_at_slot,
+};
+use tree_hash::TreeHash;
+
+/// Return the root of the execution payload header committed to by ``header``.
+///
+/// Only Deneb execution payload headers are represented, so this is always its hash tree root.
+pub fn get_lc_execution_root(header: &LightClientHeader) -> B256 {
+    header.execution.tree_hash_root()
+}
+
+pub fn is_valid_light_client_header<E: EthSpec>(
+    header: &LightClientHeader,
+    spec: &ChainSpec,
+) -> bool {
+    let epoch = compute_epoch_at_slot::<E>(header.beacon.slot);
+
+    if epoch < spec.deneb_fork_epoch
+        && (header.execution.blob_gas_used != 0 || header.execution.excess_blob_gas != 0)
+    {
+        return false;
+    }
+
+    if epoch < spec.capella_fork_epoch {
+        return header.execution == ExecutionPayloadHeader::default()
+            && header
+                .execution_branch
+                .iter()
+                .all(|node| *node == B256::ZERO);
+    }
+
+    is_valid_merkle_branch(
+        get_lc_execution_root(header),
+        &header.execution_branch,
+        floorlog2(EXECUTION_PAYLOAD_GINDEX),
+        get_subtree_index(EXECUTION_PAYLOAD_GINDEX),
+        header.beacon.body_root,
+    )
+}
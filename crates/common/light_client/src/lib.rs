@@ -0,0 +1,4 @@
+#![warn(clippy::unwrap_used)]
+
+pub mod helpers;
+pub mod light_client_store;
@@ -0,0 +1,549 @@
+use std::cmp::max;
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use ream_bls::traits::Verifiable;
+use ream_consensus::{
+    chain_spec::ChainSpec,
+    deneb::beacon_state::is_valid_merkle_branch,
+    eth_spec::EthSpec,
+    fork_choice::helpers::constants::{
+        CURRENT_SYNC_COMMITTEE_GINDEX, DOMAIN_SYNC_COMMITTEE, FINALIZED_ROOT_GINDEX, GENESIS_SLOT,
+        NEXT_SYNC_COMMITTEE_GINDEX,
+    },
+    light_client::{
+        light_client_bootstrap::LightClientBootstrap,
+        light_client_finality_update::LightClientFinalityUpdate,
+        light_client_header::LightClientHeader,
+        light_client_optimistic_update::LightClientOptimisticUpdate,
+        light_client_update::LightClientUpdate,
+    },
+    merkle_proof::{floorlog2, get_subtree_index},
+    misc::{
+        compute_domain, compute_epoch_at_slot, compute_signing_root,
+        compute_sync_committee_period_at_slot,
+    },
+    sync_committee::SyncCommittee,
+};
+use ssz_types::FixedVector;
+use tree_hash::TreeHash;
+
+use crate::helpers::is_valid_light_client_header;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LightClientStore<E: EthSpec> {
+    /// Header that is finalized
+    pub finalized_header: LightClientHeader,
+
+    /// Sync committees corresponding to the finalized header
+    pub current_sync_committee: SyncCommittee<E>,
+    pub next_sync_committee: SyncCommittee<E>,
+
+    /// Best available header to switch finalized head to if we see nothing else
+    pub best_valid_update: Option<LightClientUpdate<E>>,
+
+    /// Most recent available reasonably-safe header
+    pub optimistic_header: LightClientHeader,
+
+    /// Max number of active participants in a sync committee (used to calculate safety
+    /// threshold)
+    pub previous_max_active_participants: u64,
+    pub current_max_active_participants: u64,
+}
+
+/// Initialize a light client store from a ``bootstrap`` for the trusted ``trusted_block_root``.
+pub fn initialize_light_client_store<E: EthSpec>(
+    trusted_block_root: B256,
+    bootstrap: &LightClientBootstrap<E>,
+    spec: &ChainSpec,
+) -> anyhow::Result<LightClientStore<E>> {
+    ensure!(
+        is_valid_light_client_header::<E>(&bootstrap.header, spec),
+        "Invalid bootstrap header"
+    );
+    ensure!(
+        bootstrap.header.beacon.tree_hash_root() == trusted_block_root,
+        "Bootstrap header does not match the trusted block root"
+    );
+    ensure!(
+        is_valid_merkle_branch(
+            bootstrap.current_sync_committee.tree_hash_root(),
+            &bootstrap.current_sync_committee_branch,
+            floorlog2(CURRENT_SYNC_COMMITTEE_GINDEX),
+            get_subtree_index(CURRENT_SYNC_COMMITTEE_GINDEX),
+            bootstrap.header.beacon.state_root,
+        ),
+        "Invalid current sync committee branch"
+    );
+
+    Ok(LightClientStore {
+        finalized_header: bootstrap.header.clone(),
+        current_sync_committee: bootstrap.current_sync_committee.clone(),
+        next_sync_committee: SyncCommittee::default(),
+        best_valid_update: None,
+        optimistic_header: bootstrap.header.clone(),
+        previous_max_active_participants: 0,
+        current_max_active_participants: 0,
+    })
+}
+
+impl<E: EthSpec> LightClientStore<E> {
+    pub fn is_next_sync_committee_known(&self) -> bool {
+        self.next_sync_committee != SyncCommittee::default()
+    }
+
+    pub fn get_safety_threshold(&self) -> u64 {
+        max(
+            self.previous_max_active_participants,
+            self.current_max_active_participants,
+        ) / 2
+    }
+
+    pub fn validate_light_client_update(
+        &self,
+        update: &LightClientUpdate<E>,
+        current_slot: u64,
+        genesis_validators_root: B256,
+        spec: &ChainSpec,
+    ) -> anyhow::Result<()> {
+        // Verify sync committee has sufficient participants
+        let sync_aggregate = &update.sync_aggregate;
+        ensure!(
+            sync_aggregate.num_active_participants() >= E::MIN_SYNC_COMMITTEE_PARTICIPANTS,
+            "Not enough sync committee participants"
+        );
+
+        // Verify update does not skip a sync committee period
+        ensure!(
+            is_valid_light_client_header::<E>(&update.attested_header, spec),
+            "Invalid attested header"
+        );
+        let update_attested_slot = update.attested_header.beacon.slot;
+        let update_finalized_slot = update.finalized_header.beacon.slot;
+        ensure!(
+            current_slot >= update.signature_slot
+                && update.signature_slot > update_attested_slot
+                && update_attested_slot >= update_finalized_slot,
+            "Update slots are out of order"
+        );
+        let store_period =
+            compute_sync_committee_period_at_slot::<E>(self.finalized_header.beacon.slot);
+        let update_signature_period =
+            compute_sync_committee_period_at_slot::<E>(update.signature_slot);
+        if self.is_next_sync_committee_known() {
+            ensure!(
+                update_signature_period == store_period
+                    || update_signature_period == store_period + 1,
+                "Update signature period is not the current or next period"
+            );
+        } else {
+            ensure!(
+                update_signature_period == store_period,
+                "Update signature period is not the current period"
+            );
+        }
+
+        // Verify update is relevant
+        let update_attested_period =
+            compute_sync_committee_period_at_slot::<E>(update_attested_slot);
+        let update_has_next_sync_committee = !self.is_next_sync_committee_known()
+            && update.is_sync_committee_update()
+            && update_attested_period == store_period;
+        ensure!(
+            update_attested_slot > self.finalized_header.beacon.slot
+                || update_has_next_sync_committee,
+            "Update is not relevant"
+        );
+
+        // Verify that the ``finality_branch``, if present, confirms ``finalized_header`` to match
+        // the finalized checkpoint root saved in the state of ``attested_header``. Note that the
+        // genesis finalized checkpoint root is represented as a zero hash.
+        if !update.is_finality_update() {
+            ensure!(
+                update.finalized_header == LightClientHeader::default(),
+                "Finalized header must be empty without a finality branch"
+            );
+        } else {
+            let finalized_root = if update_finalized_slot == GENESIS_SLOT {
+                ensure!(
+                    update.finalized_header == LightClientHeader::default(),
+                    "Genesis finalized header must be empty"
+                );
+                B256::ZERO
+            } else {
+                ensure!(
+                    is_valid_light_client_header::<E>(&update.finalized_header, spec),
+                    "Invalid finalized header"
+                );
+                update.finalized_header.beacon.tree_hash_root()
+            };
+            ensure!(
+                is_valid_merkle_branch(
+                    finalized_root,
+                    &update.finality_branch,
+                    floorlog2(FINALIZED_ROOT_GINDEX),
+                    get_subtree_index(FINALIZED_ROOT_GINDEX),
+                    update.attested_header.beacon.state_root,
+                ),
+                "Invalid finality branch"
+            );
+        }
+
+        // Verify that the ``next_sync_committee``, if present, actually is the next sync committee
+        // saved in the state of the ``attested_header``
+        if !update.is_sync_committee_update() {
+            ensure!(
+                update.next_sync_committee == SyncCommittee::default(),
+                "Next sync committee must be empty without a next sync committee branch"
+            );
+        } else {
+            if update_attested_period == store_period && self.is_next_sync_committee_known() {
+                ensure!(
+                    update.next_sync_committee == self.next_sync_committee,
+                    "Next sync committee does not match the known next sync committee"
+                );
+            }
+            ensure!(
+                is_valid_merkle_branch(
+                    update.next_sync_committee.tree_hash_root(),
+                    &update.next_sync_committee_branch,
+                    floorlog2(NEXT_SYNC_COMMITTEE_GINDEX),
+                    get_subtree_index(NEXT_SYNC_COMMITTEE_GINDEX),
+                    update.attested_header.beacon.state_root,
+                ),
+                "Invalid next sync committee branch"
+            );
+        }
+
+        // Verify sync committee aggregate signature
+        let sync_committee = if update_signature_period == store_period {
+            &self.current_sync_committee
+        } else {
+            &self.next_sync_committee
+        };
+        let participant_pubkeys = sync_committee
+            .pubkeys
+            .iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+            .filter_map(|(pubkey, bit)| bit.then_some(pubkey))
+            .collect::<Vec<_>>();
+        let fork_version_slot = max(update.signature_slot, 1) - 1;
+        let fork_version = spec.compute_fork_version(compute_epoch_at_slot::<E>(fork_version_slot));
+        let domain = compute_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            fork_version,
+            Some(genesis_validators_root),
+        );
+        let signing_root = compute_signing_root(update.attested_header.beacon.clone(), domain);
+        ensure!(
+            sync_aggregate
+                .sync_committee_signature
+                .fast_aggregate_verify(&participant_pubkeys, signing_root.as_ref())
+                .map_err(|err| anyhow!("Failed to verify fast aggregate: {err:?}"))?,
+            "Sync committee signature verification failed"
+        );
+
+        Ok(())
+    }
+
+    pub fn apply_light_client_update(
+        &mut self,
+        update: &LightClientUpdate<E>,
+    ) -> anyhow::Result<()> {
+        let store_period =
+            compute_sync_committee_period_at_slot::<E>(self.finalized_header.beacon.slot);
+        let update_finalized_period =
+            compute_sync_committee_period_at_slot::<E>(update.finalized_header.beacon.slot);
+        if !self.is_next_sync_committee_known() {
+            ensure!(
+                update_finalized_period == store_period,
+                "Update finalized period must match the store period"
+            );
+            self.next_sync_committee = update.next_sync_committee.clone();
+        } else if update_finalized_period == store_period + 1 {
+            self.current_sync_committee = self.next_sync_committee.clone();
+            self.next_sync_committee = update.next_sync_committee.clone();
+            self.previous_max_active_participants = self.current_max_active_participants;
+            self.current_max_active_participants = 0;
+        }
+        if update.finalized_header.beacon.slot > self.finalized_header.beacon.slot {
+            self.finalized_header = update.finalized_header.clone();
+            if self.finalized_header.beacon.slot > self.optimistic_header.beacon.slot {
+                self.optimistic_header = self.finalized_header.clone();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn process_light_client_store_force_update(
+        &mut self,
+        current_slot: u64,
+    ) -> anyhow::Result<()> {
+        if current_slot > self.finalized_header.beacon.slot + E::UPDATE_TIMEOUT {
+            if let Some(mut best_valid_update) = self.best_valid_update.take() {
+                // Forced best update when the update timeout has elapsed.
+                // Because the apply logic waits for ``finalized_header.beacon.slot`` to indicate
+                // sync committee finality, the ``attested_header`` may be treated as
+                // ``finalized_header`` in extended periods of non-finality to guarantee
+                // progression into later sync committee periods according to
+                // ``is_better_update``.
+                if best_valid_update.finalized_header.beacon.slot
+                    <= self.finalized_header.beacon.slot
+                {
+                    best_valid_update.finalized_header = best_valid_update.attested_header.clone();
+                }
+                self.apply_light_client_update(&best_valid_update)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn process_light_client_update(
+        &mut self,
+        update: &LightClientUpdate<E>,
+        current_slot: u64,
+        genesis_validators_root: B256,
+        spec: &ChainSpec,
+    ) -> anyhow::Result<()> {
+        self.validate_light_client_update(update, current_slot, genesis_validators_root, spec)?;
+
+        let num_active_participants = update.sync_aggregate.num_active_participants();
+
+        // Update the best update in case we have to force-update to it if the timeout elapses
+        if self
+            .best_valid_update
+            .as_ref()
+            .map_or(true, |best_valid_update| {
+                update.is_better_update(best_valid_update)
+            })
+        {
+            self.best_valid_update = Some(update.clone());
+        }
+
+        // Track the maximum number of active participants in the committee signatures
+        self.current_max_active_participants = max(
+            self.current_max_active_participants,
+            num_active_participants,
+        );
+
+        // Update the optimistic header
+        if num_active_participants > self.get_safety_threshold()
+            && update.attested_header.beacon.slot > self.optimistic_header.beacon.slot
+        {
+            self.optimistic_header = update.attested_header.clone();
+        }
+
+        // Update finalized header
+        let update_has_finalized_next_sync_committee = !self.is_next_sync_committee_known()
+            && update.is_sync_committee_update()
+            && update.is_finality_update()
+            && compute_sync_committee_period_at_slot::<E>(update.finalized_header.beacon.slot)
+                == compute_sync_committee_period_at_slot::<E>(update.attested_header.beacon.slot);
+        if num_active_participants * 3 >= E::SYNC_COMMITTEE_SIZE * 2
+            && (update.finalized_header.beacon.slot > self.finalized_header.beacon.slot
+                || update_has_finalized_next_sync_committee)
+        {
+            // Normal update through 2/3 threshold
+            self.apply_light_client_update(update)?;
+            self.best_valid_update = None;
+        }
+
+        Ok(())
+    }
+
+    pub fn process_light_client_finality_update(
+        &mut self,
+        finality_update: &LightClientFinalityUpdate<E>,
+        current_slot: u64,
+        genesis_validators_root: B256,
+        spec: &ChainSpec,
+    ) -> anyhow::Result<()> {
+        let update = LightClientUpdate {
+            attested_header: finality_update.attested_header.clone(),
+            next_sync_committee: SyncCommittee::default(),
+            next_sync_committee_branch: FixedVector::default(),
+            finalized_header: finality_update.finalized_header.clone(),
+            finality_branch: finality_update.finality_branch.clone(),
+            sync_aggregate: finality_update.sync_aggregate.clone(),
+            signature_slot: finality_update.signature_slot,
+        };
+        self.process_light_client_update(&update, current_slot, genesis_validators_root, spec)
+    }
+
+    pub fn process_light_client_optimistic_update(
+        &mut self,
+        optimistic_update: &LightClientOptimisticUpdate<E>,
+        current_slot: u64,
+        genesis_validators_root: B256,
+        spec: &ChainSpec,
+    ) -> anyhow::Result<()> {
+        let update = LightClientUpdate {
+            attested_header: optimistic_update.attested_header.clone(),
+            next_sync_committee: SyncCommittee::default(),
+            next_sync_committee_branch: FixedVector::default(),
+            finalized_header: LightClientHeader::default(),
+            finality_branch: FixedVector::default(),
+            sync_aggregate: optimistic_update.sync_aggregate.clone(),
+            signature_slot: optimistic_update.signature_slot,
+        };
+        self.process_light_client_update(&update, current_slot, genesis_validators_root, spec)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ream_bls::{BLSSignature, PubKey};
+    use ream_consensus::{
+        beacon_block_header::BeaconBlockHeader, eth_spec::MinimalEthSpec,
+        sync_aggregate::SyncAggregate,
+    };
+    use ssz_types::BitVector;
+
+    use super::*;
+
+    fn header(slot: u64) -> LightClientHeader {
+        LightClientHeader {
+            beacon: BeaconBlockHeader {
+                slot,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn sync_committee(byte: u8) -> SyncCommittee<MinimalEthSpec> {
+        SyncCommittee {
+            aggregate_pubkey: PubKey {
+                inner: FixedVector::from(vec![byte; 48]),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn store(finalized_slot: u64) -> LightClientStore<MinimalEthSpec> {
+        LightClientStore {
+            finalized_header: header(finalized_slot),
+            current_sync_committee: sync_committee(1),
+            next_sync_committee: SyncCommittee::default(),
+            best_valid_update: None,
+            optimistic_header: header(finalized_slot),
+            previous_max_active_participants: 0,
+            current_max_active_participants: 0,
+        }
+    }
+
+    /// Build an update of the minimal preset, where a sync committee period is 64 slots.
+    fn update(
+        num_participants: usize,
+        attested_slot: u64,
+        finalized_slot: u64,
+        next_sync_committee: SyncCommittee<MinimalEthSpec>,
+    ) -> LightClientUpdate<MinimalEthSpec> {
+        let mut sync_committee_bits = BitVector::new();
+        for index in 0..num_participants {
+            sync_committee_bits.set(index, true).unwrap();
+        }
+        LightClientUpdate {
+            attested_header: header(attested_slot),
+            next_sync_committee,
+            next_sync_committee_branch: FixedVector::default(),
+            finalized_header: header(finalized_slot),
+            finality_branch: FixedVector::default(),
+            sync_aggregate: SyncAggregate {
+                sync_committee_bits,
+                sync_committee_signature: BLSSignature::infinity(),
+            },
+            signature_slot: attested_slot + 1,
+        }
+    }
+
+    #[test]
+    fn test_apply_light_client_update() {
+        let mut store = store(10);
+
+        // Without a known next sync committee, the update must be finalized in the store period
+        assert!(store
+            .clone()
+            .apply_light_client_update(&update(32, 80, 70, sync_committee(2)))
+            .is_err());
+
+        // The first update of the period only learns the next sync committee
+        store
+            .apply_light_client_update(&update(32, 20, 12, sync_committee(2)))
+            .unwrap();
+        assert!(store.is_next_sync_committee_known());
+        assert_eq!(store.current_sync_committee, sync_committee(1));
+        assert_eq!(store.next_sync_committee, sync_committee(2));
+        assert_eq!(store.finalized_header.beacon.slot, 12);
+        assert_eq!(store.optimistic_header.beacon.slot, 12);
+
+        // An update finalized in the next period rotates the sync committees
+        store.current_max_active_participants = 20;
+        store
+            .apply_light_client_update(&update(32, 80, 70, sync_committee(3)))
+            .unwrap();
+        assert_eq!(store.current_sync_committee, sync_committee(2));
+        assert_eq!(store.next_sync_committee, sync_committee(3));
+        assert_eq!(store.previous_max_active_participants, 20);
+        assert_eq!(store.current_max_active_participants, 0);
+        assert_eq!(store.get_safety_threshold(), 10);
+        assert_eq!(store.finalized_header.beacon.slot, 70);
+        assert_eq!(store.optimistic_header.beacon.slot, 70);
+    }
+
+    #[test]
+    fn test_process_light_client_store_force_update() {
+        let mut store = store(10);
+        store.best_valid_update = Some(update(10, 30, 0, sync_committee(2)));
+
+        // Nothing is forced until the update timeout has elapsed
+        store
+            .process_light_client_store_force_update(10 + MinimalEthSpec::UPDATE_TIMEOUT)
+            .unwrap();
+        assert!(store.best_valid_update.is_some());
+        assert_eq!(store.finalized_header.beacon.slot, 10);
+
+        // The attested header of the best update is then treated as finalized
+        store
+            .process_light_client_store_force_update(11 + MinimalEthSpec::UPDATE_TIMEOUT)
+            .unwrap();
+        assert!(store.best_valid_update.is_none());
+        assert_eq!(store.finalized_header.beacon.slot, 30);
+        assert_eq!(store.optimistic_header.beacon.slot, 30);
+        assert_eq!(store.next_sync_committee, sync_committee(2));
+    }
+
+    #[test]
+    fn test_validate_light_client_update_rejections() {
+        let store = store(10);
+        let spec = ChainSpec::minimal();
+        let validation_error = |update: LightClientUpdate<MinimalEthSpec>, current_slot| {
+            store
+                .validate_light_client_update(&update, current_slot, B256::ZERO, &spec)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            validation_error(update(0, 20, 0, SyncCommittee::default()), 30),
+            "Not enough sync committee participants"
+        );
+        assert_eq!(
+            validation_error(update(32, 20, 0, SyncCommittee::default()), 20),
+            "Update slots are out of order"
+        );
+        assert_eq!(
+            validation_error(update(32, 70, 0, SyncCommittee::default()), 80),
+            "Update signature period is not the current period"
+        );
+        assert_eq!(
+            validation_error(update(32, 8, 0, SyncCommittee::default()), 30),
+            "Update is not relevant"
+        );
+        assert_eq!(
+            validation_error(update(32, 20, 12, SyncCommittee::default()), 30),
+            "Finalized header must be empty without a finality branch"
+        );
+    }
+}
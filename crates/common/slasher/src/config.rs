@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+/// Number of epochs of attestation and block history kept by default, about 18 days on mainnet.
+pub const DEFAULT_HISTORY_LENGTH: u64 = 4096;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlasherConfig {
+    /// Path of the database holding the slasher history.
+    pub database_path: PathBuf,
+
+    /// Number of epochs of history to check new attestations and blocks against. Older records
+    /// are pruned.
+    pub history_length: u64,
+}
+
+impl SlasherConfig {
+    pub fn new(database_path: PathBuf) -> Self {
+        Self {
+            database_path,
+            history_length: DEFAULT_HISTORY_LENGTH,
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use std::marker::PhantomData;
+
+use anyhow::{anyhow, ensure};
+use ream_consensus::{
+    attester_slashing::AttesterSlashing,
+    beacon_block_header::SignedBeaconBlockHeader,
+    deneb::{beacon_block::SignedBeaconBlock, beacon_state::BeaconState},
+    eth_spec::EthSpec,
+    indexed_attestation::IndexedAttestation,
+    misc::compute_start_slot_at_epoch,
+    predicates::is_slashable_attestation_data,
+    proposer_slashing::ProposerSlashing,
+};
+use redb::{Database, ReadableTable, Table, TableDefinition, WriteTransaction};
+use ssz::{Decode, Encode};
+use tree_hash::TreeHash;
+
+use crate::config::SlasherConfig;
+
+/// Number of epochs of a validator's min or max spans stored in a single database entry.
+const CHUNK_SIZE: u64 = 16;
+
+/// Span value of an epoch for which no attestation sets a bound.
+const MIN_SPAN_NONE: u16 = u16::MAX;
+const MAX_SPAN_NONE: u16 = 0;
+
+/// ``(validator_index, target_epoch) -> attestation_root``
+const ATTESTER_RECORDS: TableDefinition<(u64, u64), [u8; 32]> =
+    TableDefinition::new("attester_records");
+
+/// ``(target_epoch, attestation_root) -> IndexedAttestation``
+const INDEXED_ATTESTATIONS: TableDefinition<(u64, [u8; 32]), &[u8]> =
+    TableDefinition::new("indexed_attestations");
+
+/// ``(validator_index, chunk_index) -> [u16; CHUNK_SIZE]``
+///
+/// The min span of an epoch is the smallest distance from it to the target of an attestation
+/// whose source is after it.
+const MIN_SPANS: TableDefinition<(u64, u64), &[u8]> = TableDefinition::new("min_spans");
+
+/// ``(validator_index, chunk_index) -> [u16; CHUNK_SIZE]``
+///
+/// The max span of an epoch is the largest distance from it to the target of an attestation
+/// whose source is before it.
+const MAX_SPANS: TableDefinition<(u64, u64), &[u8]> = TableDefinition::new("max_spans");
+
+/// ``(slot, proposer_index) -> SignedBeaconBlockHeader``
+const PROPOSALS: TableDefinition<(u64, u64), &[u8]> = TableDefinition::new("proposals");
+
+type SpanTable<'txn> = Table<'txn, (u64, u64), &'static [u8]>;
+
+/// Detects slashable attestations and block proposals among everything seen on gossip and in
+/// blocks.
+///
+/// Attestations are indexed by validator and target epoch to find double votes, and min-max span
+/// arrays per validator find surround votes without scanning the validator's history. The history
+/// is kept in a database, so it survives restarts, and is pruned to
+/// ``SlasherConfig.history_length`` epochs.
+pub struct Slasher<E: EthSpec> {
+    config: SlasherConfig,
+    database: Database,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Slasher<E> {
+    pub fn open(config: SlasherConfig) -> anyhow::Result<Self> {
+        ensure!(
+            config.history_length > 0 && config.history_length < u64::from(u16::MAX),
+            "History length must be between 1 and {}",
+            u16::MAX - 1
+        );
+
+        let database = Database::create(&config.database_path)?;
+        let transaction = database.begin_write()?;
+        transaction.open_table(ATTESTER_RECORDS)?;
+        transaction.open_table(INDEXED_ATTESTATIONS)?;
+        transaction.open_table(MIN_SPANS)?;
+        transaction.open_table(MAX_SPANS)?;
+        transaction.open_table(PROPOSALS)?;
+        transaction.commit()?;
+
+        Ok(Self {
+            config,
+            database,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Return the first epoch of the history window at ``current_epoch``.
+    fn window_start(&self, current_epoch: u64) -> u64 {
+        current_epoch.saturating_sub(self.config.history_length)
+    }
+
+    /// Record ``attestations`` and return the slashings they cause against the history.
+    pub fn process_attestations(
+        &self,
+        attestations: &[IndexedAttestation],
+        current_epoch: u64,
+    ) -> anyhow::Result<Vec<AttesterSlashing>> {
+        let window_start = self.window_start(current_epoch);
+        let transaction = self.database.begin_write()?;
+        let mut attester_slashings = vec![];
+        for attestation in attestations {
+            if attestation.data.target.epoch < window_start
+                || attestation.data.source.epoch > attestation.data.target.epoch
+            {
+                continue;
+            }
+            for attester_slashing in process_attestation(&transaction, attestation, window_start)? {
+                if !attester_slashings.contains(&attester_slashing) {
+                    attester_slashings.push(attester_slashing);
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(attester_slashings)
+    }
+
+    /// Record ``block_headers`` and return the slashings of proposers that signed two different
+    /// blocks for the same slot.
+    pub fn process_block_headers(
+        &self,
+        block_headers: &[SignedBeaconBlockHeader],
+        current_epoch: u64,
+    ) -> anyhow::Result<Vec<ProposerSlashing>> {
+        let window_start_slot = compute_start_slot_at_epoch::<E>(self.window_start(current_epoch));
+        let transaction = self.database.begin_write()?;
+        let mut proposer_slashings = vec![];
+        {
+            let mut proposals = transaction.open_table(PROPOSALS)?;
+            for block_header in block_headers {
+                if block_header.message.slot < window_start_slot {
+                    continue;
+                }
+                let key = (
+                    block_header.message.slot,
+                    block_header.message.proposer_index,
+                );
+                let existing = proposals
+                    .get(key)?
+                    .map(|bytes| SignedBeaconBlockHeader::from_ssz_bytes(bytes.value()))
+                    .transpose()
+                    .map_err(|err| anyhow!("Failed to decode block header: {err:?}"))?;
+                match existing {
+                    Some(existing) => {
+                        if existing.message != block_header.message {
+                            proposer_slashings.push(ProposerSlashing {
+                                signed_header_1: existing,
+                                signed_header_2: block_header.clone(),
+                            });
+                        }
+                    }
+                    None => {
+                        proposals.insert(key, block_header.as_ssz_bytes().as_slice())?;
+                    }
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(proposer_slashings)
+    }
+
+    /// Record the header and attestations of ``block``, whose attestations are resolved against
+    /// ``state``.
+    pub fn process_block(
+        &self,
+        state: &BeaconState<E>,
+        block: &SignedBeaconBlock<E>,
+        current_epoch: u64,
+    ) -> anyhow::Result<(Vec<ProposerSlashing>, Vec<AttesterSlashing>)> {
+        let proposer_slashings =
+            self.process_block_headers(&[block.compute_signed_block_header()], current_epoch)?;
+        let attestations = block
+            .message
+            .body
+            .attestations
+            .iter()
+            .map(|attestation| state.get_indexed_attestation(attestation))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let attester_slashings = self.process_attestations(&attestations, current_epoch)?;
+        Ok((proposer_slashings, attester_slashings))
+    }
+
+    /// Remove the records that are older than the history window at ``current_epoch``.
+    pub fn prune(&self, current_epoch: u64) -> anyhow::Result<()> {
+        let window_start = self.window_start(current_epoch);
+        let window_start_chunk = window_start / CHUNK_SIZE;
+        let window_start_slot = compute_start_slot_at_epoch::<E>(window_start);
+
+        let transaction = self.database.begin_write()?;
+        transaction
+            .open_table(ATTESTER_RECORDS)?
+            .retain(|(_, target_epoch), _| target_epoch >= window_start)?;
+        transaction
+            .open_table(INDEXED_ATTESTATIONS)?
+            .retain(|(target_epoch, _), _| target_epoch >= window_start)?;
+        transaction
+            .open_table(MIN_SPANS)?
+            .retain(|(_, chunk_index), _| chunk_index >= window_start_chunk)?;
+        transaction
+            .open_table(MAX_SPANS)?
+            .retain(|(_, chunk_index), _| chunk_index >= window_start_chunk)?;
+        transaction
+            .open_table(PROPOSALS)?
+            .retain(|(slot, _), _| slot >= window_start_slot)?;
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+/// Check ``attestation`` against the history of each of its attesters, then add it to the
+/// history.
+fn process_attestation(
+    transaction: &WriteTransaction,
+    attestation: &IndexedAttestation,
+    window_start: u64,
+) -> anyhow::Result<Vec<AttesterSlashing>> {
+    let mut attester_records = transaction.open_table(ATTESTER_RECORDS)?;
+    let mut indexed_attestations = transaction.open_table(INDEXED_ATTESTATIONS)?;
+    let mut min_spans = transaction.open_table(MIN_SPANS)?;
+    let mut max_spans = transaction.open_table(MAX_SPANS)?;
+
+    let source = attestation.data.source.epoch;
+    let target = attestation.data.target.epoch;
+    let distance = target - source;
+    let attestation_root = attestation.tree_hash_root().0;
+
+    let get_attester_record =
+        |validator_index: u64, target_epoch: u64| -> anyhow::Result<Option<IndexedAttestation>> {
+            let Some(root) = attester_records
+                .get((validator_index, target_epoch))?
+                .map(|root| root.value())
+            else {
+                return Ok(None);
+            };
+            indexed_attestations
+                .get((target_epoch, root))?
+                .map(|bytes| IndexedAttestation::from_ssz_bytes(bytes.value()))
+                .transpose()
+                .map_err(|err| anyhow!("Failed to decode indexed attestation: {err:?}"))
+        };
+
+    let mut attester_slashings = vec![];
+    let mut new_attesters = vec![];
+    for &validator_index in attestation.attesting_indices.iter() {
+        // Double vote
+        if let Some(existing) = get_attester_record(validator_index, target)? {
+            if existing.data != attestation.data {
+                attester_slashings.push(AttesterSlashing {
+                    attestation_1: existing,
+                    attestation_2: attestation.clone(),
+                });
+            }
+            continue;
+        }
+        new_attesters.push(validator_index);
+
+        if source < window_start {
+            continue;
+        }
+
+        // ``attestation`` surrounds an attestation with a later source and an earlier target
+        let min_span = get_span(&min_spans, validator_index, source, MIN_SPAN_NONE)?;
+        if min_span != MIN_SPAN_NONE && u64::from(min_span) < distance {
+            if let Some(existing) = get_attester_record(validator_index, source + min_span as u64)?
+            {
+                if is_slashable_attestation_data(&attestation.data, &existing.data) {
+                    attester_slashings.push(AttesterSlashing {
+                        attestation_1: attestation.clone(),
+                        attestation_2: existing,
+                    });
+                    continue;
+                }
+            }
+        }
+
+        // ``attestation`` is surrounded by an attestation with an earlier source and a later
+        // target
+        let max_span = get_span(&max_spans, validator_index, source, MAX_SPAN_NONE)?;
+        if u64::from(max_span) > distance {
+            if let Some(existing) = get_attester_record(validator_index, source + max_span as u64)?
+            {
+                if is_slashable_attestation_data(&existing.data, &attestation.data) {
+                    attester_slashings.push(AttesterSlashing {
+                        attestation_1: existing,
+                        attestation_2: attestation.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    if new_attesters.is_empty() {
+        return Ok(attester_slashings);
+    }
+    indexed_attestations.insert(
+        (target, attestation_root),
+        attestation.as_ssz_bytes().as_slice(),
+    )?;
+    for validator_index in new_attesters {
+        attester_records.insert((validator_index, target), attestation_root)?;
+
+        // Spans are monotonic, so updates stop at the first epoch that is already bounded
+        // tighter.
+        for epoch in (window_start..source).rev() {
+            let span = to_span(target - epoch);
+            if span >= get_span(&min_spans, validator_index, epoch, MIN_SPAN_NONE)? {
+                break;
+            }
+            set_span(&mut min_spans, validator_index, epoch, span, MIN_SPAN_NONE)?;
+        }
+        for epoch in window_start.max(source + 1)..target {
+            let span = to_span(target - epoch);
+            if span <= get_span(&max_spans, validator_index, epoch, MAX_SPAN_NONE)? {
+                break;
+            }
+            set_span(&mut max_spans, validator_index, epoch, span, MAX_SPAN_NONE)?;
+        }
+    }
+
+    Ok(attester_slashings)
+}
+
+fn to_span(distance: u64) -> u16 {
+    distance.min(u64::from(u16::MAX - 1)) as u16
+}
+
+fn get_span(
+    table: &SpanTable,
+    validator_index: u64,
+    epoch: u64,
+    default: u16,
+) -> anyhow::Result<u16> {
+    let Some(chunk) = table.get((validator_index, epoch / CHUNK_SIZE))? else {
+        return Ok(default);
+    };
+    let offset = (epoch % CHUNK_SIZE) as usize * 2;
+    let chunk = chunk.value();
+    Ok(u16::from_le_bytes([chunk[offset], chunk[offset + 1]]))
+}
+
+fn set_span(
+    table: &mut SpanTable,
+    validator_index: u64,
+    epoch: u64,
+    span: u16,
+    default: u16,
+) -> anyhow::Result<()> {
+    let key = (validator_index, epoch / CHUNK_SIZE);
+    let mut chunk = match table.get(key)? {
+        Some(chunk) => chunk.value().to_vec(),
+        None => default.to_le_bytes().repeat(CHUNK_SIZE as usize),
+    };
+    let offset = (epoch % CHUNK_SIZE) as usize * 2;
+    chunk[offset..offset + 2].copy_from_slice(&span.to_le_bytes());
+    table.insert(key, chunk.as_slice())?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::B256;
+    use ream_bls::BLSSignature;
+    use ream_consensus::{
+        attestation_data::AttestationData, beacon_block_header::BeaconBlockHeader,
+        checkpoint::Checkpoint, eth_spec::MinimalEthSpec,
+    };
+    use ssz_types::VariableList;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Open a slasher on a fresh database, which is deleted when the returned directory is
+    /// dropped.
+    fn open_slasher() -> (TempDir, Slasher<MinimalEthSpec>) {
+        let database_dir = TempDir::new().unwrap();
+        let slasher =
+            Slasher::open(SlasherConfig::new(database_dir.path().join("slasher.redb"))).unwrap();
+        (database_dir, slasher)
+    }
+
+    fn attestation(indices: Vec<u64>, source: u64, target: u64, root: u8) -> IndexedAttestation {
+        IndexedAttestation {
+            attesting_indices: VariableList::from(indices),
+            data: AttestationData {
+                slot: target * MinimalEthSpec::SLOTS_PER_EPOCH,
+                index: 0,
+                beacon_block_root: B256::repeat_byte(root),
+                source: Checkpoint {
+                    epoch: source,
+                    root: B256::ZERO,
+                },
+                target: Checkpoint {
+                    epoch: target,
+                    root: B256::repeat_byte(root),
+                },
+            },
+            signature: BLSSignature::default(),
+        }
+    }
+
+    #[test]
+    fn test_double_vote() {
+        let (_database_dir, slasher) = open_slasher();
+        let attestation_1 = attestation(vec![1, 2], 1, 2, 1);
+        let attestation_2 = attestation(vec![2, 3], 1, 2, 2);
+        assert!(slasher
+            .process_attestations(&[attestation_1.clone()], 3)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            slasher
+                .process_attestations(&[attestation_2.clone()], 3)
+                .unwrap(),
+            vec![AttesterSlashing {
+                attestation_1,
+                attestation_2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_surround_vote() {
+        let (_database_dir, slasher) = open_slasher();
+        let inner = attestation(vec![1], 3, 4, 1);
+        let outer = attestation(vec![1], 2, 6, 2);
+        let surrounded = attestation(vec![1], 4, 5, 3);
+        slasher.process_attestations(&[inner.clone()], 6).unwrap();
+        assert_eq!(
+            slasher.process_attestations(&[outer.clone()], 6).unwrap(),
+            vec![AttesterSlashing {
+                attestation_1: outer.clone(),
+                attestation_2: inner,
+            }]
+        );
+        assert_eq!(
+            slasher
+                .process_attestations(&[surrounded.clone()], 6)
+                .unwrap(),
+            vec![AttesterSlashing {
+                attestation_1: outer,
+                attestation_2: surrounded,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_double_proposal_across_restarts() {
+        let database_dir = TempDir::new().unwrap();
+        let database_path = database_dir.path().join("slasher.redb");
+        let header = |body_root: u8| SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                slot: 9,
+                proposer_index: 4,
+                body_root: B256::repeat_byte(body_root),
+                ..Default::default()
+            },
+            signature: BLSSignature::default(),
+        };
+
+        let slasher =
+            Slasher::<MinimalEthSpec>::open(SlasherConfig::new(database_path.clone())).unwrap();
+        assert!(slasher
+            .process_block_headers(&[header(1)], 1)
+            .unwrap()
+            .is_empty());
+        drop(slasher);
+
+        let slasher = Slasher::<MinimalEthSpec>::open(SlasherConfig::new(database_path)).unwrap();
+        assert!(slasher
+            .process_block_headers(&[header(1)], 1)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            slasher.process_block_headers(&[header(2)], 1).unwrap(),
+            vec![ProposerSlashing {
+                signed_header_1: header(1),
+                signed_header_2: header(2),
+            }]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use anyhow::anyhow;
+use bip39::Mnemonic;
+use unicode_normalization::UnicodeNormalization;
+
+/// Return the BIP-39 seed of ``mnemonic`` and ``passphrase``, after checking that the mnemonic is
+/// made of English words and has a valid checksum.
+pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> anyhow::Result<[u8; 64]> {
+    let mnemonic = Mnemonic::parse_normalized(&mnemonic.nfkd().collect::<String>())
+        .map_err(|err| anyhow!("Invalid mnemonic: {err}"))?;
+    Ok(mnemonic.to_seed_normalized(&passphrase.nfkd().collect::<String>()))
+}
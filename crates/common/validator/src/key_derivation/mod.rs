@@ -0,0 +1,181 @@
+//! Key derivation for validator keys: BIP-39 mnemonics are turned into seeds, from which the
+//! EIP-2333 tree of BLS secret keys is derived along EIP-2334 paths.
+
+pub mod mnemonic;
+pub mod path;
+
+use alloy_primitives::ruint::Uint;
+use anyhow::{anyhow, ensure};
+use hkdf::Hkdf;
+use ream_bls::{constants::CURVE_ORDER, SecretKey};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+type U384 = Uint<384, 6>;
+
+/// Number of 32 byte chunks in each half of a Lamport secret key.
+const LAMPORT_CHUNKS: usize = 255;
+
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+
+/// Derive the master secret key of the EIP-2333 tree of ``seed``, which must be at least 32 bytes.
+pub fn derive_master_sk(seed: &[u8]) -> anyhow::Result<SecretKey> {
+    ensure!(
+        seed.len() >= 32,
+        "Seed must be at least 32 bytes, got {}",
+        seed.len()
+    );
+    hkdf_mod_r(seed, b"")
+}
+
+/// Derive the child at ``index`` of ``parent_sk`` in the EIP-2333 tree.
+pub fn derive_child_sk(parent_sk: &SecretKey, index: u32) -> anyhow::Result<SecretKey> {
+    let compressed_lamport_pk = parent_sk_to_lamport_pk(parent_sk, index)?;
+    hkdf_mod_r(&compressed_lamport_pk, b"")
+}
+
+/// Hash ``ikm`` to a nonzero scalar modulo ``r``, as ``KeyGen`` of the BLS signature draft
+/// standard.
+fn hkdf_mod_r(ikm: &[u8], key_info: &[u8]) -> anyhow::Result<SecretKey> {
+    let mut ikm = [ikm, &[0]].concat();
+    let info = [key_info, &48u16.to_be_bytes()].concat();
+    let curve_order = U384::from_be_slice(&CURVE_ORDER);
+
+    let mut salt = KEYGEN_SALT.to_vec();
+    loop {
+        salt = Sha256::digest(&salt).to_vec();
+
+        let mut okm = [0; 48];
+        Hkdf::<Sha256>::new(Some(&salt), &ikm)
+            .expand(&info, &mut okm)
+            .map_err(|err| anyhow!("Failed to expand key material: {err}"))?;
+        let mut secret_key = (U384::from_be_slice(&okm) % curve_order).to_be_bytes::<48>();
+        okm.zeroize();
+
+        if secret_key != [0; 48] {
+            let result = SecretKey::from_bytes(&secret_key[16..])
+                .map_err(|err| anyhow!("Failed to derive secret key: {err}"));
+            secret_key.zeroize();
+            ikm.zeroize();
+            return result;
+        }
+    }
+}
+
+fn ikm_to_lamport_sk(ikm: &[u8], salt: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut okm = vec![0; 32 * LAMPORT_CHUNKS];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(&[], &mut okm)
+        .map_err(|err| anyhow!("Failed to expand key material: {err}"))?;
+    Ok(okm)
+}
+
+/// Return the compressed Lamport public key that ``parent_sk`` derives the child at ``index``
+/// from.
+fn parent_sk_to_lamport_pk(parent_sk: &SecretKey, index: u32) -> anyhow::Result<[u8; 32]> {
+    let salt = index.to_be_bytes();
+    let ikm = parent_sk.to_bytes();
+    let mut not_ikm = ikm.iter().map(|byte| !byte).collect::<Vec<_>>();
+
+    let mut lamport_pk = Sha256::new();
+    for ikm in [ikm, not_ikm.as_slice()] {
+        let mut lamport_sk = ikm_to_lamport_sk(ikm, &salt)?;
+        for chunk in lamport_sk.chunks_exact(32) {
+            lamport_pk.update(Sha256::digest(chunk));
+        }
+        lamport_sk.zeroize();
+    }
+    not_ikm.zeroize();
+
+    Ok(lamport_pk.finalize().into())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::{hex, U256};
+
+    use super::{mnemonic::mnemonic_to_seed, path::derive_sk_from_path, *};
+
+    fn assert_secret_key(secret_key: &SecretKey, expected: &str) {
+        assert_eq!(
+            secret_key.to_bytes(),
+            expected.parse::<U256>().unwrap().to_be_bytes::<32>()
+        );
+    }
+
+    /// Test cases of EIP-2333
+    #[test]
+    fn test_derive_sk() {
+        let seed = mnemonic_to_seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon about",
+            "TREZOR",
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(seed),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        let test_cases = [
+            (
+                seed.to_vec(),
+                "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+                0,
+                "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+            ),
+            (
+                hex::decode("3141592653589793238462643383279502884197169399375105820974944592")
+                    .unwrap(),
+                "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+                3141592653,
+                "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+            ),
+            (
+                hex::decode("0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00")
+                    .unwrap(),
+                "27580842291869792442942448775674722299803720648445448686099262467207037398656",
+                4294967295,
+                "29358610794459428860402234341874281240803786294062035874021252734817515685787",
+            ),
+            (
+                hex::decode("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+                    .unwrap(),
+                "19022158461524446591288038168518313374041767046816487870552872741050760015818",
+                42,
+                "31372231650479070279774297061823572166496564838472787488249775572789064611981",
+            ),
+        ];
+
+        for (seed, master_sk, child_index, child_sk) in test_cases {
+            let derived_master_sk = derive_master_sk(&seed).unwrap();
+            assert_secret_key(&derived_master_sk, master_sk);
+            assert_secret_key(
+                &derive_child_sk(&derived_master_sk, child_index).unwrap(),
+                child_sk,
+            );
+        }
+
+        assert!(derive_master_sk(&[0; 31]).is_err());
+    }
+
+    #[test]
+    fn test_derive_sk_from_path() {
+        let seed = [1; 32];
+        let mut expected = derive_master_sk(&seed).unwrap();
+        for index in [12381, 3600, 5, 0, 0] {
+            expected = derive_child_sk(&expected, index).unwrap();
+        }
+
+        assert_eq!(
+            derive_sk_from_path(&seed, "m/12381/3600/5/0/0")
+                .unwrap()
+                .to_bytes(),
+            expected.to_bytes()
+        );
+        assert!(derive_sk_from_path(&seed, "12381/3600/5/0/0").is_err());
+        assert!(derive_sk_from_path(&seed, "m/12381/3600/-5/0/0").is_err());
+        assert!(derive_sk_from_path(&seed, "m/44/3600/5/0/0").is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::{anyhow, ensure};
+use ream_bls::SecretKey;
+
+use super::{derive_child_sk, derive_master_sk};
+
+/// ``purpose`` of EIP-2334 paths, the first index after the master node.
+pub const PURPOSE: u32 = 12381;
+
+/// ``coin_type`` of Ethereum.
+pub const COIN_TYPE: u32 = 3600;
+
+/// Return the EIP-2334 path of the withdrawal key of the validator at ``index``.
+pub fn withdrawal_key_path(index: u32) -> String {
+    format!("m/{PURPOSE}/{COIN_TYPE}/{index}/0")
+}
+
+/// Return the EIP-2334 path of the signing key of the validator at ``index``.
+pub fn signing_key_path(index: u32) -> String {
+    format!("m/{PURPOSE}/{COIN_TYPE}/{index}/0/0")
+}
+
+/// Parse an EIP-2334 path such as ``m/12381/3600/0/0/0`` into the child indices to derive from
+/// the master node.
+pub fn parse_path(path: &str) -> anyhow::Result<Vec<u32>> {
+    let mut nodes = path.split('/');
+    ensure!(
+        nodes.next() == Some("m"),
+        "Path must start with the master node m: {path}"
+    );
+
+    let indices = nodes
+        .map(|node| {
+            node.parse::<u32>()
+                .map_err(|err| anyhow!("Invalid index {node} in path {path}: {err}"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    ensure!(
+        indices.len() >= 4,
+        "Path must have at least the purpose, coin_type, account and use levels: {path}"
+    );
+    ensure!(
+        indices[0] == PURPOSE,
+        "Path must have purpose {PURPOSE}: {path}"
+    );
+    ensure!(
+        indices[1] == COIN_TYPE,
+        "Path must have coin_type {COIN_TYPE}: {path}"
+    );
+
+    Ok(indices)
+}
+
+/// Derive the secret key at ``path`` in the EIP-2333 tree of ``seed``.
+pub fn derive_sk_from_path(seed: &[u8], path: &str) -> anyhow::Result<SecretKey> {
+    parse_path(path)?
+        .into_iter()
+        .try_fold(derive_master_sk(seed)?, |secret_key, index| {
+            derive_child_sk(&secret_key, index)
+        })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path(&signing_key_path(5)).unwrap(),
+            vec![12381, 3600, 5, 0, 0]
+        );
+        assert_eq!(
+            parse_path(&withdrawal_key_path(5)).unwrap(),
+            vec![12381, 3600, 5, 0]
+        );
+    }
+
+    #[test]
+    fn test_parse_path_rejections() {
+        for (path, error) in [
+            (
+                "12381/3600/0/0/0",
+                "Path must start with the master node m: 12381/3600/0/0/0",
+            ),
+            (
+                "m",
+                "Path must have at least the purpose, coin_type, account and use levels: m",
+            ),
+            (
+                "m/12381",
+                "Path must have at least the purpose, coin_type, account and use levels: m/12381",
+            ),
+            (
+                "m/12381/3600/x/0",
+                "Invalid index x in path m/12381/3600/x/0: invalid digit found in string",
+            ),
+            (
+                "m/12381/3600/0",
+                "Path must have at least the purpose, coin_type, account and use levels: \
+                 m/12381/3600/0",
+            ),
+            (
+                "m/44/3600/0/0/0",
+                "Path must have purpose 12381: m/44/3600/0/0/0",
+            ),
+            (
+                "m/12381/60/0/0/0",
+                "Path must have coin_type 3600: m/12381/60/0/0/0",
+            ),
+        ] {
+            assert_eq!(parse_path(path).unwrap_err().to_string(), error);
+        }
+    }
+}
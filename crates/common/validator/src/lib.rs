@@ -0,0 +1,2 @@
+pub mod key_derivation;
+pub mod slashing_protection;
@@ -0,0 +1,74 @@
+use alloy_primitives::{FixedBytes, B256};
+use serde::{Deserialize, Serialize};
+
+/// The only interchange format version defined by EIP-3076.
+pub const INTERCHANGE_FORMAT_VERSION: u64 = 5;
+
+/// Slashing protection history in the EIP-3076 interchange format.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Interchange {
+    pub metadata: InterchangeMetadata,
+    pub data: Vec<InterchangeData>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct InterchangeMetadata {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub interchange_format_version: u64,
+    pub genesis_validators_root: B256,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct InterchangeData {
+    pub pubkey: FixedBytes<48>,
+    pub signed_blocks: Vec<SignedBlock>,
+    pub signed_attestations: Vec<SignedAttestation>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SignedBlock {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_root: Option<B256>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SignedAttestation {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub source_epoch: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub target_epoch: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_root: Option<B256>,
+}
+
+/// The two interchange formats of EIP-3076.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum InterchangeFormat {
+    /// Only the latest block and attestation of each validator, which is enough to stay safe.
+    Minimal,
+
+    /// Every block and attestation in the database.
+    Complete,
+}
+
+impl InterchangeData {
+    pub fn new(pubkey: [u8; 48]) -> Self {
+        Self {
+            pubkey: FixedBytes::from(pubkey),
+            signed_blocks: vec![],
+            signed_attestations: vec![],
+        }
+    }
+}
+
+impl Interchange {
+    pub fn from_json_str(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json_string(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
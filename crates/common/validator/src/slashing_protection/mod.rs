@@ -0,0 +1,666 @@
+pub mod interchange;
+
+use std::{collections::BTreeMap, path::Path};
+
+use alloy_primitives::B256;
+use anyhow::{bail, ensure};
+use interchange::{
+    Interchange, InterchangeData, InterchangeFormat, InterchangeMetadata, SignedAttestation,
+    SignedBlock, INTERCHANGE_FORMAT_VERSION,
+};
+use ream_bls::PubKey;
+use ream_consensus::{
+    attestation_data::AttestationData, beacon_block_header::BeaconBlockHeader,
+    misc::compute_signing_root,
+};
+use redb::{Database, ReadableTable, TableDefinition};
+
+const GENESIS_VALIDATORS_ROOT_KEY: &str = "genesis_validators_root";
+
+const METADATA: TableDefinition<&str, [u8; 32]> = TableDefinition::new("metadata");
+
+/// ``(pubkey, slot) -> signing_root``
+const SIGNED_BLOCKS: TableDefinition<([u8; 48], u64), [u8; 32]> =
+    TableDefinition::new("signed_blocks");
+
+/// ``(pubkey, target_epoch) -> (source_epoch, signing_root)``
+const SIGNED_ATTESTATIONS: TableDefinition<([u8; 48], u64), (u64, [u8; 32])> =
+    TableDefinition::new("signed_attestations");
+
+/// Local record of every block and attestation signed by our validators, following the rules of
+/// EIP-3076.
+///
+/// Signing roots imported without a value are stored as zero and never match a new signing root,
+/// so a repeat signing of them is refused.
+pub struct SlashingProtectionDatabase {
+    database: Database,
+    genesis_validators_root: B256,
+}
+
+impl SlashingProtectionDatabase {
+    /// Open or create the database at ``path`` for the chain with ``genesis_validators_root``.
+    pub fn open(path: &Path, genesis_validators_root: B256) -> anyhow::Result<Self> {
+        let database = Database::create(path)?;
+        let transaction = database.begin_write()?;
+        {
+            let mut metadata = transaction.open_table(METADATA)?;
+            let stored_root = metadata
+                .get(GENESIS_VALIDATORS_ROOT_KEY)?
+                .map(|root| B256::from(root.value()));
+            match stored_root {
+                Some(stored_root) => ensure!(
+                    stored_root == genesis_validators_root,
+                    "Database is for genesis validators root {stored_root}, not \
+                     {genesis_validators_root}"
+                ),
+                None => {
+                    metadata.insert(GENESIS_VALIDATORS_ROOT_KEY, genesis_validators_root.0)?;
+                }
+            }
+            transaction.open_table(SIGNED_BLOCKS)?;
+            transaction.open_table(SIGNED_ATTESTATIONS)?;
+        }
+        transaction.commit()?;
+
+        Ok(Self {
+            database,
+            genesis_validators_root,
+        })
+    }
+
+    /// Check that signing ``block_header`` under ``domain`` with ``pubkey`` is safe and record it.
+    pub fn check_and_insert_block_proposal(
+        &self,
+        pubkey: &PubKey,
+        block_header: &BeaconBlockHeader,
+        domain: B256,
+    ) -> anyhow::Result<()> {
+        self.check_and_insert_block_signing_root(
+            pubkey,
+            block_header.slot,
+            compute_signing_root(block_header, domain),
+        )
+    }
+
+    /// Check that signing a block with ``signing_root`` at ``slot`` with ``pubkey`` is safe and
+    /// record it.
+    pub fn check_and_insert_block_signing_root(
+        &self,
+        pubkey: &PubKey,
+        slot: u64,
+        signing_root: B256,
+    ) -> anyhow::Result<()> {
+        let pubkey: [u8; 48] = pubkey.to_bytes().try_into()?;
+        let transaction = self.database.begin_write()?;
+        {
+            let mut signed_blocks = transaction.open_table(SIGNED_BLOCKS)?;
+            if let Some(existing_root) = signed_blocks.get((pubkey, slot))?.map(|root| root.value())
+            {
+                if !signing_root.is_zero() && existing_root == signing_root.0 {
+                    return Ok(());
+                }
+                bail!("Block at slot {slot} was already signed with a different signing root");
+            }
+
+            if let Some(entry) = signed_blocks
+                .range((pubkey, 0)..=(pubkey, u64::MAX))?
+                .next()
+            {
+                let (min_slot_key, _) = entry?;
+                let (_, min_slot) = min_slot_key.value();
+                ensure!(
+                    slot > min_slot,
+                    "Block at slot {slot} is not after the lowest signed slot {min_slot}"
+                );
+            }
+
+            signed_blocks.insert((pubkey, slot), signing_root.0)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Check that signing ``attestation_data`` under ``domain`` with ``pubkey`` is safe and
+    /// record it.
+    pub fn check_and_insert_attestation(
+        &self,
+        pubkey: &PubKey,
+        attestation_data: &AttestationData,
+        domain: B256,
+    ) -> anyhow::Result<()> {
+        self.check_and_insert_attestation_signing_root(
+            pubkey,
+            attestation_data.source.epoch,
+            attestation_data.target.epoch,
+            compute_signing_root(attestation_data, domain),
+        )
+    }
+
+    /// Check that signing an attestation with ``signing_root`` from ``source_epoch`` to
+    /// ``target_epoch`` with ``pubkey`` is safe and record it.
+    pub fn check_and_insert_attestation_signing_root(
+        &self,
+        pubkey: &PubKey,
+        source_epoch: u64,
+        target_epoch: u64,
+        signing_root: B256,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            source_epoch <= target_epoch,
+            "Source epoch {source_epoch} is after target epoch {target_epoch}"
+        );
+
+        let pubkey: [u8; 48] = pubkey.to_bytes().try_into()?;
+        let transaction = self.database.begin_write()?;
+        {
+            let mut signed_attestations = transaction.open_table(SIGNED_ATTESTATIONS)?;
+
+            // Double vote
+            if let Some((_, existing_root)) = signed_attestations
+                .get((pubkey, target_epoch))?
+                .map(|attestation| attestation.value())
+            {
+                if !signing_root.is_zero() && existing_root == signing_root.0 {
+                    return Ok(());
+                }
+                bail!(
+                    "Attestation with target epoch {target_epoch} was already signed with a \
+                     different signing root"
+                );
+            }
+
+            let mut min_source_epoch = u64::MAX;
+            let mut min_target_epoch = u64::MAX;
+            for entry in signed_attestations.range((pubkey, 0)..=(pubkey, u64::MAX))? {
+                let (key, value) = entry?;
+                let (_, existing_target_epoch) = key.value();
+                let (existing_source_epoch, _) = value.value();
+
+                ensure!(
+                    !(source_epoch < existing_source_epoch && existing_target_epoch < target_epoch),
+                    "Attestation surrounds the signed attestation from epoch \
+                     {existing_source_epoch} to {existing_target_epoch}"
+                );
+                ensure!(
+                    !(existing_source_epoch < source_epoch && target_epoch < existing_target_epoch),
+                    "Attestation is surrounded by the signed attestation from epoch \
+                     {existing_source_epoch} to {existing_target_epoch}"
+                );
+                min_source_epoch = min_source_epoch.min(existing_source_epoch);
+                min_target_epoch = min_target_epoch.min(existing_target_epoch);
+            }
+
+            if min_target_epoch != u64::MAX {
+                ensure!(
+                    source_epoch >= min_source_epoch,
+                    "Source epoch {source_epoch} is before the lowest signed source epoch \
+                     {min_source_epoch}"
+                );
+                ensure!(
+                    target_epoch > min_target_epoch,
+                    "Target epoch {target_epoch} is not after the lowest signed target epoch \
+                     {min_target_epoch}"
+                );
+            }
+
+            signed_attestations.insert((pubkey, target_epoch), (source_epoch, signing_root.0))?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Merge the history in ``interchange`` into the database.
+    ///
+    /// As EIP-3076 advises, only the latest block and attestation of each validator are imported
+    /// and the older records are pruned, so the latest ones act as a low watermark. Conflicting
+    /// records for the same slot or target epoch are stored without a signing root, so neither
+    /// can be signed again.
+    pub fn import_interchange(&self, interchange: &Interchange) -> anyhow::Result<()> {
+        ensure!(
+            interchange.metadata.interchange_format_version == INTERCHANGE_FORMAT_VERSION,
+            "Unsupported interchange format version {}",
+            interchange.metadata.interchange_format_version
+        );
+        ensure!(
+            interchange.metadata.genesis_validators_root == self.genesis_validators_root,
+            "Interchange is for genesis validators root {}, not {}",
+            interchange.metadata.genesis_validators_root,
+            self.genesis_validators_root
+        );
+
+        let transaction = self.database.begin_write()?;
+        {
+            let mut signed_blocks = transaction.open_table(SIGNED_BLOCKS)?;
+            let mut signed_attestations = transaction.open_table(SIGNED_ATTESTATIONS)?;
+            for data in &interchange.data {
+                let pubkey = data.pubkey.0;
+
+                if let Some(max_slot) = data.signed_blocks.iter().map(|block| block.slot).max() {
+                    let mut signing_roots = data
+                        .signed_blocks
+                        .iter()
+                        .filter(|block| block.slot == max_slot)
+                        .map(|block| block.signing_root.unwrap_or_default())
+                        .collect::<Vec<_>>();
+                    let mut pruned_slots = vec![];
+                    for entry in signed_blocks.range((pubkey, 0)..=(pubkey, max_slot))? {
+                        let (key, signing_root) = entry?;
+                        let (_, slot) = key.value();
+                        if slot == max_slot {
+                            signing_roots.push(B256::from(signing_root.value()));
+                        } else {
+                            pruned_slots.push(slot);
+                        }
+                    }
+                    for slot in pruned_slots {
+                        signed_blocks.remove((pubkey, slot))?;
+                    }
+                    signed_blocks
+                        .insert((pubkey, max_slot), merge_signing_roots(&signing_roots).0)?;
+                }
+
+                if let Some(max_target_epoch) = data
+                    .signed_attestations
+                    .iter()
+                    .map(|attestation| attestation.target_epoch)
+                    .max()
+                {
+                    let mut max_source_epoch = 0;
+                    let mut latest_attestations = vec![];
+                    for attestation in &data.signed_attestations {
+                        max_source_epoch = max_source_epoch.max(attestation.source_epoch);
+                        if attestation.target_epoch == max_target_epoch {
+                            latest_attestations.push((
+                                attestation.source_epoch,
+                                attestation.signing_root.unwrap_or_default(),
+                            ));
+                        }
+                    }
+                    let mut pruned_target_epochs = vec![];
+                    for entry in
+                        signed_attestations.range((pubkey, 0)..=(pubkey, max_target_epoch))?
+                    {
+                        let (key, value) = entry?;
+                        let (_, target_epoch) = key.value();
+                        let (source_epoch, signing_root) = value.value();
+                        max_source_epoch = max_source_epoch.max(source_epoch);
+                        if target_epoch == max_target_epoch {
+                            latest_attestations.push((source_epoch, B256::from(signing_root)));
+                        } else {
+                            pruned_target_epochs.push(target_epoch);
+                        }
+                    }
+                    // A watermark combining epochs of different attestations was never signed
+                    let signing_roots = latest_attestations
+                        .into_iter()
+                        .map(|(source_epoch, signing_root)| {
+                            if source_epoch == max_source_epoch {
+                                signing_root
+                            } else {
+                                B256::ZERO
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    for target_epoch in pruned_target_epochs {
+                        signed_attestations.remove((pubkey, target_epoch))?;
+                    }
+                    signed_attestations.insert(
+                        (pubkey, max_target_epoch),
+                        (max_source_epoch, merge_signing_roots(&signing_roots).0),
+                    )?;
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Export the history of every validator in the database in the interchange ``format``.
+    pub fn export_interchange(&self, format: InterchangeFormat) -> anyhow::Result<Interchange> {
+        let transaction = self.database.begin_read()?;
+        let mut data: BTreeMap<[u8; 48], InterchangeData> = BTreeMap::new();
+        let to_signing_root =
+            |root: [u8; 32]| Some(B256::from(root)).filter(|root| !root.is_zero());
+
+        for signed_block in transaction.open_table(SIGNED_BLOCKS)?.iter()? {
+            let (key, signing_root) = signed_block?;
+            let (pubkey, slot) = key.value();
+            data.entry(pubkey)
+                .or_insert_with(|| InterchangeData::new(pubkey))
+                .signed_blocks
+                .push(SignedBlock {
+                    slot,
+                    signing_root: to_signing_root(signing_root.value()),
+                });
+        }
+        for signed_attestation in transaction.open_table(SIGNED_ATTESTATIONS)?.iter()? {
+            let (key, value) = signed_attestation?;
+            let (pubkey, target_epoch) = key.value();
+            let (source_epoch, signing_root) = value.value();
+            data.entry(pubkey)
+                .or_insert_with(|| InterchangeData::new(pubkey))
+                .signed_attestations
+                .push(SignedAttestation {
+                    source_epoch,
+                    target_epoch,
+                    signing_root: to_signing_root(signing_root),
+                });
+        }
+
+        let mut data: Vec<InterchangeData> = data.into_values().collect();
+        if format == InterchangeFormat::Minimal {
+            for validator_data in &mut data {
+                validator_data.signed_blocks = validator_data
+                    .signed_blocks
+                    .iter()
+                    .map(|block| block.slot)
+                    .max()
+                    .map(|slot| SignedBlock {
+                        slot,
+                        signing_root: None,
+                    })
+                    .into_iter()
+                    .collect();
+                validator_data.signed_attestations = validator_data
+                    .signed_attestations
+                    .iter()
+                    .map(|attestation| attestation.source_epoch)
+                    .max()
+                    .zip(
+                        validator_data
+                            .signed_attestations
+                            .iter()
+                            .map(|attestation| attestation.target_epoch)
+                            .max(),
+                    )
+                    .map(|(source_epoch, target_epoch)| SignedAttestation {
+                        source_epoch,
+                        target_epoch,
+                        signing_root: None,
+                    })
+                    .into_iter()
+                    .collect();
+            }
+        }
+
+        Ok(Interchange {
+            metadata: InterchangeMetadata {
+                interchange_format_version: INTERCHANGE_FORMAT_VERSION,
+                genesis_validators_root: self.genesis_validators_root,
+            },
+            data,
+        })
+    }
+}
+
+/// The signing root shared by every record in ``signing_roots``, or zero if they differ.
+fn merge_signing_roots(signing_roots: &[B256]) -> B256 {
+    match signing_roots.split_first() {
+        Some((first, rest)) if rest.iter().all(|signing_root| signing_root == first) => *first,
+        _ => B256::ZERO,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::FixedBytes;
+    use ssz_types::FixedVector;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn open_database() -> (TempDir, SlashingProtectionDatabase) {
+        let database_dir = TempDir::new().unwrap();
+        let database = SlashingProtectionDatabase::open(
+            &database_dir.path().join("slashing_protection.redb"),
+            B256::repeat_byte(1),
+        )
+        .unwrap();
+        (database_dir, database)
+    }
+
+    fn pubkey() -> PubKey {
+        PubKey {
+            inner: FixedVector::from(vec![0xaa; 48]),
+        }
+    }
+
+    fn interchange(
+        signed_blocks: Vec<SignedBlock>,
+        signed_attestations: Vec<SignedAttestation>,
+    ) -> Interchange {
+        Interchange {
+            metadata: InterchangeMetadata {
+                interchange_format_version: INTERCHANGE_FORMAT_VERSION,
+                genesis_validators_root: B256::repeat_byte(1),
+            },
+            data: vec![InterchangeData {
+                pubkey: FixedBytes::from([0xaa; 48]),
+                signed_blocks,
+                signed_attestations,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_refuse_slashable_signatures() {
+        let (_database_dir, database) = open_database();
+        let pubkey = pubkey();
+        let root = |byte| B256::repeat_byte(byte);
+
+        database
+            .check_and_insert_block_signing_root(&pubkey, 10, root(1))
+            .unwrap();
+        database
+            .check_and_insert_block_signing_root(&pubkey, 10, root(1))
+            .unwrap();
+        assert!(database
+            .check_and_insert_block_signing_root(&pubkey, 10, root(2))
+            .is_err());
+        assert!(database
+            .check_and_insert_block_signing_root(&pubkey, 9, root(3))
+            .is_err());
+
+        database
+            .check_and_insert_attestation_signing_root(&pubkey, 2, 5, root(1))
+            .unwrap();
+        // Double vote
+        assert!(database
+            .check_and_insert_attestation_signing_root(&pubkey, 3, 5, root(2))
+            .is_err());
+        // Surrounded vote
+        assert!(database
+            .check_and_insert_attestation_signing_root(&pubkey, 3, 4, root(3))
+            .is_err());
+        // Surrounding vote
+        database
+            .check_and_insert_attestation_signing_root(&pubkey, 4, 6, root(4))
+            .unwrap();
+        assert!(database
+            .check_and_insert_attestation_signing_root(&pubkey, 3, 7, root(5))
+            .is_err());
+    }
+
+    #[test]
+    fn test_interchange_round_trip() {
+        let (_database_dir, database) = open_database();
+        let interchange = Interchange::from_json_str(&format!(
+            r#"{{
+                "metadata": {{
+                    "interchange_format_version": "5",
+                    "genesis_validators_root": "{}"
+                }},
+                "data": [
+                    {{
+                        "pubkey": "0x{}",
+                        "signed_blocks": [
+                            {{ "slot": "81952", "signing_root": "{}" }},
+                            {{ "slot": "81951" }}
+                        ],
+                        "signed_attestations": [
+                            {{ "source_epoch": "2290", "target_epoch": "3007", "signing_root": "{}" }},
+                            {{ "source_epoch": "2289", "target_epoch": "3006" }}
+                        ]
+                    }}
+                ]
+            }}"#,
+            B256::repeat_byte(1),
+            "aa".repeat(48),
+            B256::repeat_byte(2),
+            B256::repeat_byte(3),
+        ))
+        .unwrap();
+        database.import_interchange(&interchange).unwrap();
+
+        // Only the latest block and attestation are kept
+        let mut expected = interchange.clone();
+        expected.data[0].signed_blocks.truncate(1);
+        expected.data[0].signed_attestations.truncate(1);
+        assert_eq!(
+            database
+                .export_interchange(InterchangeFormat::Complete)
+                .unwrap(),
+            expected
+        );
+
+        let minimal = database
+            .export_interchange(InterchangeFormat::Minimal)
+            .unwrap();
+        assert_eq!(
+            minimal.data[0].signed_blocks,
+            vec![SignedBlock {
+                slot: 81952,
+                signing_root: None
+            }]
+        );
+        assert_eq!(
+            minimal.data[0].signed_attestations,
+            vec![SignedAttestation {
+                source_epoch: 2290,
+                target_epoch: 3007,
+                signing_root: None
+            }]
+        );
+
+        assert!(database
+            .check_and_insert_attestation_signing_root(&pubkey(), 2290, 3006, B256::ZERO)
+            .is_err());
+        assert!(database
+            .check_and_insert_block_signing_root(&pubkey(), 81951, B256::repeat_byte(4))
+            .is_err());
+    }
+
+    #[test]
+    fn test_import_conflicting_interchange() {
+        let (_database_dir, database) = open_database();
+        let pubkey = pubkey();
+        let root = |byte| B256::repeat_byte(byte);
+
+        database
+            .check_and_insert_block_signing_root(&pubkey, 10, root(1))
+            .unwrap();
+        database
+            .check_and_insert_attestation_signing_root(&pubkey, 2, 5, root(1))
+            .unwrap();
+        database
+            .import_interchange(&interchange(
+                vec![SignedBlock {
+                    slot: 10,
+                    signing_root: Some(root(2)),
+                }],
+                vec![SignedAttestation {
+                    source_epoch: 3,
+                    target_epoch: 5,
+                    signing_root: Some(root(1)),
+                }],
+            ))
+            .unwrap();
+
+        // Neither of the conflicting records can be signed again
+        assert!(database
+            .check_and_insert_block_signing_root(&pubkey, 10, root(1))
+            .is_err());
+        assert!(database
+            .check_and_insert_block_signing_root(&pubkey, 10, root(2))
+            .is_err());
+        assert!(database
+            .check_and_insert_attestation_signing_root(&pubkey, 2, 5, root(1))
+            .is_err());
+        assert!(database
+            .check_and_insert_attestation_signing_root(&pubkey, 3, 5, root(1))
+            .is_err());
+
+        database
+            .check_and_insert_block_signing_root(&pubkey, 11, root(3))
+            .unwrap();
+        database
+            .check_and_insert_attestation_signing_root(&pubkey, 3, 6, root(3))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_import_minimal_interchange() {
+        let (_database_dir, database) = open_database();
+        let pubkey = pubkey();
+        let root = |byte| B256::repeat_byte(byte);
+
+        database
+            .check_and_insert_block_signing_root(&pubkey, 15, root(1))
+            .unwrap();
+        database
+            .check_and_insert_attestation_signing_root(&pubkey, 1, 4, root(1))
+            .unwrap();
+        database
+            .import_interchange(&interchange(
+                vec![SignedBlock {
+                    slot: 20,
+                    signing_root: None,
+                }],
+                vec![SignedAttestation {
+                    source_epoch: 3,
+                    target_epoch: 8,
+                    signing_root: None,
+                }],
+            ))
+            .unwrap();
+
+        // The imported records are a low watermark
+        assert!(database
+            .check_and_insert_block_signing_root(&pubkey, 18, root(2))
+            .is_err());
+        assert!(database
+            .check_and_insert_block_signing_root(&pubkey, 20, root(2))
+            .is_err());
+        assert!(database
+            .check_and_insert_attestation_signing_root(&pubkey, 2, 9, root(2))
+            .is_err());
+        assert!(database
+            .check_and_insert_attestation_signing_root(&pubkey, 3, 7, root(2))
+            .is_err());
+
+        database
+            .check_and_insert_block_signing_root(&pubkey, 21, root(2))
+            .unwrap();
+        database
+            .check_and_insert_attestation_signing_root(&pubkey, 3, 9, root(2))
+            .unwrap();
+        assert_eq!(
+            database
+                .export_interchange(InterchangeFormat::Complete)
+                .unwrap()
+                .data[0]
+                .signed_blocks,
+            vec![
+                SignedBlock {
+                    slot: 20,
+                    signing_root: None
+                },
+                SignedBlock {
+                    slot: 21,
+                    signing_root: Some(root(2))
+                }
+            ]
+        );
+    }
+}
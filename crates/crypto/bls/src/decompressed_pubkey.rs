@@ -0,0 +1,21 @@
+use crate::pubkey::PubKey;
+
+/// A public key that passed ``KeyValidate`` and is held decompressed, so that it can verify any
+/// number of signatures without being decompressed and subgroup checked again.
+///
+/// Build it with ``DecompressedPubKey::try_from(&PubKey)``.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DecompressedPubKey {
+    pub(crate) pubkey: PubKey,
+    #[cfg(feature = "supranational")]
+    pub(crate) point: blst::min_pk::PublicKey,
+    #[cfg(all(feature = "zkcrypto", not(feature = "supranational")))]
+    pub(crate) point: bls12_381::G1Affine,
+}
+
+impl DecompressedPubKey {
+    /// The compressed encoding the key was decompressed from.
+    pub fn pubkey(&self) -> &PubKey {
+        &self.pubkey
+    }
+}
@@ -1,17 +1,15 @@
 use thiserror::Error;
 
-#[cfg(feature = "supranational")]
-use crate::supranational::errors::BlstError;
-
 #[derive(Error, PartialEq, Debug)]
 pub enum BLSError {
-    #[cfg(feature = "supranational")]
-    #[error("blst error: {0}")]
-    BlstError(#[from] BlstError),
+    #[error("nothing to aggregate")]
+    EmptyAggregate,
     #[error("invalid byte length")]
     InvalidByteLength,
     #[error("invalid public key")]
     InvalidPublicKey,
+    #[error("invalid secret key")]
+    InvalidSecretKey,
     #[error("invalid signature")]
     InvalidSignature,
 }
@@ -2,19 +2,30 @@
 //! at compile time via feature flags. Two implementations are supported:
 //! - "supranational": Uses the supranational/blst library, optimized for performance
 //! - "zkcrypto": Uses the zkcrypto/bls12_381 library implementation, optimized for zkVMs
+//!
+//! When both features are enabled, e.g. through feature unification with a crate that depends on
+//! the default "zkcrypto" backend, "supranational" takes precedence.
 
 pub mod aggregate_pubkey;
+pub mod aggregate_signature;
 pub mod constants;
+pub mod decompressed_pubkey;
 pub mod errors;
 pub mod pubkey;
+pub mod secret_key;
 pub mod signature;
+pub mod signature_set;
 pub mod traits;
 
 pub use aggregate_pubkey::AggregatePubKey;
+pub use aggregate_signature::AggregateSignature;
+pub use decompressed_pubkey::DecompressedPubKey;
 pub use pubkey::PubKey;
+pub use secret_key::SecretKey;
 pub use signature::BLSSignature;
+pub use signature_set::SignatureSet;
 
 #[cfg(feature = "supranational")]
 pub mod supranational;
-#[cfg(feature = "zkcrypto")]
+#[cfg(all(feature = "zkcrypto", not(feature = "supranational")))]
 pub mod zkcrypto;
@@ -0,0 +1,33 @@
+use std::fmt;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{constants::CURVE_ORDER, errors::BLSError};
+
+/// A BLS secret key, stored as a big-endian scalar and wiped from memory when dropped.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct SecretKey {
+    inner: [u8; 32],
+}
+
+impl SecretKey {
+    /// Create a secret key from its 32 byte big-endian encoding, which must be a scalar in
+    /// ``[1, r)``.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BLSError> {
+        let inner: [u8; 32] = bytes.try_into().map_err(|_| BLSError::InvalidByteLength)?;
+        if inner == [0; 32] || inner >= CURVE_ORDER {
+            return Err(BLSError::InvalidSecretKey);
+        }
+        Ok(Self { inner })
+    }
+
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{pubkey::PubKey, signature::BLSSignature};
+
+/// A signature together with the public keys that signed ``message``, so that many of them can be
+/// verified at once with ``Verifiable::verify_signature_sets``.
+#[derive(Debug, Clone)]
+pub struct SignatureSet<'a> {
+    pub signature: &'a BLSSignature,
+    pub pubkeys: Vec<&'a PubKey>,
+    pub message: &'a [u8],
+}
+
+impl<'a> SignatureSet<'a> {
+    pub fn new(signature: &'a BLSSignature, pubkeys: Vec<&'a PubKey>, message: &'a [u8]) -> Self {
+        Self {
+            signature,
+            pubkeys,
+            message,
+        }
+    }
+
+    pub fn single_pubkey(
+        signature: &'a BLSSignature,
+        pubkey: &'a PubKey,
+        message: &'a [u8],
+    ) -> Self {
+        Self::new(signature, vec![pubkey], message)
+    }
+}
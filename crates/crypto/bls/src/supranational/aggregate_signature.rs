@@ -0,0 +1,31 @@
+use blst::min_pk::AggregateSignature as BlstAggregateSignature;
+
+use crate::{
+    aggregate_signature::AggregateSignature,
+    errors::BLSError,
+    signature::BLSSignature,
+    traits::{Aggregatable, SupranationalAggregatable},
+};
+
+impl Aggregatable<BLSSignature> for AggregateSignature {
+    type Error = BLSError;
+
+    fn aggregate(signatures: &[&BLSSignature]) -> Result<Self, Self::Error> {
+        if signatures.is_empty() {
+            return Err(BLSError::EmptyAggregate);
+        }
+
+        let blst_signatures = signatures
+            .iter()
+            .map(|signature| signature.to_blst_signature())
+            .collect::<Result<Vec<_>, _>>()?;
+        let aggregate_signature =
+            BlstAggregateSignature::aggregate(&blst_signatures.iter().collect::<Vec<_>>(), false)
+                .map_err(|_| BLSError::InvalidSignature)?;
+        Ok(Self {
+            inner: aggregate_signature.to_signature().into(),
+        })
+    }
+}
+
+impl SupranationalAggregatable<BLSSignature> for AggregateSignature {}
@@ -1,4 +1,5 @@
 pub mod aggregate_pubkey;
-pub mod errors;
+pub mod aggregate_signature;
 pub mod pubkey;
+pub mod secret_key;
 pub mod signature;
@@ -1,7 +1,7 @@
 use blst::min_pk::PublicKey as BlstPublicKey;
 use ssz_types::FixedVector;
 
-use crate::{errors::BLSError, pubkey::PubKey};
+use crate::{decompressed_pubkey::DecompressedPubKey, errors::BLSError, pubkey::PubKey};
 
 impl From<BlstPublicKey> for PubKey {
     fn from(value: BlstPublicKey) -> Self {
@@ -12,7 +12,20 @@ impl From<BlstPublicKey> for PubKey {
 }
 
 impl PubKey {
+    /// Decompress the public key, rejecting it unless it passes ``KeyValidate``: the point must
+    /// be on the curve, in the G1 subgroup and not the point at infinity.
     pub fn to_blst_pubkey(&self) -> Result<BlstPublicKey, BLSError> {
-        BlstPublicKey::from_bytes(&self.inner).map_err(|err| BLSError::BlstError(err.into()))
+        BlstPublicKey::key_validate(&self.inner).map_err(|_| BLSError::InvalidPublicKey)
+    }
+}
+
+impl TryFrom<&PubKey> for DecompressedPubKey {
+    type Error = BLSError;
+
+    fn try_from(value: &PubKey) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pubkey: value.clone(),
+            point: value.to_blst_pubkey()?,
+        })
     }
 }
@@ -0,0 +1,28 @@
+use blst::min_pk::SecretKey as BlstSecretKey;
+
+use crate::{
+    constants::DST,
+    errors::BLSError,
+    traits::{Signable, SupranationalSignable},
+    BLSSignature, PubKey, SecretKey,
+};
+
+impl SecretKey {
+    pub fn to_blst_secret_key(&self) -> Result<BlstSecretKey, BLSError> {
+        BlstSecretKey::from_bytes(self.to_bytes()).map_err(|_| BLSError::InvalidSecretKey)
+    }
+}
+
+impl Signable for SecretKey {
+    type Error = BLSError;
+
+    fn public_key(&self) -> Result<PubKey, BLSError> {
+        Ok(self.to_blst_secret_key()?.sk_to_pk().into())
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<BLSSignature, BLSError> {
+        Ok(self.to_blst_secret_key()?.sign(message, DST, &[]).into())
+    }
+}
+
+impl SupranationalSignable for SecretKey {}
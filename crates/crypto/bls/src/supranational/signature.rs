@@ -1,48 +1,185 @@
-use blst::{min_pk::Signature as BlstSignature, BLST_ERROR};
+use blst::{
+    blst_scalar,
+    min_pk::{
+        AggregatePublicKey as BlstAggregatePublicKey, PublicKey as BlstPublicKey,
+        Signature as BlstSignature,
+    },
+    BLST_ERROR,
+};
+use rand::Rng;
+use ssz_types::FixedVector;
 
 use crate::{
     constants::DST,
     errors::BLSError,
     pubkey::PubKey,
     signature::BLSSignature,
+    signature_set::SignatureSet,
     traits::{SupranationalVerifiable, Verifiable},
+    DecompressedPubKey,
 };
 
+impl From<BlstSignature> for BLSSignature {
+    fn from(value: BlstSignature) -> Self {
+        BLSSignature {
+            inner: FixedVector::from(value.to_bytes().to_vec()),
+        }
+    }
+}
+
 impl BLSSignature {
+    /// Decompress the signature, rejecting points outside the G2 subgroup. The point at infinity
+    /// is a valid signature.
     pub fn to_blst_signature(&self) -> Result<BlstSignature, BLSError> {
-        BlstSignature::from_bytes(&self.inner).map_err(|e| BLSError::BlstError(e.into()))
+        BlstSignature::sig_validate(&self.inner, false).map_err(|_| BLSError::InvalidSignature)
+    }
+}
+
+/// Compressed encoding of the point at infinity of G1.
+const INFINITY_PUBLIC_KEY: [u8; 48] = {
+    let mut bytes = [0; 48];
+    bytes[0] = 0xc0;
+    bytes
+};
+
+/// Sum ``pubkeys``, returning ``None`` if there are none or they add up to the point at infinity,
+/// neither of which can verify a signature.
+fn aggregate_public_key(pubkeys: &[&BlstPublicKey]) -> Result<Option<BlstPublicKey>, BLSError> {
+    if pubkeys.is_empty() {
+        return Ok(None);
     }
+
+    let aggregate_public_key = BlstAggregatePublicKey::aggregate(pubkeys, false)
+        .map_err(|_| BLSError::InvalidPublicKey)?
+        .to_public_key();
+    Ok((aggregate_public_key.compress() != INFINITY_PUBLIC_KEY).then_some(aggregate_public_key))
 }
 
 impl Verifiable for BLSSignature {
     type Error = BLSError;
 
     fn verify(&self, pubkey: &PubKey, message: &[u8]) -> Result<bool, BLSError> {
+        self.verify_decompressed(&DecompressedPubKey::try_from(pubkey)?, message)
+    }
+
+    fn fast_aggregate_verify<'a, P>(&self, pubkeys: P, message: &[u8]) -> Result<bool, BLSError>
+    where
+        P: AsRef<[&'a PubKey]>,
+    {
+        let pubkeys = pubkeys
+            .as_ref()
+            .iter()
+            .map(|key| DecompressedPubKey::try_from(*key))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.fast_aggregate_verify_decompressed(pubkeys.iter().collect::<Vec<_>>(), message)
+    }
+
+    fn verify_decompressed(
+        &self,
+        pubkey: &DecompressedPubKey,
+        message: &[u8],
+    ) -> Result<bool, BLSError> {
         let signature = self.to_blst_signature()?;
-        let public_key = pubkey.to_blst_pubkey()?;
 
         Ok(
-            signature.verify(true, message, DST, &[], &public_key, false)
+            signature.verify(true, message, DST, &[], &pubkey.point, false)
                 == BLST_ERROR::BLST_SUCCESS,
         )
     }
 
-    fn fast_aggregate_verify<'a, P>(&self, pubkeys: P, message: &[u8]) -> Result<bool, BLSError>
+    fn fast_aggregate_verify_decompressed<'a, P>(
+        &self,
+        pubkeys: P,
+        message: &[u8],
+    ) -> Result<bool, BLSError>
+    where
+        P: AsRef<[&'a DecompressedPubKey]>,
+    {
+        let Some(aggregate_public_key) = aggregate_public_key(
+            &pubkeys
+                .as_ref()
+                .iter()
+                .map(|key| &key.point)
+                .collect::<Vec<_>>(),
+        )?
+        else {
+            return Ok(false);
+        };
+
+        let signature = self.to_blst_signature()?;
+
+        Ok(
+            signature.verify(true, message, DST, &[], &aggregate_public_key, false)
+                == BLST_ERROR::BLST_SUCCESS,
+        )
+    }
+
+    fn aggregate_verify<'a, P, M>(&self, pubkeys: P, messages: M) -> Result<bool, BLSError>
     where
         P: AsRef<[&'a PubKey]>,
+        M: AsRef<[&'a [u8]]>,
     {
+        let (pubkeys, messages) = (pubkeys.as_ref(), messages.as_ref());
+        if pubkeys.is_empty() || pubkeys.len() != messages.len() {
+            return Ok(false);
+        }
+
         let signature = self.to_blst_signature()?;
         let public_keys = pubkeys
-            .as_ref()
             .iter()
             .map(|key| key.to_blst_pubkey())
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(signature.fast_aggregate_verify(
+        Ok(signature.aggregate_verify(
             true,
-            message,
+            messages,
             DST,
             &public_keys.iter().collect::<Vec<_>>(),
+            true,
+        ) == BLST_ERROR::BLST_SUCCESS)
+    }
+
+    fn verify_signature_sets(signature_sets: &[SignatureSet]) -> Result<bool, BLSError> {
+        if signature_sets.is_empty() {
+            return Ok(false);
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut signatures = Vec::with_capacity(signature_sets.len());
+        let mut public_keys = Vec::with_capacity(signature_sets.len());
+        let mut scalars = Vec::with_capacity(signature_sets.len());
+        for signature_set in signature_sets {
+            let set_public_keys = signature_set
+                .pubkeys
+                .iter()
+                .map(|key| key.to_blst_pubkey())
+                .collect::<Result<Vec<_>, _>>()?;
+            let Some(aggregate_public_key) =
+                aggregate_public_key(&set_public_keys.iter().collect::<Vec<_>>())?
+            else {
+                return Ok(false);
+            };
+
+            let mut scalar = blst_scalar::default();
+            scalar.b[..8].copy_from_slice(&rng.gen_range(1..=u64::MAX).to_le_bytes());
+
+            signatures.push(signature_set.signature.to_blst_signature()?);
+            public_keys.push(aggregate_public_key);
+            scalars.push(scalar);
+        }
+
+        Ok(BlstSignature::verify_multiple_aggregate_signatures(
+            &signature_sets
+                .iter()
+                .map(|signature_set| signature_set.message)
+                .collect::<Vec<_>>(),
+            DST,
+            &public_keys.iter().collect::<Vec<_>>(),
+            false,
+            &signatures.iter().collect::<Vec<_>>(),
+            true,
+            &scalars,
+            64,
         ) == BLST_ERROR::BLST_SUCCESS)
     }
 }
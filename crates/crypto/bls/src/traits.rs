@@ -1,28 +1,33 @@
-use crate::{errors::BLSError, AggregatePubKey, PubKey};
+use crate::{
+    errors::BLSError, AggregatePubKey, BLSSignature, DecompressedPubKey, PubKey, SignatureSet,
+};
 
-/// Trait for aggregating BLS public keys.
+/// Trait for aggregating BLS public keys or signatures.
 ///
-/// This trait provides functionality to combine multiple BLS public keys into a single
-/// aggregate public key. This is useful for signature verification of messages signed
-/// by multiple parties.
-pub trait Aggregatable {
+/// This trait provides functionality to combine multiple BLS public keys or signatures into a
+/// single aggregate. Aggregate public keys are used to verify messages signed by multiple parties,
+/// and aggregate signatures replace the signatures of all those parties.
+pub trait Aggregatable<T> {
     type Error;
 
-    /// Aggregates multiple BLS public keys into a single aggregate public key.
+    /// Aggregates multiple BLS public keys or signatures into a single aggregate.
     ///
     /// # Arguments
-    /// * `pubkeys` - Slice of public key references to aggregate
+    /// * `items` - Slice of public key or signature references to aggregate
     ///
     /// # Returns
-    /// * `Result<AggregatePubKey, Self::Error>` - The aggregated public key or an error
-    fn aggregate(pubkeys: &[&PubKey]) -> Result<AggregatePubKey, Self::Error>;
+    /// * `Result<Self, Self::Error>` - The aggregate or an error if `items` is empty or contains
+    ///   invalid points
+    fn aggregate(items: &[&T]) -> Result<Self, Self::Error>
+    where
+        Self: Sized;
 }
 
-/// Marker trait for zkcrypto/bls12_381 BLS public key aggregation implementation
-pub trait ZkcryptoAggregatable: Aggregatable<Error = BLSError> {}
+/// Marker trait for zkcrypto/bls12_381 BLS aggregation implementation
+pub trait ZkcryptoAggregatable<T>: Aggregatable<T, Error = BLSError> {}
 
-/// Marker trait for supranational/blst BLS public key aggregation implementation
-pub trait SupranationalAggregatable: Aggregatable<Error = anyhow::Error> {}
+/// Marker trait for supranational/blst BLS aggregation implementation
+pub trait SupranationalAggregatable<T>: Aggregatable<T, Error = BLSError> {}
 
 /// Trait for verifying BLS signatures.
 ///
@@ -51,11 +56,74 @@ pub trait Verifiable {
     ///
     /// # Returns
     /// * `Result<bool, BLSError>` - Ok(true) if the signature is valid for the aggregate
-    ///   verification, Ok(false) if verification fails, or Err if there are issues with signature
-    ///   or public key bytes
+    ///   verification, Ok(false) if verification fails or `pubkeys` is empty or adds up to the
+    ///   point at infinity, or Err if there are issues with signature or public key bytes
     fn fast_aggregate_verify<'a, P>(&self, pubkeys: P, message: &[u8]) -> Result<bool, Self::Error>
     where
         P: AsRef<[&'a PubKey]>;
+
+    /// Verifies a BLS signature against an already decompressed and validated public key and a
+    /// message.
+    ///
+    /// # Arguments
+    /// * `pubkey` - The decompressed public key to verify against
+    /// * `message` - The message that was signed
+    ///
+    /// # Returns
+    /// * `Result<bool, BLSError>` - Ok(true) if the signature is valid, Ok(false) if verification
+    ///   fails, or Err if there are issues with signature bytes
+    fn verify_decompressed(
+        &self,
+        pubkey: &DecompressedPubKey,
+        message: &[u8],
+    ) -> Result<bool, Self::Error>;
+
+    /// Verifies the signature against a message using an aggregate of multiple already
+    /// decompressed and validated public keys
+    ///
+    /// # Arguments
+    /// * `pubkeys` - Collection of decompressed public key references to verify against
+    /// * `message` - Message that was signed
+    ///
+    /// # Returns
+    /// * `Result<bool, BLSError>` - Ok(true) if the signature is valid for the aggregate
+    ///   verification, Ok(false) if verification fails or `pubkeys` is empty or adds up to the
+    ///   point at infinity, or Err if there are issues with signature bytes
+    fn fast_aggregate_verify_decompressed<'a, P>(
+        &self,
+        pubkeys: P,
+        message: &[u8],
+    ) -> Result<bool, Self::Error>
+    where
+        P: AsRef<[&'a DecompressedPubKey]>;
+
+    /// Verifies the signature as the aggregate of one signature per public key, each over its own
+    /// message
+    ///
+    /// # Arguments
+    /// * `pubkeys` - Collection of public key references, one per signer
+    /// * `messages` - Messages signed by the public key at the same position in `pubkeys`
+    ///
+    /// # Returns
+    /// * `Result<bool, BLSError>` - Ok(true) if the signature is valid, Ok(false) if verification
+    ///   fails or `pubkeys` and `messages` are empty or of different lengths, or Err if there are
+    ///   issues with signature or public key bytes
+    fn aggregate_verify<'a, P, M>(&self, pubkeys: P, messages: M) -> Result<bool, Self::Error>
+    where
+        P: AsRef<[&'a PubKey]>,
+        M: AsRef<[&'a [u8]]>;
+
+    /// Verifies many signature sets at once with a single multi-pairing, scaling each set by a
+    /// random 64-bit scalar so that invalid signatures cannot cancel each other out
+    ///
+    /// # Arguments
+    /// * `signature_sets` - The signatures to verify, each with its public keys and message
+    ///
+    /// # Returns
+    /// * `Result<bool, BLSError>` - Ok(true) if every set is valid, Ok(false) if any set fails
+    ///   verification, has public keys that are empty or add up to the point at infinity, or there
+    ///   are no sets, or Err if there are issues with signature or public key bytes
+    fn verify_signature_sets(signature_sets: &[SignatureSet]) -> Result<bool, Self::Error>;
 }
 
 /// Marker trait for zkcrypto/bls12_381 BLS signature verification implementation
@@ -63,3 +131,271 @@ pub trait ZkcryptoVerifiable: Verifiable<Error = BLSError> {}
 
 /// Marker trait for supranational/blst BLS signature verification implementation
 pub trait SupranationalVerifiable: Verifiable<Error = BLSError> {}
+
+/// Trait for signing messages with a BLS secret key.
+///
+/// This trait provides functionality to derive the public key of a secret key and to sign
+/// messages with the proof of possession ciphersuite used by Ethereum.
+pub trait Signable {
+    type Error;
+
+    /// Derives the public key of the secret key.
+    ///
+    /// # Returns
+    /// * `Result<PubKey, Self::Error>` - The public key or an error if the secret key is invalid
+    fn public_key(&self) -> Result<PubKey, Self::Error>;
+
+    /// Signs a message with the secret key.
+    ///
+    /// # Arguments
+    /// * `message` - The message to sign
+    ///
+    /// # Returns
+    /// * `Result<BLSSignature, Self::Error>` - The signature or an error if the secret key is
+    ///   invalid
+    fn sign(&self, message: &[u8]) -> Result<BLSSignature, Self::Error>;
+}
+
+/// Marker trait for zkcrypto/bls12_381 BLS signing implementation
+pub trait ZkcryptoSignable: Signable<Error = BLSError> {}
+
+/// Marker trait for supranational/blst BLS signing implementation
+pub trait SupranationalSignable: Signable<Error = BLSError> {}
+
+/// Both backends run the same cases against the same expected results, so ``cargo test`` with
+/// either feature fails as soon as their behavior diverges.
+#[cfg(all(test, any(feature = "zkcrypto", feature = "supranational")))]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use alloy_primitives::hex;
+    use ssz_types::FixedVector;
+
+    use super::*;
+    use crate::{constants::CURVE_ORDER, AggregateSignature, SecretKey, SignatureSet};
+
+    const G1_GENERATOR: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+
+    fn secret_key(value: u8) -> SecretKey {
+        let mut bytes = [0; 32];
+        bytes[31] = value;
+        SecretKey::from_bytes(&bytes).unwrap()
+    }
+
+    /// Return the secret key ``r - 1``, whose public key is the negation of the one of
+    /// ``secret_key(1)``.
+    fn negated_secret_key() -> SecretKey {
+        let mut bytes = CURVE_ORDER;
+        bytes[31] -= 1;
+        SecretKey::from_bytes(&bytes).unwrap()
+    }
+
+    fn pubkey_from_bytes(first_byte: u8) -> PubKey {
+        let mut bytes = vec![0; 48];
+        bytes[0] = first_byte;
+        PubKey {
+            inner: FixedVector::from(bytes),
+        }
+    }
+
+    #[test]
+    fn test_key_validation() {
+        assert_eq!(
+            hex::encode(secret_key(1).public_key().unwrap().to_bytes()),
+            G1_GENERATOR
+        );
+
+        let signature = secret_key(1).sign(b"message").unwrap();
+        // The point at infinity and a point without the compression flag
+        for pubkey in [pubkey_from_bytes(0xc0), pubkey_from_bytes(0x00)] {
+            assert_eq!(
+                signature.verify(&pubkey, b"message"),
+                Err(BLSError::InvalidPublicKey)
+            );
+            assert_eq!(
+                signature.fast_aggregate_verify([&pubkey], b"message"),
+                Err(BLSError::InvalidPublicKey)
+            );
+            assert_eq!(
+                signature.aggregate_verify([&pubkey], [b"message".as_slice()]),
+                Err(BLSError::InvalidPublicKey)
+            );
+            assert_eq!(
+                AggregatePubKey::aggregate(&[&pubkey]),
+                Err(BLSError::InvalidPublicKey)
+            );
+            assert_eq!(
+                DecompressedPubKey::try_from(&pubkey),
+                Err(BLSError::InvalidPublicKey)
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let pubkey = secret_key(1).public_key().unwrap();
+        let signature = secret_key(1).sign(b"message").unwrap();
+
+        assert_eq!(signature.verify(&pubkey, b"message"), Ok(true));
+        let decompressed_pubkey = DecompressedPubKey::try_from(&pubkey).unwrap();
+        assert_eq!(decompressed_pubkey.pubkey(), &pubkey);
+        assert_eq!(
+            signature.verify_decompressed(&decompressed_pubkey, b"message"),
+            Ok(true)
+        );
+        assert_eq!(
+            signature.fast_aggregate_verify_decompressed([&decompressed_pubkey], b"message"),
+            Ok(true)
+        );
+        assert_eq!(signature.verify(&pubkey, b"other message"), Ok(false));
+        assert_eq!(
+            signature.verify(&secret_key(2).public_key().unwrap(), b"message"),
+            Ok(false)
+        );
+        assert_eq!(
+            BLSSignature::infinity().verify(&pubkey, b"message"),
+            Ok(false)
+        );
+        assert_eq!(
+            BLSSignature::default().verify(&pubkey, b"message"),
+            Err(BLSError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let pubkeys = [
+            secret_key(1).public_key().unwrap(),
+            secret_key(2).public_key().unwrap(),
+        ];
+        let pubkeys = pubkeys.iter().collect::<Vec<_>>();
+        let signatures = [
+            secret_key(1).sign(b"message").unwrap(),
+            secret_key(2).sign(b"message").unwrap(),
+        ];
+        let signature = AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>())
+            .unwrap()
+            .to_signature();
+
+        assert_eq!(
+            AggregatePubKey::aggregate(&[]),
+            Err(BLSError::EmptyAggregate)
+        );
+        assert_eq!(
+            AggregateSignature::aggregate(&[]),
+            Err(BLSError::EmptyAggregate)
+        );
+        assert_eq!(
+            AggregatePubKey::aggregate(&pubkeys).unwrap().to_pubkey(),
+            secret_key(3).public_key().unwrap()
+        );
+        assert_eq!(signature, secret_key(3).sign(b"message").unwrap());
+
+        assert_eq!(
+            signature.fast_aggregate_verify(&pubkeys, b"message"),
+            Ok(true)
+        );
+        assert_eq!(
+            signature.fast_aggregate_verify(&pubkeys[..1], b"message"),
+            Ok(false)
+        );
+        assert_eq!(
+            signature.fast_aggregate_verify([] as [&PubKey; 0], b"message"),
+            Ok(false)
+        );
+        assert_eq!(
+            BLSSignature::infinity().fast_aggregate_verify([] as [&PubKey; 0], b"message"),
+            Ok(false)
+        );
+
+        // {P, -P} adds up to the point at infinity, which the infinity signature would satisfy
+        let opposite_pubkeys = [
+            secret_key(1).public_key().unwrap(),
+            negated_secret_key().public_key().unwrap(),
+        ];
+        assert_eq!(
+            BLSSignature::infinity()
+                .fast_aggregate_verify(opposite_pubkeys.iter().collect::<Vec<_>>(), b"message"),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_aggregate_verify() {
+        let pubkeys = [
+            secret_key(1).public_key().unwrap(),
+            secret_key(2).public_key().unwrap(),
+        ];
+        let pubkeys = pubkeys.iter().collect::<Vec<_>>();
+        let messages = [b"first".as_slice(), b"second".as_slice()];
+        let signature = AggregateSignature::aggregate(&[
+            &secret_key(1).sign(messages[0]).unwrap(),
+            &secret_key(2).sign(messages[1]).unwrap(),
+        ])
+        .unwrap()
+        .to_signature();
+
+        assert_eq!(signature.aggregate_verify(&pubkeys, messages), Ok(true));
+        assert_eq!(
+            signature.aggregate_verify(&pubkeys, [messages[1], messages[0]]),
+            Ok(false)
+        );
+        assert_eq!(
+            signature.aggregate_verify(&pubkeys, &messages[..1]),
+            Ok(false)
+        );
+        assert_eq!(
+            BLSSignature::infinity().aggregate_verify([] as [&PubKey; 0], [] as [&[u8]; 0]),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_sets() {
+        let pubkeys = [
+            secret_key(1).public_key().unwrap(),
+            secret_key(2).public_key().unwrap(),
+        ];
+        let signatures = [
+            secret_key(1).sign(b"first").unwrap(),
+            secret_key(2).sign(b"second").unwrap(),
+        ];
+        let valid_sets = vec![
+            SignatureSet::single_pubkey(&signatures[0], &pubkeys[0], b"first"),
+            SignatureSet::single_pubkey(&signatures[1], &pubkeys[1], b"second"),
+        ];
+        let mut invalid_sets = valid_sets.clone();
+        invalid_sets[1].message = b"first".as_slice();
+
+        assert_eq!(BLSSignature::verify_signature_sets(&valid_sets), Ok(true));
+        assert_eq!(
+            BLSSignature::verify_signature_sets(&invalid_sets),
+            Ok(false)
+        );
+        assert_eq!(BLSSignature::verify_signature_sets(&[]), Ok(false));
+        assert_eq!(
+            BLSSignature::verify_signature_sets(&[SignatureSet::new(
+                &signatures[0],
+                vec![],
+                b"first"
+            )]),
+            Ok(false)
+        );
+
+        // The pubkeys of a set adding up to the point at infinity fail the whole batch
+        let opposite_pubkeys = [
+            secret_key(1).public_key().unwrap(),
+            negated_secret_key().public_key().unwrap(),
+        ];
+        let infinity_signature = BLSSignature::infinity();
+        let mut identity_sets = valid_sets.clone();
+        identity_sets.push(SignatureSet::new(
+            &infinity_signature,
+            opposite_pubkeys.iter().collect(),
+            b"first",
+        ));
+        assert_eq!(
+            BLSSignature::verify_signature_sets(&identity_sets),
+            Ok(false)
+        );
+    }
+}
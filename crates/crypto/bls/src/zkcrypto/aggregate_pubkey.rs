@@ -6,10 +6,14 @@ use crate::{
     AggregatePubKey, PubKey,
 };
 
-impl Aggregatable for AggregatePubKey {
+impl Aggregatable<PubKey> for AggregatePubKey {
     type Error = BLSError;
 
     fn aggregate(pubkeys: &[&PubKey]) -> Result<Self, Self::Error> {
+        if pubkeys.is_empty() {
+            return Err(BLSError::EmptyAggregate);
+        }
+
         let agg_point = pubkeys
             .iter()
             .try_fold(G1Projective::identity(), |acc, pubkey| {
@@ -22,4 +26,4 @@ impl Aggregatable for AggregatePubKey {
     }
 }
 
-impl ZkcryptoAggregatable for AggregatePubKey {}
+impl ZkcryptoAggregatable<PubKey> for AggregatePubKey {}
@@ -0,0 +1,31 @@
+use bls12_381::{G2Affine, G2Projective};
+
+use crate::{
+    errors::BLSError,
+    traits::{Aggregatable, ZkcryptoAggregatable},
+    AggregateSignature, BLSSignature,
+};
+
+impl Aggregatable<BLSSignature> for AggregateSignature {
+    type Error = BLSError;
+
+    fn aggregate(signatures: &[&BLSSignature]) -> Result<Self, Self::Error> {
+        if signatures.is_empty() {
+            return Err(BLSError::EmptyAggregate);
+        }
+
+        // The point at infinity is the identity, so infinity signatures don't change the result
+        let agg_point =
+            signatures
+                .iter()
+                .try_fold(G2Projective::identity(), |acc, signature| {
+                    Ok::<_, BLSError>(acc + G2Projective::from(G2Affine::try_from(*signature)?))
+                })?;
+
+        Ok(Self {
+            inner: BLSSignature::from(agg_point),
+        })
+    }
+}
+
+impl ZkcryptoAggregatable<BLSSignature> for AggregateSignature {}
@@ -1,6 +1,6 @@
 use bls12_381::{G1Affine, G1Projective};
 
-use crate::{errors::BLSError, PubKey};
+use crate::{errors::BLSError, DecompressedPubKey, PubKey};
 
 impl From<G1Projective> for PubKey {
     fn from(value: G1Projective) -> Self {
@@ -10,6 +10,8 @@ impl From<G1Projective> for PubKey {
     }
 }
 
+/// Decompresses the public key, rejecting it unless it passes ``KeyValidate``: the point must be
+/// on the curve, in the G1 subgroup and not the point at infinity.
 impl TryFrom<&PubKey> for G1Affine {
     type Error = BLSError;
 
@@ -22,8 +24,19 @@ impl TryFrom<&PubKey> for G1Affine {
         )
         .into_option()
         {
-            Some(point) => Ok(point),
-            None => Err(BLSError::InvalidPublicKey),
+            Some(point) if !bool::from(point.is_identity()) => Ok(point),
+            _ => Err(BLSError::InvalidPublicKey),
         }
     }
 }
+
+impl TryFrom<&PubKey> for DecompressedPubKey {
+    type Error = BLSError;
+
+    fn try_from(value: &PubKey) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pubkey: value.clone(),
+            point: G1Affine::try_from(value)?,
+        })
+    }
+}
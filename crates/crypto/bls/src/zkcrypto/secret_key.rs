@@ -0,0 +1,44 @@
+use bls12_381::{
+    hash_to_curve::{ExpandMsgXmd, HashToCurve},
+    G1Projective, G2Projective, Scalar,
+};
+use zeroize::Zeroize;
+
+use crate::{
+    constants::DST,
+    errors::BLSError,
+    traits::{Signable, ZkcryptoSignable},
+    BLSSignature, PubKey, SecretKey,
+};
+
+impl SecretKey {
+    fn to_scalar(&self) -> Result<Scalar, BLSError> {
+        // ``Scalar::from_bytes`` expects the little-endian encoding
+        let mut bytes: [u8; 32] = self
+            .to_bytes()
+            .try_into()
+            .map_err(|_| BLSError::InvalidByteLength)?;
+        bytes.reverse();
+        let scalar = Scalar::from_bytes(&bytes).into_option();
+        bytes.zeroize();
+        scalar.ok_or(BLSError::InvalidSecretKey)
+    }
+}
+
+impl Signable for SecretKey {
+    type Error = BLSError;
+
+    fn public_key(&self) -> Result<PubKey, BLSError> {
+        Ok(PubKey::from(G1Projective::generator() * self.to_scalar()?))
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<BLSSignature, BLSError> {
+        let h = <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(
+            [message],
+            DST,
+        );
+        Ok(BLSSignature::from(h * self.to_scalar()?))
+    }
+}
+
+impl ZkcryptoSignable for SecretKey {}
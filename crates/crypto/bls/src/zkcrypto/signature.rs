@@ -1,15 +1,25 @@
 use bls12_381::{
     hash_to_curve::{ExpandMsgXmd, HashToCurve},
-    pairing, G1Affine, G2Affine, G2Projective,
+    multi_miller_loop, pairing, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt,
+    Scalar,
 };
+use rand::Rng;
 
 use crate::{
     constants::DST,
     errors::BLSError,
-    traits::{Aggregatable, Verifiable, ZkcryptoVerifiable},
-    AggregatePubKey, BLSSignature, PubKey,
+    traits::{Verifiable, ZkcryptoVerifiable},
+    BLSSignature, DecompressedPubKey, PubKey, SignatureSet,
 };
 
+impl From<G2Projective> for BLSSignature {
+    fn from(value: G2Projective) -> Self {
+        Self {
+            inner: G2Affine::from(value).to_compressed().to_vec().into(),
+        }
+    }
+}
+
 impl TryFrom<&BLSSignature> for G2Affine {
     type Error = BLSError;
 
@@ -28,39 +38,135 @@ impl TryFrom<&BLSSignature> for G2Affine {
     }
 }
 
+fn hash_to_g2(message: &[u8]) -> G2Affine {
+    G2Affine::from(
+        <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve([message], DST),
+    )
+}
+
+/// Sum ``pubkeys``, returning ``None`` if there are none or they add up to the point at infinity,
+/// neither of which can verify a signature.
+fn aggregate_pubkey_point(pubkeys: &[&DecompressedPubKey]) -> Option<G1Affine> {
+    let aggregate_point = G1Affine::from(
+        pubkeys
+            .iter()
+            .fold(G1Projective::identity(), |acc, pubkey| acc + pubkey.point),
+    );
+
+    (!bool::from(aggregate_point.is_identity())).then_some(aggregate_point)
+}
+
+fn decompress_pubkeys(pubkeys: &[&PubKey]) -> Result<Vec<DecompressedPubKey>, BLSError> {
+    pubkeys
+        .iter()
+        .map(|pubkey| DecompressedPubKey::try_from(*pubkey))
+        .collect()
+}
+
+/// Check that the product of the pairings of ``terms`` is the identity of ``Gt``.
+fn is_pairing_product_identity(terms: &[(G1Affine, G2Prepared)]) -> bool {
+    let terms = terms.iter().map(|(g1, g2)| (g1, g2)).collect::<Vec<_>>();
+    multi_miller_loop(&terms).final_exponentiation() == Gt::identity()
+}
+
 impl Verifiable for BLSSignature {
     type Error = BLSError;
 
     fn verify(&self, pubkey: &PubKey, message: &[u8]) -> Result<bool, BLSError> {
-        let h = <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(
-            [message],
-            DST,
-        );
+        self.verify_decompressed(&DecompressedPubKey::try_from(pubkey)?, message)
+    }
+
+    fn fast_aggregate_verify<'a, P>(&self, pubkeys: P, message: &[u8]) -> Result<bool, BLSError>
+    where
+        P: AsRef<[&'a PubKey]>,
+    {
+        let pubkeys = decompress_pubkeys(pubkeys.as_ref())?;
+        self.fast_aggregate_verify_decompressed(pubkeys.iter().collect::<Vec<_>>(), message)
+    }
 
-        let gt1 = pairing(&G1Affine::try_from(pubkey)?, &G2Affine::from(h));
+    fn verify_decompressed(
+        &self,
+        pubkey: &DecompressedPubKey,
+        message: &[u8],
+    ) -> Result<bool, BLSError> {
+        let gt1 = pairing(&pubkey.point, &hash_to_g2(message));
         let gt2 = pairing(&G1Affine::generator(), &G2Affine::try_from(self)?);
 
         Ok(gt1 == gt2)
     }
 
-    fn fast_aggregate_verify<'a, P>(&self, pubkeys: P, message: &[u8]) -> Result<bool, BLSError>
+    fn fast_aggregate_verify_decompressed<'a, P>(
+        &self,
+        pubkeys: P,
+        message: &[u8],
+    ) -> Result<bool, BLSError>
     where
-        P: AsRef<[&'a PubKey]>,
+        P: AsRef<[&'a DecompressedPubKey]>,
     {
-        let aggregate_pubkey = AggregatePubKey::aggregate(pubkeys.as_ref())?;
-        let h = <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(
-            [message],
-            DST,
-        );
-
-        let gt1 = pairing(
-            &G1Affine::try_from(&aggregate_pubkey.to_pubkey())?,
-            &G2Affine::from(h),
-        );
+        let Some(aggregate_pubkey) = aggregate_pubkey_point(pubkeys.as_ref()) else {
+            return Ok(false);
+        };
+
+        let gt1 = pairing(&aggregate_pubkey, &hash_to_g2(message));
         let gt2 = pairing(&G1Affine::generator(), &G2Affine::try_from(self)?);
 
         Ok(gt1 == gt2)
     }
+
+    fn aggregate_verify<'a, P, M>(&self, pubkeys: P, messages: M) -> Result<bool, BLSError>
+    where
+        P: AsRef<[&'a PubKey]>,
+        M: AsRef<[&'a [u8]]>,
+    {
+        let (pubkeys, messages) = (pubkeys.as_ref(), messages.as_ref());
+        if pubkeys.is_empty() || pubkeys.len() != messages.len() {
+            return Ok(false);
+        }
+
+        let signature = G2Affine::try_from(self)?;
+        let mut terms = Vec::with_capacity(pubkeys.len() + 1);
+        for (pubkey, message) in pubkeys.iter().zip(messages) {
+            terms.push((
+                G1Affine::try_from(*pubkey)?,
+                G2Prepared::from(hash_to_g2(message)),
+            ));
+        }
+        terms.push((-G1Affine::generator(), G2Prepared::from(signature)));
+
+        Ok(is_pairing_product_identity(&terms))
+    }
+
+    fn verify_signature_sets(signature_sets: &[SignatureSet]) -> Result<bool, BLSError> {
+        if signature_sets.is_empty() {
+            return Ok(false);
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut aggregate_signature = G2Projective::identity();
+        let mut terms = Vec::with_capacity(signature_sets.len() + 1);
+        for signature_set in signature_sets {
+            let pubkeys = decompress_pubkeys(&signature_set.pubkeys)?;
+            let Some(aggregate_pubkey) =
+                aggregate_pubkey_point(&pubkeys.iter().collect::<Vec<_>>())
+            else {
+                return Ok(false);
+            };
+            let scalar = Scalar::from(rng.gen_range(1..=u64::MAX));
+
+            aggregate_signature +=
+                G2Projective::from(G2Affine::try_from(signature_set.signature)?) * scalar;
+            terms.push((
+                G1Affine::from(G1Projective::from(aggregate_pubkey) * scalar),
+                G2Prepared::from(hash_to_g2(signature_set.message)),
+            ));
+        }
+        terms.push((
+            -G1Affine::generator(),
+            G2Prepared::from(G2Affine::from(aggregate_signature)),
+        ));
+
+        Ok(is_pairing_product_identity(&terms))
+    }
 }
 
 impl ZkcryptoVerifiable for BLSSignature {}
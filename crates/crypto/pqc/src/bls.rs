@@ -0,0 +1,48 @@
+use ream_bls::{
+    errors::BLSError,
+    traits::{Aggregatable, Signable, Verifiable},
+    AggregateSignature, BLSSignature, PubKey, SecretKey,
+};
+
+use crate::scheme::SignatureScheme;
+
+/// The BLS12-381 signatures of the Beacon chain, using the backend selected through the features
+/// of ``ream-bls``.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Bls;
+
+impl SignatureScheme for Bls {
+    type PublicKey = PubKey;
+    type SecretKey = SecretKey;
+    type Signature = BLSSignature;
+    type AggregateSignature = BLSSignature;
+    type Error = BLSError;
+
+    fn public_key(secret_key: &SecretKey) -> Result<PubKey, BLSError> {
+        secret_key.public_key()
+    }
+
+    fn sign(secret_key: &mut SecretKey, message: &[u8]) -> Result<BLSSignature, BLSError> {
+        secret_key.sign(message)
+    }
+
+    fn verify(
+        public_key: &PubKey,
+        message: &[u8],
+        signature: &BLSSignature,
+    ) -> Result<bool, BLSError> {
+        signature.verify(public_key, message)
+    }
+
+    fn aggregate(signatures: &[&BLSSignature]) -> Result<BLSSignature, BLSError> {
+        AggregateSignature::aggregate(signatures).map(AggregateSignature::to_signature)
+    }
+
+    fn verify_aggregate(
+        public_keys: &[&PubKey],
+        message: &[u8],
+        aggregate_signature: &BLSSignature,
+    ) -> Result<bool, BLSError> {
+        aggregate_signature.fast_aggregate_verify(public_keys, message)
+    }
+}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Debug)]
+pub enum XmssError {
+    #[error("nothing to aggregate")]
+    EmptyAggregate,
+    #[error("all {0} one-time keys have been used")]
+    KeyExhausted(u64),
+    #[error("tree height {0} is not supported")]
+    InvalidTreeHeight(usize),
+    #[error("too many signatures to aggregate")]
+    TooManySignatures,
+}
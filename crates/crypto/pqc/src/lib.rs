@@ -0,0 +1,14 @@
+//! Signature schemes for Beam chain containers. ``SignatureScheme`` abstracts over the scheme a
+//! container is signed with, so that experimental containers can swap the BLS signatures of the
+//! Beacon chain for the hash-based, post-quantum ``Xmss`` scheme:
+//! - ``Bls``: BLS12-381 signatures from ``ream-bls``
+//! - ``Xmss``: stateful Winternitz one-time signatures authenticated by a Merkle tree
+
+pub mod bls;
+pub mod errors;
+pub mod scheme;
+pub mod xmss;
+
+pub use bls::Bls;
+pub use scheme::SignatureScheme;
+pub use xmss::Xmss;
@@ -0,0 +1,66 @@
+use ssz::{Decode, Encode};
+use tree_hash::TreeHash;
+
+/// Trait for the signature schemes Beam containers can be signed with.
+///
+/// Public keys and signatures are SSZ containers, so that a container generic over the scheme can
+/// embed them. Secret keys are taken mutably when signing, because stateful hash-based schemes
+/// must never sign twice with the same one-time key.
+pub trait SignatureScheme {
+    type PublicKey: Clone + Encode + Decode + TreeHash;
+    type SecretKey;
+    type Signature: Clone + Encode + Decode + TreeHash;
+    type AggregateSignature: Clone + Encode + Decode + TreeHash;
+    type Error;
+
+    /// Derives the public key of a secret key.
+    ///
+    /// # Returns
+    /// * `Result<Self::PublicKey, Self::Error>` - The public key or an error if the secret key is
+    ///   invalid
+    fn public_key(secret_key: &Self::SecretKey) -> Result<Self::PublicKey, Self::Error>;
+
+    /// Signs a message, advancing the state of the secret key if the scheme is stateful.
+    ///
+    /// # Arguments
+    /// * `secret_key` - The secret key to sign with
+    /// * `message` - The message to sign
+    ///
+    /// # Returns
+    /// * `Result<Self::Signature, Self::Error>` - The signature or an error if the secret key is
+    ///   invalid or has no signatures left
+    fn sign(
+        secret_key: &mut Self::SecretKey,
+        message: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+
+    /// Verifies a signature against a public key and message.
+    ///
+    /// # Returns
+    /// * `Result<bool, Self::Error>` - Ok(true) if the signature is valid, Ok(false) if
+    ///   verification fails, or Err if the public key or signature is malformed
+    fn verify(
+        public_key: &Self::PublicKey,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<bool, Self::Error>;
+
+    /// Aggregates signatures of the same message into a single aggregate signature.
+    ///
+    /// # Returns
+    /// * `Result<Self::AggregateSignature, Self::Error>` - The aggregate or an error if
+    ///   `signatures` is empty or cannot be aggregated
+    fn aggregate(signatures: &[&Self::Signature]) -> Result<Self::AggregateSignature, Self::Error>;
+
+    /// Verifies an aggregate signature of a message against the public keys of its signers, in
+    /// the order their signatures were aggregated.
+    ///
+    /// # Returns
+    /// * `Result<bool, Self::Error>` - Ok(true) if the aggregate is valid, Ok(false) if
+    ///   verification fails, or Err if a public key or signature is malformed
+    fn verify_aggregate(
+        public_keys: &[&Self::PublicKey],
+        message: &[u8],
+        aggregate_signature: &Self::AggregateSignature,
+    ) -> Result<bool, Self::Error>;
+}
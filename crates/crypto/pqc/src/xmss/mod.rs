@@ -0,0 +1,434 @@
+//! The eXtended Merkle Signature Scheme: ``2 ** H`` Winternitz one-time keys are the leaves of a
+//! Merkle tree whose root is the public key, and every signature carries the authentication path
+//! of the one-time key it was made with. The secret key is stateful and must never sign with the
+//! same leaf twice.
+
+pub mod wots;
+
+use std::{fmt, marker::PhantomData};
+
+use alloy_primitives::B256;
+use rand::{CryptoRng, RngCore};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    typenum::{Unsigned, U67},
+    FixedVector, VariableList,
+};
+use tree_hash_derive::TreeHash;
+use zeroize::Zeroize;
+
+use crate::{
+    errors::XmssError,
+    scheme::SignatureScheme,
+    xmss::wots::{
+        chain, chain_positions, leaf, message_digest, node, secret_chain_start, CHAINS,
+        WINTERNITZ_PARAMETER,
+    },
+};
+
+/// Largest supported Merkle tree height. The secret key computes and holds the whole tree of
+/// ``2**H`` one-time keys in memory, which is already over a million leaves at this height.
+pub const MAX_TREE_HEIGHT: usize = 20;
+
+const _: () = assert!(U67::USIZE == CHAINS);
+
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TreeHash)]
+pub struct XmssPublicKey {
+    pub root: B256,
+    pub public_seed: B256,
+}
+
+/// A Winternitz one-time signature and the authentication path of its one-time key in a Merkle
+/// tree of height ``H``.
+#[derive(Debug, PartialEq, Clone, Encode, Decode, TreeHash)]
+pub struct XmssSignature<H: Unsigned> {
+    pub leaf_index: u64,
+    pub chains: FixedVector<B256, U67>,
+    pub auth_path: FixedVector<B256, H>,
+}
+
+/// Up to ``N`` signatures of the same message, aggregated by concatenation.
+#[derive(Debug, PartialEq, Clone, Encode, Decode, TreeHash)]
+pub struct XmssAggregateSignature<H: Unsigned, N: Unsigned> {
+    pub signatures: VariableList<XmssSignature<H>, N>,
+}
+
+/// The secret key of an XMSS key pair, holding the Merkle tree of its one-time keys and the index
+/// of the next unused one.
+///
+/// ``sign`` advances ``next_leaf_index`` before returning the signature. Persist the index after
+/// every signature: restoring an older one and signing again reuses a one-time key, which leaks
+/// enough of it to forge signatures.
+pub struct XmssSecretKey<H: Unsigned> {
+    secret_seed: [u8; 32],
+    public_seed: B256,
+    next_leaf_index: u64,
+    tree: Vec<Vec<B256>>,
+    _phantom: PhantomData<H>,
+}
+
+impl<H: Unsigned> XmssSecretKey<H> {
+    /// Generate a fresh key pair from random seeds.
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Result<Self, XmssError> {
+        let mut secret_seed = [0; 32];
+        rng.fill_bytes(&mut secret_seed);
+        let mut public_seed = B256::ZERO;
+        rng.fill_bytes(public_seed.as_mut_slice());
+
+        let secret_key = Self::from_seeds(&secret_seed, public_seed, 0);
+        secret_seed.zeroize();
+        secret_key
+    }
+
+    /// Rebuild the key pair of ``secret_seed`` and ``public_seed``, whose next unused one-time key
+    /// is at ``next_leaf_index``.
+    pub fn from_seeds(
+        secret_seed: &[u8; 32],
+        public_seed: B256,
+        next_leaf_index: u64,
+    ) -> Result<Self, XmssError> {
+        if H::USIZE > MAX_TREE_HEIGHT {
+            return Err(XmssError::InvalidTreeHeight(H::USIZE));
+        }
+
+        let leaves = (0..1u64 << H::USIZE)
+            .map(|leaf_index| {
+                let chain_ends = (0..CHAINS)
+                    .map(|chain_index| {
+                        chain(
+                            public_seed,
+                            leaf_index,
+                            chain_index,
+                            0,
+                            WINTERNITZ_PARAMETER - 1,
+                            secret_chain_start(secret_seed, leaf_index, chain_index),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                leaf(public_seed, leaf_index, &chain_ends)
+            })
+            .collect::<Vec<_>>();
+
+        let mut tree = vec![leaves];
+        for level in 1..=H::USIZE {
+            let nodes = tree[level - 1]
+                .chunks_exact(2)
+                .enumerate()
+                .map(|(index, children)| {
+                    node(
+                        public_seed,
+                        level as u8,
+                        index as u64,
+                        children[0],
+                        children[1],
+                    )
+                })
+                .collect();
+            tree.push(nodes);
+        }
+
+        Ok(Self {
+            secret_seed: *secret_seed,
+            public_seed,
+            next_leaf_index,
+            tree,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn public_key(&self) -> XmssPublicKey {
+        XmssPublicKey {
+            root: self.tree[H::USIZE][0],
+            public_seed: self.public_seed,
+        }
+    }
+
+    pub fn next_leaf_index(&self) -> u64 {
+        self.next_leaf_index
+    }
+
+    /// Return how many more messages the key can sign.
+    pub fn remaining_signatures(&self) -> u64 {
+        (1u64 << H::USIZE).saturating_sub(self.next_leaf_index)
+    }
+
+    /// Sign ``message`` with the next unused one-time key.
+    pub fn sign(&mut self, message: &[u8]) -> Result<XmssSignature<H>, XmssError> {
+        let leaf_index = self.next_leaf_index;
+        let capacity = 1u64 << H::USIZE;
+        if leaf_index >= capacity {
+            return Err(XmssError::KeyExhausted(capacity));
+        }
+        self.next_leaf_index += 1;
+
+        let positions = chain_positions(message_digest(self.public_seed, leaf_index, message));
+        let chains = positions
+            .iter()
+            .enumerate()
+            .map(|(chain_index, &position)| {
+                chain(
+                    self.public_seed,
+                    leaf_index,
+                    chain_index,
+                    0,
+                    position,
+                    secret_chain_start(&self.secret_seed, leaf_index, chain_index),
+                )
+            })
+            .collect::<Vec<_>>();
+        let auth_path = (0..H::USIZE)
+            .map(|level| self.tree[level][((leaf_index >> level) ^ 1) as usize])
+            .collect::<Vec<_>>();
+
+        Ok(XmssSignature {
+            leaf_index,
+            chains: FixedVector::from(chains),
+            auth_path: FixedVector::from(auth_path),
+        })
+    }
+}
+
+impl<H: Unsigned> fmt::Debug for XmssSecretKey<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XmssSecretKey")
+            .field("public_key", &self.public_key())
+            .field("next_leaf_index", &self.next_leaf_index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H: Unsigned> Drop for XmssSecretKey<H> {
+    fn drop(&mut self) {
+        self.secret_seed.zeroize();
+    }
+}
+
+impl XmssPublicKey {
+    /// Check that ``signature`` is a signature of ``message`` by one of the one-time keys of this
+    /// public key.
+    pub fn verify<H: Unsigned>(&self, message: &[u8], signature: &XmssSignature<H>) -> bool {
+        let leaf_index = signature.leaf_index;
+        if H::USIZE > MAX_TREE_HEIGHT || leaf_index >= 1u64 << H::USIZE {
+            return false;
+        }
+
+        let positions = chain_positions(message_digest(self.public_seed, leaf_index, message));
+        let chain_ends = positions
+            .iter()
+            .zip(signature.chains.iter())
+            .enumerate()
+            .map(|(chain_index, (&position, &value))| {
+                chain(
+                    self.public_seed,
+                    leaf_index,
+                    chain_index,
+                    position,
+                    WINTERNITZ_PARAMETER - 1 - position,
+                    value,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut node_hash = leaf(self.public_seed, leaf_index, &chain_ends);
+        for (level, sibling) in signature.auth_path.iter().enumerate() {
+            let parent_level = (level + 1) as u8;
+            let parent_index = leaf_index >> (level + 1);
+            node_hash = if (leaf_index >> level) & 1 == 0 {
+                node(
+                    self.public_seed,
+                    parent_level,
+                    parent_index,
+                    node_hash,
+                    *sibling,
+                )
+            } else {
+                node(
+                    self.public_seed,
+                    parent_level,
+                    parent_index,
+                    *sibling,
+                    node_hash,
+                )
+            };
+        }
+
+        node_hash == self.root
+    }
+}
+
+/// The XMSS signature scheme over Merkle trees of height ``H``, aggregating up to ``N``
+/// signatures by concatenation.
+pub struct Xmss<H: Unsigned, N: Unsigned> {
+    _phantom: PhantomData<(H, N)>,
+}
+
+impl<H: Unsigned, N: Unsigned> SignatureScheme for Xmss<H, N> {
+    type PublicKey = XmssPublicKey;
+    type SecretKey = XmssSecretKey<H>;
+    type Signature = XmssSignature<H>;
+    type AggregateSignature = XmssAggregateSignature<H, N>;
+    type Error = XmssError;
+
+    fn public_key(secret_key: &XmssSecretKey<H>) -> Result<XmssPublicKey, XmssError> {
+        Ok(secret_key.public_key())
+    }
+
+    fn sign(
+        secret_key: &mut XmssSecretKey<H>,
+        message: &[u8],
+    ) -> Result<XmssSignature<H>, XmssError> {
+        secret_key.sign(message)
+    }
+
+    fn verify(
+        public_key: &XmssPublicKey,
+        message: &[u8],
+        signature: &XmssSignature<H>,
+    ) -> Result<bool, XmssError> {
+        Ok(public_key.verify(message, signature))
+    }
+
+    fn aggregate(
+        signatures: &[&XmssSignature<H>],
+    ) -> Result<XmssAggregateSignature<H, N>, XmssError> {
+        if signatures.is_empty() {
+            return Err(XmssError::EmptyAggregate);
+        }
+
+        Ok(XmssAggregateSignature {
+            signatures: VariableList::new(
+                signatures
+                    .iter()
+                    .map(|signature| (*signature).clone())
+                    .collect(),
+            )
+            .map_err(|_| XmssError::TooManySignatures)?,
+        })
+    }
+
+    fn verify_aggregate(
+        public_keys: &[&XmssPublicKey],
+        message: &[u8],
+        aggregate_signature: &XmssAggregateSignature<H, N>,
+    ) -> Result<bool, XmssError> {
+        if public_keys.is_empty() || public_keys.len() != aggregate_signature.signatures.len() {
+            return Ok(false);
+        }
+
+        Ok(public_keys
+            .iter()
+            .zip(aggregate_signature.signatures.iter())
+            .all(|(public_key, signature)| public_key.verify(message, signature)))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use ssz::{Decode, Encode};
+    use ssz_types::typenum::{U21, U3, U4};
+
+    use super::*;
+
+    type TestXmss = Xmss<U3, U4>;
+
+    fn secret_key_from_seed(seed: u8) -> XmssSecretKey<U3> {
+        XmssSecretKey::from_seeds(&[seed; 32], B256::repeat_byte(seed), 0).unwrap()
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let mut secret_key = secret_key_from_seed(1);
+        let public_key = TestXmss::public_key(&secret_key).unwrap();
+
+        for leaf_index in 0..8 {
+            let signature = TestXmss::sign(&mut secret_key, b"message").unwrap();
+            assert_eq!(signature.leaf_index, leaf_index);
+            assert!(TestXmss::verify(&public_key, b"message", &signature).unwrap());
+            assert!(!TestXmss::verify(&public_key, b"other message", &signature).unwrap());
+            assert_eq!(
+                XmssSignature::<U3>::from_ssz_bytes(&signature.as_ssz_bytes()).unwrap(),
+                signature
+            );
+        }
+
+        assert_eq!(secret_key.remaining_signatures(), 0);
+        assert_eq!(
+            TestXmss::sign(&mut secret_key, b"message"),
+            Err(XmssError::KeyExhausted(8))
+        );
+    }
+
+    #[test]
+    fn test_tampered_signature() {
+        let mut secret_key = secret_key_from_seed(1);
+        let public_key = secret_key.public_key();
+        let signature = secret_key.sign(b"message").unwrap();
+
+        let mut tampered = signature.clone();
+        tampered.chains[0] = B256::ZERO;
+        assert!(!public_key.verify(b"message", &tampered));
+
+        let mut tampered = signature.clone();
+        tampered.leaf_index = 1;
+        assert!(!public_key.verify(b"message", &tampered));
+
+        let mut tampered = signature;
+        tampered.leaf_index = 8;
+        assert!(!public_key.verify(b"message", &tampered));
+
+        assert!(!secret_key_from_seed(2)
+            .public_key()
+            .verify(b"message", &secret_key.sign(b"message").unwrap()));
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut secret_keys = [secret_key_from_seed(1), secret_key_from_seed(2)];
+        let public_keys = secret_keys
+            .iter()
+            .map(XmssSecretKey::public_key)
+            .collect::<Vec<_>>();
+        let signatures = secret_keys
+            .iter_mut()
+            .map(|secret_key| secret_key.sign(b"message").unwrap())
+            .collect::<Vec<_>>();
+        let aggregate_signature =
+            TestXmss::aggregate(&signatures.iter().collect::<Vec<_>>()).unwrap();
+
+        assert!(TestXmss::verify_aggregate(
+            &[&public_keys[0], &public_keys[1]],
+            b"message",
+            &aggregate_signature
+        )
+        .unwrap());
+        assert!(!TestXmss::verify_aggregate(
+            &[&public_keys[1], &public_keys[0]],
+            b"message",
+            &aggregate_signature
+        )
+        .unwrap());
+        assert!(
+            !TestXmss::verify_aggregate(&[&public_keys[0]], b"message", &aggregate_signature)
+                .unwrap()
+        );
+        assert_eq!(TestXmss::aggregate(&[]), Err(XmssError::EmptyAggregate));
+    }
+
+    #[test]
+    fn test_invalid_tree_height() {
+        assert!(matches!(
+            XmssSecretKey::<U21>::from_seeds(&[1; 32], B256::repeat_byte(1), 0),
+            Err(XmssError::InvalidTreeHeight(21))
+        ));
+
+        let signature = XmssSignature::<U21> {
+            leaf_index: 0,
+            chains: FixedVector::from(vec![B256::ZERO; CHAINS]),
+            auth_path: FixedVector::from(vec![B256::ZERO; 21]),
+        };
+        assert!(!secret_key_from_seed(1)
+            .public_key()
+            .verify(b"message", &signature));
+    }
+}
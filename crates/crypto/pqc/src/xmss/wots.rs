@@ -0,0 +1,115 @@
+//! Winternitz one-time signatures over SHA-256 with ``w = 16``: the message digest is split into
+//! 64 nibbles, followed by 3 nibbles of checksum, and every nibble selects how far along its hash
+//! chain the signature reveals. Every hash is tweaked with the public seed, the leaf index and the
+//! position within the scheme, so that chains of different keys and leaves never collide.
+
+use alloy_primitives::B256;
+use ethereum_hashing::hash_fixed;
+
+/// Number of values a chain step encodes, i.e. the Winternitz parameter ``w``.
+pub const WINTERNITZ_PARAMETER: u8 = 16;
+
+/// Number of chains encoding the 256-bit message digest, 4 bits each.
+pub const MESSAGE_CHAINS: usize = 64;
+
+/// Number of chains encoding the checksum, which is at most ``64 * 15 = 960 < 16 ** 3``.
+pub const CHECKSUM_CHAINS: usize = 3;
+
+/// Total number of chains of a one-time key.
+pub const CHAINS: usize = MESSAGE_CHAINS + CHECKSUM_CHAINS;
+
+const DOMAIN_SECRET: u8 = 0x00;
+const DOMAIN_CHAIN: u8 = 0x01;
+const DOMAIN_LEAF: u8 = 0x02;
+const DOMAIN_NODE: u8 = 0x03;
+const DOMAIN_MESSAGE: u8 = 0x04;
+
+fn tweak_hash(parts: &[&[u8]]) -> B256 {
+    B256::from(hash_fixed(&parts.concat()))
+}
+
+/// Return the start of chain ``chain_index`` of the one-time key at ``leaf_index``.
+pub fn secret_chain_start(secret_seed: &[u8; 32], leaf_index: u64, chain_index: usize) -> B256 {
+    tweak_hash(&[
+        secret_seed.as_slice(),
+        &[DOMAIN_SECRET],
+        &leaf_index.to_le_bytes(),
+        &[chain_index as u8],
+    ])
+}
+
+/// Advance ``value``, which is ``start_step`` steps into chain ``chain_index``, by ``steps``
+/// steps.
+pub fn chain(
+    public_seed: B256,
+    leaf_index: u64,
+    chain_index: usize,
+    start_step: u8,
+    steps: u8,
+    value: B256,
+) -> B256 {
+    (start_step..start_step + steps).fold(value, |value, step| {
+        tweak_hash(&[
+            public_seed.as_slice(),
+            &[DOMAIN_CHAIN],
+            &leaf_index.to_le_bytes(),
+            &[chain_index as u8, step],
+            value.as_slice(),
+        ])
+    })
+}
+
+/// Return the digest of ``message`` signed by the one-time key at ``leaf_index``.
+pub fn message_digest(public_seed: B256, leaf_index: u64, message: &[u8]) -> B256 {
+    tweak_hash(&[
+        public_seed.as_slice(),
+        &[DOMAIN_MESSAGE],
+        &leaf_index.to_le_bytes(),
+        message,
+    ])
+}
+
+/// Return how many steps into every chain the signature of ``digest`` reveals.
+pub fn chain_positions(digest: B256) -> [u8; CHAINS] {
+    let mut positions = [0; CHAINS];
+    for (index, byte) in digest.iter().enumerate() {
+        positions[2 * index] = byte >> 4;
+        positions[2 * index + 1] = byte & 0x0f;
+    }
+
+    let checksum = positions[..MESSAGE_CHAINS]
+        .iter()
+        .map(|position| (WINTERNITZ_PARAMETER - 1 - position) as u16)
+        .sum::<u16>();
+    for index in 0..CHECKSUM_CHAINS {
+        let shift = 4 * (CHECKSUM_CHAINS - 1 - index);
+        positions[MESSAGE_CHAINS + index] = ((checksum >> shift) & 0x0f) as u8;
+    }
+
+    positions
+}
+
+/// Compress the chain ends of the one-time key at ``leaf_index`` into its Merkle tree leaf.
+pub fn leaf(public_seed: B256, leaf_index: u64, chain_ends: &[B256]) -> B256 {
+    let chain_ends = chain_ends
+        .iter()
+        .flat_map(|chain_end| chain_end.0)
+        .collect::<Vec<_>>();
+    tweak_hash(&[
+        public_seed.as_slice(),
+        &[DOMAIN_LEAF],
+        &leaf_index.to_le_bytes(),
+        &chain_ends,
+    ])
+}
+
+/// Return the Merkle tree node at ``index`` of ``level`` with children ``left`` and ``right``.
+pub fn node(public_seed: B256, level: u8, index: u64, left: B256, right: B256) -> B256 {
+    tweak_hash(&[
+        public_seed.as_slice(),
+        &[DOMAIN_NODE, level],
+        &index.to_le_bytes(),
+        left.as_slice(),
+        right.as_slice(),
+    ])
+}
@@ -0,0 +1,467 @@
+#[macro_export]
+macro_rules! test_bls_sign {
+    () => {
+        #[cfg(test)]
+        mod tests_bls_sign {
+            use alloy_primitives::hex;
+            use ream_bls::{traits::Signable, BLSSignature, SecretKey};
+            use rstest::rstest;
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize)]
+            struct SignInput {
+                privkey: String,
+                message: String,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct SignTest {
+                input: SignInput,
+                output: Option<String>,
+            }
+
+            fn sign(input: &SignInput) -> Result<BLSSignature, String> {
+                let privkey = hex::decode(&input.privkey).map_err(|err| err.to_string())?;
+                let message = hex::decode(&input.message).map_err(|err| err.to_string())?;
+                SecretKey::from_bytes(&privkey)
+                    .and_then(|secret_key| secret_key.sign(&message))
+                    .map_err(|err| err.to_string())
+            }
+
+            #[rstest]
+            fn test_sign() {
+                let base_path = "general/tests/general/phase0/bls/sign/bls";
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let test: SignTest = {
+                        let content = std::fs::read_to_string(case_dir.join("data.yaml"))
+                            .expect("Failed to read data.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse data.yaml")
+                    };
+
+                    let expected = test
+                        .output
+                        .map(|output| hex::decode(output).expect("Failed to decode output"));
+                    assert_eq!(
+                        sign(&test.input)
+                            .ok()
+                            .map(|signature| signature.to_bytes().to_vec()),
+                        expected,
+                        "Unexpected result in case {case_name}"
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! test_bls_aggregate {
+    () => {
+        #[cfg(test)]
+        mod tests_bls_aggregate {
+            use alloy_primitives::hex;
+            use ream_bls::{traits::Aggregatable, AggregateSignature, BLSSignature};
+            use rstest::rstest;
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize)]
+            struct AggregateTest {
+                input: Vec<String>,
+                output: Option<String>,
+            }
+
+            fn aggregate(input: &[String]) -> Result<BLSSignature, String> {
+                let signatures = input
+                    .iter()
+                    .map(|signature| ef_tests::utils::signature_from_hex(signature))
+                    .collect::<Result<Vec<_>, _>>()?;
+                AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>())
+                    .map(AggregateSignature::to_signature)
+                    .map_err(|err| err.to_string())
+            }
+
+            #[rstest]
+            fn test_aggregate() {
+                let base_path = "general/tests/general/phase0/bls/aggregate/bls";
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let test: AggregateTest = {
+                        let content = std::fs::read_to_string(case_dir.join("data.yaml"))
+                            .expect("Failed to read data.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse data.yaml")
+                    };
+
+                    let expected = test
+                        .output
+                        .map(|output| hex::decode(output).expect("Failed to decode output"));
+                    assert_eq!(
+                        aggregate(&test.input)
+                            .ok()
+                            .map(|signature| signature.to_bytes().to_vec()),
+                        expected,
+                        "Unexpected result in case {case_name}"
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! test_bls_verify {
+    () => {
+        #[cfg(test)]
+        mod tests_bls_verify {
+            use alloy_primitives::hex;
+            use ream_bls::traits::Verifiable;
+            use rstest::rstest;
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize)]
+            struct VerifyInput {
+                pubkey: String,
+                message: String,
+                signature: String,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct VerifyTest {
+                input: VerifyInput,
+                output: bool,
+            }
+
+            fn verify(input: &VerifyInput) -> Result<bool, String> {
+                let pubkey = ef_tests::utils::pubkey_from_hex(&input.pubkey)?;
+                let message = hex::decode(&input.message).map_err(|err| err.to_string())?;
+                let signature = ef_tests::utils::signature_from_hex(&input.signature)?;
+                signature
+                    .verify(&pubkey, &message)
+                    .map_err(|err| err.to_string())
+            }
+
+            #[rstest]
+            fn test_verify() {
+                let base_path = "general/tests/general/phase0/bls/verify/bls";
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let test: VerifyTest = {
+                        let content = std::fs::read_to_string(case_dir.join("data.yaml"))
+                            .expect("Failed to read data.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse data.yaml")
+                    };
+
+                    assert_eq!(
+                        verify(&test.input).unwrap_or(false),
+                        test.output,
+                        "Unexpected result in case {case_name}"
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! test_bls_fast_aggregate_verify {
+    () => {
+        #[cfg(test)]
+        mod tests_bls_fast_aggregate_verify {
+            use alloy_primitives::hex;
+            use ream_bls::traits::Verifiable;
+            use rstest::rstest;
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize)]
+            struct FastAggregateVerifyInput {
+                pubkeys: Vec<String>,
+                message: String,
+                signature: String,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct FastAggregateVerifyTest {
+                input: FastAggregateVerifyInput,
+                output: bool,
+            }
+
+            fn fast_aggregate_verify(input: &FastAggregateVerifyInput) -> Result<bool, String> {
+                let pubkeys = input
+                    .pubkeys
+                    .iter()
+                    .map(|pubkey| ef_tests::utils::pubkey_from_hex(pubkey))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let message = hex::decode(&input.message).map_err(|err| err.to_string())?;
+                let signature = ef_tests::utils::signature_from_hex(&input.signature)?;
+                signature
+                    .fast_aggregate_verify(pubkeys.iter().collect::<Vec<_>>(), &message)
+                    .map_err(|err| err.to_string())
+            }
+
+            #[rstest]
+            fn test_fast_aggregate_verify() {
+                let base_path = "general/tests/general/phase0/bls/fast_aggregate_verify/bls";
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let test: FastAggregateVerifyTest = {
+                        let content = std::fs::read_to_string(case_dir.join("data.yaml"))
+                            .expect("Failed to read data.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse data.yaml")
+                    };
+
+                    assert_eq!(
+                        fast_aggregate_verify(&test.input).unwrap_or(false),
+                        test.output,
+                        "Unexpected result in case {case_name}"
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! test_bls_aggregate_verify {
+    () => {
+        #[cfg(test)]
+        mod tests_bls_aggregate_verify {
+            use alloy_primitives::hex;
+            use ream_bls::traits::Verifiable;
+            use rstest::rstest;
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize)]
+            struct AggregateVerifyInput {
+                pubkeys: Vec<String>,
+                messages: Vec<String>,
+                signature: String,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct AggregateVerifyTest {
+                input: AggregateVerifyInput,
+                output: bool,
+            }
+
+            fn aggregate_verify(input: &AggregateVerifyInput) -> Result<bool, String> {
+                let pubkeys = input
+                    .pubkeys
+                    .iter()
+                    .map(|pubkey| ef_tests::utils::pubkey_from_hex(pubkey))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let messages = input
+                    .messages
+                    .iter()
+                    .map(|message| hex::decode(message).map_err(|err| err.to_string()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let signature = ef_tests::utils::signature_from_hex(&input.signature)?;
+                signature
+                    .aggregate_verify(
+                        pubkeys.iter().collect::<Vec<_>>(),
+                        messages.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+                    )
+                    .map_err(|err| err.to_string())
+            }
+
+            #[rstest]
+            fn test_aggregate_verify() {
+                let base_path = "general/tests/general/phase0/bls/aggregate_verify/bls";
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let test: AggregateVerifyTest = {
+                        let content = std::fs::read_to_string(case_dir.join("data.yaml"))
+                            .expect("Failed to read data.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse data.yaml")
+                    };
+
+                    assert_eq!(
+                        aggregate_verify(&test.input).unwrap_or(false),
+                        test.output,
+                        "Unexpected result in case {case_name}"
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! test_bls_eth_aggregate_pubkeys {
+    () => {
+        #[cfg(test)]
+        mod tests_bls_eth_aggregate_pubkeys {
+            use alloy_primitives::hex;
+            use ream_bls::PubKey;
+            use ream_consensus::deneb::beacon_state::eth_aggregate_pubkeys;
+            use rstest::rstest;
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize)]
+            struct EthAggregatePubkeysTest {
+                input: Vec<String>,
+                output: Option<String>,
+            }
+
+            fn aggregate_pubkeys(input: &[String]) -> Result<PubKey, String> {
+                let pubkeys = input
+                    .iter()
+                    .map(|pubkey| ef_tests::utils::pubkey_from_hex(pubkey))
+                    .collect::<Result<Vec<_>, _>>()?;
+                eth_aggregate_pubkeys(&pubkeys.iter().collect::<Vec<_>>())
+                    .map_err(|err| err.to_string())
+            }
+
+            #[rstest]
+            fn test_eth_aggregate_pubkeys() {
+                let base_path = "general/tests/general/phase0/bls/eth_aggregate_pubkeys/bls";
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let test: EthAggregatePubkeysTest = {
+                        let content = std::fs::read_to_string(case_dir.join("data.yaml"))
+                            .expect("Failed to read data.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse data.yaml")
+                    };
+
+                    let expected = test
+                        .output
+                        .map(|output| hex::decode(output).expect("Failed to decode output"));
+                    assert_eq!(
+                        aggregate_pubkeys(&test.input)
+                            .ok()
+                            .map(|pubkey| pubkey.to_bytes().to_vec()),
+                        expected,
+                        "Unexpected result in case {case_name}"
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! test_bls_eth_fast_aggregate_verify {
+    () => {
+        #[cfg(test)]
+        mod tests_bls_eth_fast_aggregate_verify {
+            use alloy_primitives::{hex, B256};
+            use ream_consensus::deneb::beacon_state::eth_fast_aggregate_verify;
+            use rstest::rstest;
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize)]
+            struct EthFastAggregateVerifyInput {
+                pubkeys: Vec<String>,
+                message: String,
+                signature: String,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct EthFastAggregateVerifyTest {
+                input: EthFastAggregateVerifyInput,
+                output: bool,
+            }
+
+            fn fast_aggregate_verify(input: &EthFastAggregateVerifyInput) -> Result<bool, String> {
+                let pubkeys = input
+                    .pubkeys
+                    .iter()
+                    .map(|pubkey| ef_tests::utils::pubkey_from_hex(pubkey))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let message = hex::decode(&input.message).map_err(|err| err.to_string())?;
+                let message = B256::try_from(message.as_slice()).map_err(|err| err.to_string())?;
+                let signature = ef_tests::utils::signature_from_hex(&input.signature)?;
+                eth_fast_aggregate_verify(&pubkeys.iter().collect::<Vec<_>>(), message, &signature)
+                    .map_err(|err| err.to_string())
+            }
+
+            #[rstest]
+            fn test_eth_fast_aggregate_verify() {
+                let base_path = "general/tests/general/phase0/bls/eth_fast_aggregate_verify/bls";
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let test: EthFastAggregateVerifyTest = {
+                        let content = std::fs::read_to_string(case_dir.join("data.yaml"))
+                            .expect("Failed to read data.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse data.yaml")
+                    };
+
+                    assert_eq!(
+                        fast_aggregate_verify(&test.input).unwrap_or(false),
+                        test.output,
+                        "Unexpected result in case {case_name}"
+                    );
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,180 @@
+#[macro_export]
+macro_rules! test_deposit_tree {
+    () => {
+        #[cfg(test)]
+        mod tests_deposit_tree {
+            use alloy_primitives::B256;
+            use ream_bls::{BLSSignature, PubKey};
+            use ream_consensus::{
+                deposit_data::DepositData, deposit_tree::DepositTree,
+                deposit_tree_snapshot::DepositTreeSnapshot, eth_1_data::Eth1Data,
+            };
+            use serde::Deserialize;
+            use tree_hash::TreeHash;
+
+            #[derive(Debug, Deserialize)]
+            struct TestDepositData {
+                pubkey: PubKey,
+                withdrawal_credentials: B256,
+                #[serde(with = "serde_utils::quoted_u64")]
+                amount: u64,
+                signature: BLSSignature,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct TestEth1Data {
+                deposit_root: B256,
+                #[serde(with = "serde_utils::quoted_u64")]
+                deposit_count: u64,
+                block_hash: B256,
+            }
+
+            impl From<&TestEth1Data> for Eth1Data {
+                fn from(eth1_data: &TestEth1Data) -> Self {
+                    Eth1Data {
+                        deposit_root: eth1_data.deposit_root,
+                        deposit_count: eth1_data.deposit_count,
+                        block_hash: eth1_data.block_hash,
+                    }
+                }
+            }
+
+            /// A deposit of the EIP-4881 test cases, with the state of the deposit contract tree
+            /// right after it.
+            #[derive(Debug, Deserialize)]
+            struct DepositTestCase {
+                deposit_data: TestDepositData,
+                deposit_data_root: B256,
+                eth1_data: TestEth1Data,
+                #[serde(with = "serde_utils::quoted_u64")]
+                block_height: u64,
+                snapshot: DepositTreeSnapshot,
+            }
+
+            fn read_test_cases() -> Vec<DepositTestCase> {
+                let content = std::fs::read_to_string("deposit_tree_test_cases.yaml")
+                    .expect("Failed to read deposit_tree_test_cases.yaml");
+                serde_yaml::from_str(&content)
+                    .expect("Failed to parse deposit_tree_test_cases.yaml")
+            }
+
+            fn deposit_tree(test_cases: &[DepositTestCase]) -> DepositTree {
+                let mut tree = DepositTree::default();
+                for test_case in test_cases {
+                    tree.push_leaf(test_case.deposit_data_root).unwrap();
+                }
+                tree
+            }
+
+            /// Restore a tree from ``snapshot`` and replay the deposits of ``test_cases``.
+            fn clone_from_snapshot(
+                snapshot: &DepositTreeSnapshot,
+                test_cases: &[DepositTestCase],
+            ) -> DepositTree {
+                let mut tree = DepositTree::from_snapshot(snapshot).unwrap();
+                for test_case in test_cases {
+                    tree.push_leaf(test_case.deposit_data_root).unwrap();
+                }
+                tree
+            }
+
+            fn assert_same_proof(tree: &DepositTree, other: &DepositTree, index: u64) {
+                let deposit_count = tree.mix_in_length;
+                assert_eq!(
+                    tree.get_proof(index, deposit_count).unwrap(),
+                    other.get_proof(index, deposit_count).unwrap(),
+                    "Proof mismatch for deposit {index}"
+                );
+            }
+
+            #[test]
+            fn test_deposit_data_roots() {
+                for test_case in read_test_cases() {
+                    let deposit_data = DepositData {
+                        pubkey: test_case.deposit_data.pubkey,
+                        withdrawal_credentials: test_case.deposit_data.withdrawal_credentials,
+                        amount: test_case.deposit_data.amount,
+                        signature: test_case.deposit_data.signature,
+                    };
+                    assert_eq!(deposit_data.tree_hash_root(), test_case.deposit_data_root);
+                }
+            }
+
+            #[test]
+            fn test_snapshot_cases() {
+                let test_cases = read_test_cases();
+                let mut tree = deposit_tree(&test_cases);
+                for test_case in &test_cases {
+                    assert_eq!(
+                        tree.get_root_at(test_case.eth1_data.deposit_count).unwrap(),
+                        test_case.eth1_data.deposit_root
+                    );
+                }
+
+                for test_case in &test_cases {
+                    tree.finalize(&(&test_case.eth1_data).into(), test_case.block_height)
+                        .unwrap();
+                    assert_eq!(tree.get_snapshot().unwrap(), test_case.snapshot);
+
+                    let restored = DepositTree::from_snapshot(&test_case.snapshot).unwrap();
+                    assert_eq!(restored.get_root(), test_case.eth1_data.deposit_root);
+                }
+            }
+
+            #[test]
+            fn test_finalization() {
+                let test_cases = read_test_cases();
+                let count = test_cases.len().min(128);
+                assert!(count > 8, "Not enough deposit tree test cases");
+                let first_finalized = count * 100 / 128;
+                let second_finalized = count * 105 / 128;
+
+                let mut tree = deposit_tree(&test_cases[..count]);
+                let original_root = tree.get_root();
+                assert_eq!(original_root, test_cases[count - 1].eth1_data.deposit_root);
+
+                // Finalization doesn't change the root
+                let test_case = &test_cases[first_finalized];
+                tree.finalize(&(&test_case.eth1_data).into(), test_case.block_height)
+                    .unwrap();
+                assert_eq!(tree.get_root(), original_root);
+                let snapshot = tree.get_snapshot().unwrap();
+                assert_eq!(snapshot, test_case.snapshot);
+
+                // A copy restored from the snapshot reaches the same root
+                let copy = clone_from_snapshot(&snapshot, &test_cases[first_finalized + 1..count]);
+                assert_eq!(copy.get_root(), original_root);
+
+                // Finalizing again keeps the root
+                let test_case = &test_cases[second_finalized];
+                tree.finalize(&(&test_case.eth1_data).into(), test_case.block_height)
+                    .unwrap();
+                assert_eq!(tree.get_root(), original_root);
+
+                // Proofs of the deposits after the finalized ones match those of a copy restored
+                // from the new snapshot and of a tree replaying every deposit
+                let copy = clone_from_snapshot(
+                    &tree.get_snapshot().unwrap(),
+                    &test_cases[second_finalized + 1..count],
+                );
+                let full_tree_copy = deposit_tree(&test_cases[..count]);
+                for index in second_finalized as u64 + 1..count as u64 {
+                    assert_same_proof(&tree, &copy, index);
+                    assert_same_proof(&tree, &full_tree_copy, index);
+                }
+            }
+
+            #[test]
+            fn test_invalid_snapshot() {
+                let invalid_snapshot = DepositTreeSnapshot {
+                    finalized: Default::default(),
+                    deposit_root: B256::ZERO,
+                    deposit_count: 0,
+                    execution_block_hash: B256::ZERO,
+                    execution_block_height: 0,
+                };
+                assert!(DepositTree::from_snapshot(&invalid_snapshot).is_err());
+            }
+        }
+    };
+}
@@ -0,0 +1,229 @@
+#[macro_export]
+macro_rules! test_kzg {
+    () => {
+        #[cfg(test)]
+        mod tests_kzg {
+            use std::{path::Path, str::FromStr};
+
+            use alloy_primitives::hex;
+            use ream_consensus::{
+                deneb::blob_sidecar::Blob,
+                eth_spec::MainnetEthSpec,
+                fulu::polynomial_commitments_sampling::{
+                    compute_cells_and_kzg_proofs, recover_cells_and_kzg_proofs,
+                    verify_cell_kzg_proof_batch, Cell, CellsAndKZGProofs,
+                },
+                kzg_commitment::KZGCommitment,
+                kzg_proof::KZGProof,
+                polynomial_commitments::{
+                    load_trusted_setup, verify_blob_kzg_proof, verify_blob_kzg_proof_batch,
+                },
+            };
+            use rstest::rstest;
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize)]
+            struct VerifyBlobKzgProofInput {
+                blob: String,
+                commitment: String,
+                proof: String,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct VerifyBlobKzgProofBatchInput {
+                blobs: Vec<String>,
+                commitments: Vec<String>,
+                proofs: Vec<String>,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct ComputeCellsAndKzgProofsInput {
+                blob: String,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct VerifyCellKzgProofBatchInput {
+                commitments: Vec<String>,
+                cell_indices: Vec<u64>,
+                cells: Vec<String>,
+                proofs: Vec<String>,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct RecoverCellsAndKzgProofsInput {
+                cell_indices: Vec<u64>,
+                cells: Vec<String>,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct KzgTest<T, O> {
+                input: T,
+                output: Option<O>,
+            }
+
+            fn parse_blob(blob: &str) -> Result<Blob<MainnetEthSpec>, String> {
+                let bytes = hex::decode(blob).map_err(|err| err.to_string())?;
+                Blob::<MainnetEthSpec>::new(bytes).map_err(|err| format!("{err:?}"))
+            }
+
+            fn parse_cells(cells: &[String]) -> Result<Vec<Cell>, String> {
+                cells
+                    .iter()
+                    .map(|cell| {
+                        let bytes = hex::decode(cell).map_err(|err| err.to_string())?;
+                        Cell::new(bytes).map_err(|err| format!("{err:?}"))
+                    })
+                    .collect()
+            }
+
+            /// Encode cells and proofs the way the test vectors list them.
+            fn encode_cells_and_kzg_proofs(
+                (cells, proofs): CellsAndKZGProofs,
+            ) -> (Vec<String>, Vec<String>) {
+                (
+                    cells
+                        .iter()
+                        .map(|cell| hex::encode_prefixed(&cell[..]))
+                        .collect(),
+                    proofs
+                        .iter()
+                        .map(|proof| hex::encode_prefixed(proof.0))
+                        .collect(),
+                )
+            }
+
+            fn run_cases<T, O>(fork: &str, handler: &str, run: impl Fn(T) -> Result<O, String>)
+            where
+                T: for<'de> Deserialize<'de>,
+                O: for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug,
+            {
+                load_trusted_setup(Path::new("trusted_setup.txt"))
+                    .expect("Failed to load trusted setup");
+
+                let base_path = format!("general/tests/general/{fork}/kzg/{handler}/kzg-mainnet");
+
+                for entry in std::fs::read_dir(&base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let test: KzgTest<T, O> = {
+                        let content = std::fs::read_to_string(case_dir.join("data.yaml"))
+                            .expect("Failed to read data.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse data.yaml")
+                    };
+
+                    assert_eq!(
+                        run(test.input).ok(),
+                        test.output,
+                        "Unexpected result in case {case_name}"
+                    );
+                }
+            }
+
+            #[rstest]
+            fn test_verify_blob_kzg_proof() {
+                run_cases(
+                    "deneb",
+                    "verify_blob_kzg_proof",
+                    |input: VerifyBlobKzgProofInput| {
+                        verify_blob_kzg_proof::<MainnetEthSpec>(
+                            &parse_blob(&input.blob)?,
+                            &KZGCommitment::from_str(&input.commitment)?,
+                            &KZGProof::from_str(&input.proof)?,
+                        )
+                        .map_err(|err| err.to_string())
+                    },
+                );
+            }
+
+            #[rstest]
+            fn test_verify_blob_kzg_proof_batch() {
+                run_cases(
+                    "deneb",
+                    "verify_blob_kzg_proof_batch",
+                    |input: VerifyBlobKzgProofBatchInput| {
+                        verify_blob_kzg_proof_batch::<MainnetEthSpec>(
+                            &input
+                                .blobs
+                                .iter()
+                                .map(|blob| parse_blob(blob))
+                                .collect::<Result<Vec<_>, _>>()?,
+                            &input
+                                .commitments
+                                .iter()
+                                .map(|commitment| KZGCommitment::from_str(commitment))
+                                .collect::<Result<Vec<_>, _>>()?,
+                            &input
+                                .proofs
+                                .iter()
+                                .map(|proof| KZGProof::from_str(proof))
+                                .collect::<Result<Vec<_>, _>>()?,
+                        )
+                        .map_err(|err| err.to_string())
+                    },
+                );
+            }
+
+            #[rstest]
+            fn test_compute_cells_and_kzg_proofs() {
+                run_cases(
+                    "fulu",
+                    "compute_cells_and_kzg_proofs",
+                    |input: ComputeCellsAndKzgProofsInput| {
+                        compute_cells_and_kzg_proofs::<MainnetEthSpec>(&parse_blob(&input.blob)?)
+                            .map(encode_cells_and_kzg_proofs)
+                            .map_err(|err| err.to_string())
+                    },
+                );
+            }
+
+            #[rstest]
+            fn test_verify_cell_kzg_proof_batch() {
+                run_cases(
+                    "fulu",
+                    "verify_cell_kzg_proof_batch",
+                    |input: VerifyCellKzgProofBatchInput| {
+                        verify_cell_kzg_proof_batch(
+                            &input
+                                .commitments
+                                .iter()
+                                .map(|commitment| KZGCommitment::from_str(commitment))
+                                .collect::<Result<Vec<_>, _>>()?,
+                            &input.cell_indices,
+                            &parse_cells(&input.cells)?,
+                            &input
+                                .proofs
+                                .iter()
+                                .map(|proof| KZGProof::from_str(proof))
+                                .collect::<Result<Vec<_>, _>>()?,
+                        )
+                        .map_err(|err| err.to_string())
+                    },
+                );
+            }
+
+            #[rstest]
+            fn test_recover_cells_and_kzg_proofs() {
+                run_cases(
+                    "fulu",
+                    "recover_cells_and_kzg_proofs",
+                    |input: RecoverCellsAndKzgProofsInput| {
+                        recover_cells_and_kzg_proofs(
+                            &input.cell_indices,
+                            &parse_cells(&input.cells)?,
+                        )
+                        .map(encode_cells_and_kzg_proofs)
+                        .map_err(|err| err.to_string())
+                    },
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,244 @@
+#[macro_export]
+macro_rules! test_light_client_update_ranking {
+    ($spec:ty) => {
+        #[cfg(test)]
+        mod tests_light_client_update_ranking {
+            use ream_consensus::light_client::light_client_update::LightClientUpdate;
+
+            use super::*;
+
+            #[derive(Debug, serde::Deserialize)]
+            struct Meta {
+                updates_count: usize,
+            }
+
+            #[test]
+            fn test_update_ranking() {
+                let base_path = format!(
+                    "{}/deneb/light_client/update_ranking/pyspec_tests",
+                    ef_tests::utils::preset_tests_dir::<$spec>()
+                );
+
+                for entry in std::fs::read_dir(&base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let meta: Meta = {
+                        let content = std::fs::read_to_string(case_dir.join("meta.yaml"))
+                            .expect("cannot find test asset(meta.yaml)");
+                        serde_yaml::from_str(&content).expect("Failed to parse meta.yaml")
+                    };
+                    let updates = (0..meta.updates_count)
+                        .map(|index| {
+                            utils::read_ssz_snappy::<LightClientUpdate<$spec>>(
+                                &case_dir.join(format!("updates_{index}.ssz_snappy")),
+                            )
+                            .expect("cannot find test asset(updates_<index>.ssz_snappy)")
+                        })
+                        .collect::<Vec<_>>();
+
+                    // Updates are sorted from best to worst
+                    for (index, pair) in updates.windows(2).enumerate() {
+                        assert!(
+                            !pair[1].is_better_update(&pair[0]),
+                            "Update {} ranked above update {index} in case {case_name}",
+                            index + 1
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! test_light_client_sync {
+    ($spec:ty) => {
+        #[cfg(test)]
+        mod tests_light_client_sync {
+            use alloy_primitives::{aliases::B32, B256};
+            use ream_consensus::{
+                fork_data::ForkData,
+                light_client::{
+                    light_client_bootstrap::LightClientBootstrap,
+                    light_client_header::LightClientHeader, light_client_update::LightClientUpdate,
+                },
+            };
+            use ream_light_client::{
+                helpers::get_lc_execution_root, light_client_store::initialize_light_client_store,
+            };
+            use tree_hash::TreeHash;
+
+            use super::*;
+
+            #[derive(Debug, serde::Deserialize)]
+            struct Meta {
+                genesis_validators_root: B256,
+                trusted_block_root: B256,
+                bootstrap_fork_digest: B32,
+                store_fork_digest: B32,
+            }
+
+            #[derive(Debug, serde::Deserialize)]
+            struct HeaderCheck {
+                slot: u64,
+                beacon_root: B256,
+                execution_root: B256,
+            }
+
+            #[derive(Debug, serde::Deserialize)]
+            struct Checks {
+                finalized_header: HeaderCheck,
+                optimistic_header: HeaderCheck,
+            }
+
+            #[derive(Debug, serde::Deserialize)]
+            #[serde(rename_all = "snake_case")]
+            enum Step {
+                ForceUpdate {
+                    current_slot: u64,
+                    checks: Checks,
+                },
+                ProcessUpdate {
+                    update_fork_digest: B32,
+                    update: String,
+                    current_slot: u64,
+                    checks: Checks,
+                },
+                UpgradeStore {
+                    store_fork_digest: B32,
+                },
+            }
+
+            fn assert_header(header: &LightClientHeader, check: &HeaderCheck, case_name: &str) {
+                assert_eq!(
+                    header.beacon.slot, check.slot,
+                    "Slot mismatch in case {case_name}"
+                );
+                assert_eq!(
+                    header.beacon.tree_hash_root(),
+                    check.beacon_root,
+                    "Beacon root mismatch in case {case_name}"
+                );
+                assert_eq!(
+                    get_lc_execution_root(header),
+                    check.execution_root,
+                    "Execution root mismatch in case {case_name}"
+                );
+            }
+
+            #[test]
+            fn test_sync() {
+                let base_path = format!(
+                    "{}/deneb/light_client/sync/pyspec_tests",
+                    ef_tests::utils::preset_tests_dir::<$spec>()
+                );
+
+                // The Deneb spec tests activate every fork at genesis
+                let mut spec = utils::chain_spec::<$spec>();
+                spec.altair_fork_epoch = 0;
+                spec.bellatrix_fork_epoch = 0;
+                spec.capella_fork_epoch = 0;
+                spec.deneb_fork_epoch = 0;
+
+                for entry in std::fs::read_dir(&base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+
+                    let meta: Meta = {
+                        let content = std::fs::read_to_string(case_dir.join("meta.yaml"))
+                            .expect("cannot find test asset(meta.yaml)");
+                        serde_yaml::from_str(&content).expect("Failed to parse meta.yaml")
+                    };
+                    let steps: Vec<Step> = {
+                        let content = std::fs::read_to_string(case_dir.join("steps.yaml"))
+                            .expect("cannot find test asset(steps.yaml)");
+                        serde_yaml::from_str(&content).expect("Failed to parse steps.yaml")
+                    };
+
+                    // Only Deneb light client data is supported, so skip cases that cross forks
+                    let deneb_fork_digest = ForkData {
+                        current_version: spec.deneb_fork_version,
+                        genesis_validators_root: meta.genesis_validators_root,
+                    }
+                    .compute_fork_digest();
+                    let is_deneb_only = meta.bootstrap_fork_digest == deneb_fork_digest
+                        && meta.store_fork_digest == deneb_fork_digest
+                        && steps.iter().all(|step| match step {
+                            Step::ForceUpdate { .. } => true,
+                            Step::ProcessUpdate {
+                                update_fork_digest, ..
+                            } => *update_fork_digest == deneb_fork_digest,
+                            Step::UpgradeStore { .. } => false,
+                        });
+                    if !is_deneb_only {
+                        println!("Skipping case: {}", case_name);
+                        continue;
+                    }
+                    println!("Testing case: {}", case_name);
+
+                    let bootstrap: LightClientBootstrap<$spec> =
+                        utils::read_ssz_snappy(&case_dir.join("bootstrap.ssz_snappy"))
+                            .expect("cannot find test asset(bootstrap.ssz_snappy)");
+                    let mut store =
+                        initialize_light_client_store(meta.trusted_block_root, &bootstrap, &spec)
+                            .expect("Failed to initialize light client store");
+
+                    for step in steps {
+                        let checks = match step {
+                            Step::ForceUpdate {
+                                current_slot,
+                                checks,
+                            } => {
+                                store
+                                    .process_light_client_store_force_update(current_slot)
+                                    .expect("Force update failed");
+                                checks
+                            }
+                            Step::ProcessUpdate {
+                                update,
+                                current_slot,
+                                checks,
+                                ..
+                            } => {
+                                let update: LightClientUpdate<$spec> = utils::read_ssz_snappy(
+                                    &case_dir.join(format!("{update}.ssz_snappy")),
+                                )
+                                .expect("cannot find test asset(<update>.ssz_snappy)");
+                                store
+                                    .process_light_client_update(
+                                        &update,
+                                        current_slot,
+                                        meta.genesis_validators_root,
+                                        &spec,
+                                    )
+                                    .expect("Processing update failed");
+                                checks
+                            }
+                            Step::UpgradeStore { .. } => unreachable!(),
+                        };
+                        assert_header(&store.finalized_header, &checks.finalized_header, case_name);
+                        assert_header(
+                            &store.optimistic_header,
+                            &checks.optimistic_header,
+                            case_name,
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,66 @@
+#[macro_export]
+macro_rules! test_merkle_proof {
+    ($spec:ty, $runner:ident, $struct_name:ident $(<$generic:ty>)?) => {
+        paste::paste! {
+            #[cfg(test)]
+            #[allow(non_snake_case)]
+            mod [<tests_ $runner _single_merkle_proof_ $struct_name>] {
+                use super::*;
+                use alloy_primitives::B256;
+                use ream_consensus::merkle_proof::compute_merkle_proof_for_generalized_index;
+                use tree_hash::TreeHash;
+
+                #[derive(Debug, serde::Deserialize)]
+                struct SingleMerkleProof {
+                    leaf: B256,
+                    leaf_index: u64,
+                    branch: Vec<B256>,
+                }
+
+                #[test]
+                fn test_single_merkle_proof() {
+                    let base_path = format!(
+                        "{}/deneb/{}/single_merkle_proof/{}",
+                        ef_tests::utils::preset_tests_dir::<$spec>(),
+                        stringify!($runner),
+                        stringify!($struct_name)
+                    );
+
+                    for entry in std::fs::read_dir(&base_path).unwrap() {
+                        let entry = entry.unwrap();
+                        let case_dir = entry.path();
+
+                        if !case_dir.is_dir() {
+                            continue;
+                        }
+
+                        let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                        println!("Testing case: {}", case_name);
+
+                        let object: $struct_name $(<$generic>)? =
+                            utils::read_ssz_snappy(&case_dir.join("object.ssz_snappy"))
+                                .expect("cannot find test asset(object.ssz_snappy)");
+                        let expected: SingleMerkleProof = {
+                            let content = std::fs::read_to_string(case_dir.join("proof.yaml"))
+                                .expect("cannot find test asset(proof.yaml)");
+                            serde_yaml::from_str(&content).expect("Failed to parse proof.yaml")
+                        };
+
+                        let proof =
+                            compute_merkle_proof_for_generalized_index(&object, expected.leaf_index)
+                                .expect("proof generation should not fail");
+                        assert_eq!(proof.leaf, expected.leaf, "Leaf mismatch in case {case_name}");
+                        assert_eq!(
+                            proof.branch, expected.branch,
+                            "Branch mismatch in case {case_name}"
+                        );
+                        assert!(
+                            proof.verify(object.tree_hash_root()),
+                            "Proof does not verify in case {case_name}"
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
@@ -1,3 +1,10 @@
+pub mod bls;
+pub mod deposit_tree;
+pub mod kzg;
+pub mod light_client;
+pub mod merkle_proof;
+pub mod networking;
 pub mod operations;
 pub mod shuffling;
+pub mod slashing_protection;
 pub mod ssz_static;
@@ -0,0 +1,85 @@
+#[macro_export]
+macro_rules! test_networking {
+    ($spec:ty) => {
+        #[cfg(test)]
+        mod tests_networking {
+            use alloy_primitives::U256;
+            use ream_consensus::fulu::das_core::{
+                compute_columns_for_custody_group, get_custody_groups,
+            };
+            use rstest::rstest;
+            use serde::{de::DeserializeOwned, Deserialize};
+
+            #[derive(Debug, Deserialize)]
+            struct GetCustodyGroupsMeta {
+                node_id: String,
+                custody_group_count: u64,
+                result: Vec<u64>,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct ComputeColumnsForCustodyGroupMeta {
+                custody_group: u64,
+                result: Vec<u64>,
+            }
+
+            fn run_cases<T: DeserializeOwned>(handler: &str, run: impl Fn(&str, T)) {
+                let base_path = format!(
+                    "{}/fulu/networking/{handler}/pyspec_tests",
+                    ef_tests::utils::preset_tests_dir::<$spec>()
+                );
+
+                for entry in std::fs::read_dir(&base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+                    println!("Testing case: {}", case_name);
+
+                    let meta: T = {
+                        let content = std::fs::read_to_string(case_dir.join("meta.yaml"))
+                            .expect("Failed to read meta.yaml");
+                        serde_yaml::from_str(&content).expect("Failed to parse meta.yaml")
+                    };
+                    run(case_name, meta);
+                }
+            }
+
+            #[rstest]
+            fn test_get_custody_groups() {
+                run_cases(
+                    "get_custody_groups",
+                    |case_name, meta: GetCustodyGroupsMeta| {
+                        let node_id = U256::from_str_radix(&meta.node_id, 10)
+                            .expect("Failed to parse node_id");
+                        assert_eq!(
+                            get_custody_groups(node_id, meta.custody_group_count)
+                                .expect("get_custody_groups should not fail"),
+                            meta.result,
+                            "Unexpected custody groups in case {case_name}"
+                        );
+                    },
+                );
+            }
+
+            #[rstest]
+            fn test_compute_columns_for_custody_group() {
+                run_cases(
+                    "compute_columns_for_custody_group",
+                    |case_name, meta: ComputeColumnsForCustodyGroupMeta| {
+                        assert_eq!(
+                            compute_columns_for_custody_group(meta.custody_group)
+                                .expect("compute_columns_for_custody_group should not fail"),
+                            meta.result,
+                            "Unexpected columns in case {case_name}"
+                        );
+                    },
+                );
+            }
+        }
+    };
+}
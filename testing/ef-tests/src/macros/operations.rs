@@ -1,8 +1,9 @@
 #[macro_export]
 macro_rules! test_operation_impl {
-    ($operation_name:ident, $operation_object:ty, $input_name:literal, $compute_result:expr) => {{
+    ($spec:ty, $operation_name:ident, $operation_object:ty, $input_name:literal, $compute_result:expr) => {{
         let base_path = format!(
-            "mainnet/tests/mainnet/deneb/operations/{}/pyspec_tests",
+            "{}/deneb/operations/{}/pyspec_tests",
+            utils::preset_tests_dir::<$spec>(),
             stringify!($operation_name)
         );
         for entry in std::fs::read_dir(&base_path).unwrap() {
@@ -14,7 +15,7 @@ macro_rules! test_operation_impl {
             let case_name = case_dir.file_name().unwrap().to_str().unwrap();
             println!("Testing case: {}", case_name);
 
-            let pre_state: Arc<Mutex<BeaconState>> = Arc::new(Mutex::new(
+            let pre_state: Arc<Mutex<BeaconState<$spec>>> = Arc::new(Mutex::new(
                 utils::read_ssz_snappy(&case_dir.join("pre.ssz_snappy"))
                     .expect("cannot find test asset(pre.ssz_snappy)"),
             ));
@@ -22,7 +23,7 @@ macro_rules! test_operation_impl {
                 utils::read_ssz_snappy(&case_dir.join(format!("{}.ssz_snappy", $input_name)))
                     .expect("cannot find test asset(<input>.ssz_snappy)");
             let expected_post =
-                utils::read_ssz_snappy::<BeaconState>(&case_dir.join("post.ssz_snappy"));
+                utils::read_ssz_snappy::<BeaconState<$spec>>(&case_dir.join("post.ssz_snappy"));
             let mut state = pre_state.clone();
 
             // Call the provided closure to compute the result.
@@ -56,8 +57,28 @@ macro_rules! test_operation_impl {
 
 #[macro_export]
 macro_rules! test_operation {
+    // Variant with a processing function that also takes the chain spec.
+    ($spec:ty, $operation_name:ident, $operation_object:ty, $input_name:literal, $processing_fn:path, spec) => {
+        paste::paste! {
+            #[cfg(test)]
+            #[allow(non_snake_case)]
+            mod [<tests_ $processing_fn>] {
+                use super::*;
+                use ef_tests::test_operation_impl;
+                use std::{path::PathBuf, sync::Arc};
+                use tokio::sync::Mutex;
+
+                #[tokio::test]
+                async fn test_operation() {
+                    test_operation_impl!($spec, $operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState<$spec>>>, input: $operation_object, _case_dir: PathBuf| async move {
+                        state.lock().await.$processing_fn(&input, &utils::chain_spec::<$spec>())
+                    });
+                }
+            }
+        }
+    };
     // Variant with a processing function provided.
-    ($operation_name:ident, $operation_object:ty, $input_name:literal, $processing_fn:path) => {
+    ($spec:ty, $operation_name:ident, $operation_object:ty, $input_name:literal, $processing_fn:path) => {
         paste::paste! {
             #[cfg(test)]
             #[allow(non_snake_case)]
@@ -70,7 +91,7 @@ macro_rules! test_operation {
 
                 #[tokio::test]
                 async fn test_operation() {
-                    test_operation_impl!($operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState>>, input: $operation_object, _case_dir: PathBuf| async move {
+                    test_operation_impl!($spec, $operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState<$spec>>>, input: $operation_object, _case_dir: PathBuf| async move {
                         state.lock().await.$processing_fn(&input)
                     });
                 }
@@ -78,7 +99,7 @@ macro_rules! test_operation {
         }
     };
     // Variant that uses process_execution_payload with a mock engine.
-    ($operation_name:ident, $operation_object:ty, $input_name:literal) => {
+    ($spec:ty, $operation_name:ident, $operation_object:ty, $input_name:literal) => {
         #[cfg(test)]
         mod tests_process_execution_payload {
             use super::*;
@@ -89,10 +110,10 @@ macro_rules! test_operation {
 
             #[tokio::test]
             async fn test_operation() {
-                test_operation_impl!($operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState>>, input: $operation_object, case_dir: PathBuf| async move {
+                test_operation_impl!($spec, $operation_name, $operation_object, $input_name, |state: Arc<Mutex<BeaconState<$spec>>>, input: $operation_object, case_dir: PathBuf| async move {
                     let mock_engine = MockExecutionEngine::new(&case_dir.as_path().join("execution.yaml"))
                         .expect("remove result");
-                    state.lock().await.process_execution_payload(&input, &mock_engine).await
+                    state.lock().await.process_execution_payload(&input, &mock_engine, &utils::chain_spec::<$spec>()).await
                 });
             }
         }
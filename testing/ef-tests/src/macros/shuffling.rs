@@ -1,6 +1,6 @@
 #[macro_export]
 macro_rules! test_shuffling {
-    () => {
+    ($spec:ty) => {
         #[cfg(test)]
         mod tests_shuffling {
             use std::str::FromStr;
@@ -19,9 +19,12 @@ macro_rules! test_shuffling {
 
             #[rstest]
             fn test_shuffling() {
-                let base_path = "mainnet/tests/mainnet/phase0/shuffling/core/shuffle";
+                let base_path = format!(
+                    "{}/phase0/shuffling/core/shuffle",
+                    ef_tests::utils::preset_tests_dir::<$spec>()
+                );
 
-                for entry in std::fs::read_dir(base_path).unwrap() {
+                for entry in std::fs::read_dir(&base_path).unwrap() {
                     let entry = entry.unwrap();
                     let case_dir = entry.path();
 
@@ -46,7 +49,7 @@ macro_rules! test_shuffling {
 
                     // Test compute_shuffled_index for each index
                     for i in 0..test_data.count {
-                        let shuffled = compute_shuffled_index(i, test_data.count, seed)
+                        let shuffled = compute_shuffled_index::<$spec>(i, test_data.count, seed)
                             .expect("shuffling should not fail");
                         assert_eq!(
                             shuffled, test_data.mapping[i],
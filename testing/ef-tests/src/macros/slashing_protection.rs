@@ -0,0 +1,129 @@
+#[macro_export]
+macro_rules! test_slashing_protection {
+    () => {
+        #[cfg(test)]
+        mod tests_slashing_protection {
+            use alloy_primitives::B256;
+            use ream_bls::PubKey;
+            use ream_validator::slashing_protection::{
+                interchange::Interchange, SlashingProtectionDatabase,
+            };
+            use serde::Deserialize;
+            use tempfile::TempDir;
+
+            /// An EIP-3076 interchange test, a sequence of imports each followed by signings.
+            #[derive(Debug, Deserialize)]
+            struct TestCase {
+                name: String,
+                genesis_validators_root: B256,
+                steps: Vec<TestStep>,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct TestStep {
+                should_succeed: bool,
+                contains_slashable_data: bool,
+                interchange: Interchange,
+                blocks: Vec<TestBlock>,
+                attestations: Vec<TestAttestation>,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct TestBlock {
+                pubkey: PubKey,
+                #[serde(with = "serde_utils::quoted_u64")]
+                slot: u64,
+                #[serde(default)]
+                signing_root: B256,
+                should_succeed: bool,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct TestAttestation {
+                pubkey: PubKey,
+                #[serde(with = "serde_utils::quoted_u64")]
+                source_epoch: u64,
+                #[serde(with = "serde_utils::quoted_u64")]
+                target_epoch: u64,
+                #[serde(default)]
+                signing_root: B256,
+                should_succeed: bool,
+            }
+
+            #[test]
+            fn test_interchange() {
+                let base_path = "slashing_protection_interchange_tests/tests/generated";
+
+                for entry in std::fs::read_dir(base_path).unwrap() {
+                    let case_path = entry.unwrap().path();
+
+                    let test_case: TestCase = {
+                        let content =
+                            std::fs::read_to_string(&case_path).expect("Failed to read test case");
+                        serde_json::from_str(&content).expect("Failed to parse test case")
+                    };
+                    println!("Testing case: {}", test_case.name);
+
+                    let database_dir = TempDir::new().unwrap();
+                    let database = SlashingProtectionDatabase::open(
+                        &database_dir.path().join("slashing_protection.redb"),
+                        test_case.genesis_validators_root,
+                    )
+                    .expect("Failed to open the slashing protection database");
+
+                    for (index, step) in test_case.steps.iter().enumerate() {
+                        // Importing slashable data may be refused, in which case the signings
+                        // of the step are skipped
+                        if let Err(err) = database.import_interchange(&step.interchange) {
+                            assert!(
+                                !step.should_succeed || step.contains_slashable_data,
+                                "Failed to import step {index} of {}: {err}",
+                                test_case.name
+                            );
+                            continue;
+                        }
+                        assert!(
+                            step.should_succeed,
+                            "Imported invalid step {index} of {}",
+                            test_case.name
+                        );
+
+                        for block in &step.blocks {
+                            let result = database.check_and_insert_block_signing_root(
+                                &block.pubkey,
+                                block.slot,
+                                block.signing_root,
+                            );
+                            assert_eq!(
+                                result.is_ok(),
+                                block.should_succeed,
+                                "Unexpected result for the block at slot {} in step {index} of \
+                                 {}: {result:?}",
+                                block.slot,
+                                test_case.name
+                            );
+                        }
+
+                        for attestation in &step.attestations {
+                            let result = database.check_and_insert_attestation_signing_root(
+                                &attestation.pubkey,
+                                attestation.source_epoch,
+                                attestation.target_epoch,
+                                attestation.signing_root,
+                            );
+                            assert_eq!(
+                                result.is_ok(),
+                                attestation.should_succeed,
+                                "Unexpected result for the attestation from epoch {} to {} in \
+                                 step {index} of {}: {result:?}",
+                                attestation.source_epoch,
+                                attestation.target_epoch,
+                                test_case.name
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
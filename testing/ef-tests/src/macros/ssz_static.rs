@@ -1,6 +1,6 @@
 #[macro_export]
 macro_rules! test_consensus_type {
-    ($struct_name:ident) => {
+    ($spec:ty, $struct_name:ident $(<$generic:ty>)?) => {
         paste::paste! {
             #[cfg(test)]
             #[allow(non_snake_case)]
@@ -22,7 +22,8 @@ macro_rules! test_consensus_type {
                 #[case("case_4")]
                 fn test_type(#[case] case: &str) {
                     let path = format!(
-                        "mainnet/tests/mainnet/deneb/ssz_static/{}/ssz_random/{case}/",
+                        "{}/deneb/ssz_static/{}/ssz_random/{case}/",
+                        ef_tests::utils::preset_tests_dir::<$spec>(),
                         stringify!($struct_name)
                     );
 
@@ -39,7 +40,7 @@ macro_rules! test_consensus_type {
                     let content = {
                         let value = std::fs::read_to_string(format!("{path}value.yaml"))
                             .expect("cannot find test asset");
-                        serde_yaml::from_str::<$struct_name>(&value).unwrap()
+                        serde_yaml::from_str::<$struct_name $(<$generic>)?>(&value).unwrap()
                     };
 
                     // Read and decompress SSZ snappy file
@@ -49,7 +50,7 @@ macro_rules! test_consensus_type {
 
                     // Perform the assertions
                     assert_eq!(ssz, content.as_ssz_bytes());
-                    assert_eq!(content, $struct_name::from_ssz_bytes(&ssz).unwrap());
+                    assert_eq!(content, <$struct_name $(<$generic>)?>::from_ssz_bytes(&ssz).unwrap());
                     assert_eq!(hash_root, content.tree_hash_root());
                 }
             }
@@ -1,6 +1,10 @@
 use std::path::Path;
 
+use alloy_primitives::hex;
+use ream_bls::{BLSSignature, PubKey};
+use ream_consensus::{chain_spec::ChainSpec, eth_spec::EthSpec};
 use snap::raw::Decoder;
+use ssz_types::FixedVector;
 
 pub fn read_ssz_snappy<T: ssz::Decode>(path: &Path) -> Option<T> {
     let ssz_snappy = std::fs::read(path).ok()?;
@@ -8,3 +12,35 @@ pub fn read_ssz_snappy<T: ssz::Decode>(path: &Path) -> Option<T> {
     let ssz = decoder.decompress_vec(&ssz_snappy).unwrap();
     T::from_ssz_bytes(&ssz).ok()
 }
+
+/// Root directory of the consensus spec tests for the preset of `E`, e.g.
+/// `minimal/tests/minimal`.
+pub fn preset_tests_dir<E: EthSpec>() -> String {
+    format!("{0}/tests/{0}", E::PRESET_BASE)
+}
+
+/// The configuration the consensus spec tests of the preset of `E` were generated with.
+pub fn chain_spec<E: EthSpec>() -> ChainSpec {
+    match E::PRESET_BASE {
+        "minimal" => ChainSpec::minimal(),
+        _ => ChainSpec::mainnet(),
+    }
+}
+
+/// Decode a hex encoded ``BLSPubkey`` of the BLS test vectors, rejecting inputs of the wrong
+/// length.
+pub fn pubkey_from_hex(pubkey: &str) -> Result<PubKey, String> {
+    let bytes = hex::decode(pubkey).map_err(|err| err.to_string())?;
+    Ok(PubKey {
+        inner: FixedVector::new(bytes).map_err(|err| format!("{err:?}"))?,
+    })
+}
+
+/// Decode a hex encoded ``BLSSignature`` of the BLS test vectors, rejecting inputs of the wrong
+/// length.
+pub fn signature_from_hex(signature: &str) -> Result<BLSSignature, String> {
+    let bytes = hex::decode(signature).map_err(|err| err.to_string())?;
+    Ok(BLSSignature {
+        inner: FixedVector::new(bytes).map_err(|err| format!("{err:?}"))?,
+    })
+}
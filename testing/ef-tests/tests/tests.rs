@@ -1,104 +1,201 @@
 #![cfg(feature = "ef-tests")]
 
-use ef_tests::{test_consensus_type, test_operation, test_shuffling, utils};
+use ef_tests::{
+    test_bls_aggregate, test_bls_aggregate_verify, test_bls_eth_aggregate_pubkeys,
+    test_bls_eth_fast_aggregate_verify, test_bls_fast_aggregate_verify, test_bls_sign,
+    test_bls_verify, test_consensus_type, test_deposit_tree, test_kzg, test_light_client_sync,
+    test_light_client_update_ranking, test_merkle_proof, test_networking, test_operation,
+    test_shuffling, test_slashing_protection, utils,
+};
 use ream_consensus::{
+    aggregate_and_proof::{AggregateAndProof, SignedAggregateAndProof},
     attestation::Attestation,
     attestation_data::AttestationData,
     attester_slashing::AttesterSlashing,
-    beacon_block_header::BeaconBlockHeader,
+    beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader},
     bls_to_execution_change::{BLSToExecutionChange, SignedBLSToExecutionChange},
     checkpoint::Checkpoint,
     deneb::{
         beacon_block::{BeaconBlock, SignedBeaconBlock},
         beacon_block_body::BeaconBlockBody,
         beacon_state::BeaconState,
+        blob_sidecar::{BlobIdentifier, BlobSidecar},
         execution_payload::ExecutionPayload,
         execution_payload_header::ExecutionPayloadHeader,
     },
     deposit::Deposit,
     deposit_data::DepositData,
     eth_1_data::Eth1Data,
+    eth_spec::{MainnetEthSpec, MinimalEthSpec},
     fork::Fork,
     fork_data::ForkData,
     historical_batch::HistoricalBatch,
     historical_summary::HistoricalSummary,
     indexed_attestation::IndexedAttestation,
+    light_client::{
+        light_client_bootstrap::LightClientBootstrap,
+        light_client_finality_update::LightClientFinalityUpdate,
+        light_client_header::LightClientHeader,
+        light_client_optimistic_update::LightClientOptimisticUpdate,
+        light_client_update::LightClientUpdate,
+    },
     misc::compute_shuffled_index,
     proposer_slashing::ProposerSlashing,
     signing_data::SigningData,
     sync_aggregate::SyncAggregate,
     sync_committee::SyncCommittee,
+    sync_committee_contribution::{
+        ContributionAndProof, SignedContributionAndProof, SyncAggregatorSelectionData,
+        SyncCommitteeContribution, SyncCommitteeMessage,
+    },
     validator::Validator,
     voluntary_exit::{SignedVoluntaryExit, VoluntaryExit},
     withdrawal::Withdrawal,
 };
 
-// Testing consensus types
-test_consensus_type!(Attestation);
-test_consensus_type!(AttestationData);
-test_consensus_type!(AttesterSlashing);
-test_consensus_type!(BeaconBlock);
-test_consensus_type!(BeaconBlockBody);
-test_consensus_type!(BeaconBlockHeader);
-test_consensus_type!(BeaconState);
-test_consensus_type!(BLSToExecutionChange);
-test_consensus_type!(Checkpoint);
-test_consensus_type!(Deposit);
-test_consensus_type!(DepositData);
-test_consensus_type!(ExecutionPayload);
-test_consensus_type!(ExecutionPayloadHeader);
-test_consensus_type!(Eth1Data);
-test_consensus_type!(Fork);
-test_consensus_type!(ForkData);
-test_consensus_type!(HistoricalBatch);
-test_consensus_type!(HistoricalSummary);
-test_consensus_type!(IndexedAttestation);
-test_consensus_type!(ProposerSlashing);
-test_consensus_type!(SignedBeaconBlock);
-test_consensus_type!(SignedBLSToExecutionChange);
-test_consensus_type!(SignedVoluntaryExit);
-test_consensus_type!(SigningData);
-test_consensus_type!(SyncAggregate);
-test_consensus_type!(SyncCommittee);
-test_consensus_type!(Validator);
-test_consensus_type!(VoluntaryExit);
-test_consensus_type!(Withdrawal);
+macro_rules! test_preset {
+    ($preset:ident, $spec:ty) => {
+        mod $preset {
+            use super::*;
+
+            type E = $spec;
+
+            // Testing consensus types
+            test_consensus_type!(E, AggregateAndProof);
+            test_consensus_type!(E, Attestation);
+            test_consensus_type!(E, AttestationData);
+            test_consensus_type!(E, AttesterSlashing);
+            test_consensus_type!(E, BeaconBlock<E>);
+            test_consensus_type!(E, BeaconBlockBody<E>);
+            test_consensus_type!(E, BeaconBlockHeader);
+            test_consensus_type!(E, BeaconState<E>);
+            test_consensus_type!(E, BlobIdentifier);
+            test_consensus_type!(E, BlobSidecar<E>);
+            test_consensus_type!(E, BLSToExecutionChange);
+            test_consensus_type!(E, Checkpoint);
+            test_consensus_type!(E, ContributionAndProof<E>);
+            test_consensus_type!(E, Deposit);
+            test_consensus_type!(E, DepositData);
+            test_consensus_type!(E, ExecutionPayload<E>);
+            test_consensus_type!(E, ExecutionPayloadHeader);
+            test_consensus_type!(E, Eth1Data);
+            test_consensus_type!(E, Fork);
+            test_consensus_type!(E, ForkData);
+            test_consensus_type!(E, HistoricalBatch<E>);
+            test_consensus_type!(E, HistoricalSummary);
+            test_consensus_type!(E, IndexedAttestation);
+            test_consensus_type!(E, LightClientBootstrap<E>);
+            test_consensus_type!(E, LightClientFinalityUpdate<E>);
+            test_consensus_type!(E, LightClientHeader);
+            test_consensus_type!(E, LightClientOptimisticUpdate<E>);
+            test_consensus_type!(E, LightClientUpdate<E>);
+            test_consensus_type!(E, ProposerSlashing);
+            test_consensus_type!(E, SignedAggregateAndProof);
+            test_consensus_type!(E, SignedBeaconBlock<E>);
+            test_consensus_type!(E, SignedBeaconBlockHeader);
+            test_consensus_type!(E, SignedBLSToExecutionChange);
+            test_consensus_type!(E, SignedContributionAndProof<E>);
+            test_consensus_type!(E, SignedVoluntaryExit);
+            test_consensus_type!(E, SigningData);
+            test_consensus_type!(E, SyncAggregate<E>);
+            test_consensus_type!(E, SyncAggregatorSelectionData);
+            test_consensus_type!(E, SyncCommittee<E>);
+            test_consensus_type!(E, SyncCommitteeContribution<E>);
+            test_consensus_type!(E, SyncCommitteeMessage);
+            test_consensus_type!(E, Validator);
+            test_consensus_type!(E, VoluntaryExit);
+            test_consensus_type!(E, Withdrawal);
+
+            // Testing operations for block processing
+            test_operation!(
+                E,
+                attestation,
+                Attestation,
+                "attestation",
+                process_attestation
+            );
+            test_operation!(
+                E,
+                attester_slashing,
+                AttesterSlashing,
+                "attester_slashing",
+                process_attester_slashing,
+                spec
+            );
+            test_operation!(
+                E,
+                block_header,
+                BeaconBlock<E>,
+                "block",
+                process_block_header
+            );
+            test_operation!(
+                E,
+                bls_to_execution_change,
+                SignedBLSToExecutionChange,
+                "address_change",
+                process_bls_to_execution_change,
+                spec
+            );
+            test_operation!(E, deposit, Deposit, "deposit", process_deposit, spec);
+            test_operation!(E, execution_payload, BeaconBlockBody<E>, "body");
+            test_operation!(
+                E,
+                proposer_slashing,
+                ProposerSlashing,
+                "proposer_slashing",
+                process_proposer_slashing,
+                spec
+            );
+            test_operation!(
+                E,
+                voluntary_exit,
+                SignedVoluntaryExit,
+                "voluntary_exit",
+                process_voluntary_exit,
+                spec
+            );
+            test_operation!(
+                E,
+                withdrawals,
+                ExecutionPayload<E>,
+                "execution_payload",
+                process_withdrawals
+            );
+
+            // Testing shuffling
+            test_shuffling!(E);
+
+            // Testing merkle proofs
+            test_merkle_proof!(E, merkle_proof, BeaconBlockBody<E>);
+            test_merkle_proof!(E, light_client, BeaconBlockBody<E>);
+            test_merkle_proof!(E, light_client, BeaconState<E>);
+
+            // Testing light client
+            test_light_client_sync!(E);
+            test_light_client_update_ranking!(E);
+
+            // Testing PeerDAS custody
+            test_networking!(E);
+        }
+    };
+}
+
+test_preset!(mainnet, MainnetEthSpec);
+test_preset!(minimal, MinimalEthSpec);
+
+// Testing BLS and KZG, which are shared by all presets
+test_bls_aggregate!();
+test_bls_aggregate_verify!();
+test_bls_eth_aggregate_pubkeys!();
+test_bls_eth_fast_aggregate_verify!();
+test_bls_fast_aggregate_verify!();
+test_bls_sign!();
+test_bls_verify!();
+test_kzg!();
 
-// Testing operations for block processing
-test_operation!(attestation, Attestation, "attestation", process_attestation);
-test_operation!(
-    attester_slashing,
-    AttesterSlashing,
-    "attester_slashing",
-    process_attester_slashing
-);
-test_operation!(block_header, BeaconBlock, "block", process_block_header);
-test_operation!(
-    bls_to_execution_change,
-    SignedBLSToExecutionChange,
-    "address_change",
-    process_bls_to_execution_change
-);
-test_operation!(deposit, Deposit, "deposit", process_deposit);
-test_operation!(execution_payload, BeaconBlockBody, "body");
-test_operation!(
-    proposer_slashing,
-    ProposerSlashing,
-    "proposer_slashing",
-    process_proposer_slashing
-);
-test_operation!(
-    voluntary_exit,
-    SignedVoluntaryExit,
-    "voluntary_exit",
-    process_voluntary_exit
-);
-test_operation!(
-    withdrawals,
-    ExecutionPayload,
-    "execution_payload",
-    process_withdrawals
-);
+// Testing the EIP-4881 deposit tree
+test_deposit_tree!();
 
-// Testing shuffling
-test_shuffling!();
+// Testing the EIP-3076 slashing protection interchange
+test_slashing_protection!();